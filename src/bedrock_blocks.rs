@@ -0,0 +1,779 @@
+use quartz_nbt::{NbtCompound, NbtTag};
+
+use crate::block_state::BlockState;
+
+/// A fixed Bedrock block state value
+#[derive(Clone, Copy)]
+enum Value {
+    Text(&'static str),
+    Byte(i8),
+    Int(i32),
+}
+
+impl Value {
+    fn to_tag(self) -> NbtTag {
+        match self {
+            Value::Text(text) => NbtTag::String(text.to_string()),
+            Value::Byte(byte) => NbtTag::Byte(byte),
+            Value::Int(int) => NbtTag::Int(int),
+        }
+    }
+
+    fn matches(self, tag: &NbtTag) -> bool {
+        match (self, tag) {
+            (Value::Text(text), NbtTag::String(value)) => text == value,
+            (Value::Byte(byte), NbtTag::Byte(value)) => byte == *value,
+            (Value::Int(int), NbtTag::Int(value)) => int == *value,
+            _ => false,
+        }
+    }
+}
+
+/// How the value of a Java property turns into the value of a Bedrock state and back
+enum Conversion {
+    /// A Java number stored as a Bedrock Int, shifted by the given amount
+    Int(i32),
+    /// A fixed list of Java values and their Bedrock counterparts
+    Table(&'static [(&'static str, Value)]),
+}
+
+/// One Java property converted to one Bedrock state
+struct StateMapping {
+    java: &'static str,
+    bedrock: &'static str,
+    conversion: Conversion,
+}
+
+/// A Java block (optionally narrowed down by some of its property values)
+/// and the Bedrock block it corresponds to
+struct BlockMapping {
+    java: &'static str,
+    java_fixed: &'static [(&'static str, &'static str)],
+    bedrock: &'static str,
+    bedrock_fixed: &'static [(&'static str, Value)],
+    states: &'static [StateMapping],
+}
+
+const BOOL: Conversion = Conversion::Table(&[("false", Value::Byte(0)), ("true", Value::Byte(1))]);
+const INVERTED_BOOL: Conversion =
+    Conversion::Table(&[("false", Value::Byte(1)), ("true", Value::Byte(0))]);
+
+/// Six-way "facing" stored as Bedrock's "facing_direction"
+const FACING: Conversion = Conversion::Table(&[
+    ("down", Value::Int(0)),
+    ("up", Value::Int(1)),
+    ("north", Value::Int(2)),
+    ("south", Value::Int(3)),
+    ("west", Value::Int(4)),
+    ("east", Value::Int(5)),
+]);
+
+/// Horizontal "facing" stored as Bedrock's "direction"
+const DIRECTION: Conversion = Conversion::Table(&[
+    ("south", Value::Int(0)),
+    ("west", Value::Int(1)),
+    ("north", Value::Int(2)),
+    ("east", Value::Int(3)),
+]);
+
+/// Horizontal "facing" of wall mounted blocks stored as Bedrock's "facing_direction"
+const WALL_FACING: Conversion = Conversion::Table(&[
+    ("north", Value::Int(2)),
+    ("south", Value::Int(3)),
+    ("west", Value::Int(4)),
+    ("east", Value::Int(5)),
+]);
+
+/// Horizontal "facing" of wall torches, pointing away from the wall, stored as Bedrock's
+/// "torch_facing_direction", which names the side of the block the torch is attached to
+const TORCH_FACING: Conversion = Conversion::Table(&[
+    ("north", Value::Text("south")),
+    ("south", Value::Text("north")),
+    ("west", Value::Text("east")),
+    ("east", Value::Text("west")),
+]);
+
+/// Horizontal "facing" of wall levers stored as Bedrock's "lever_direction"
+const LEVER_FACING: Conversion = Conversion::Table(&[
+    ("north", Value::Text("north")),
+    ("south", Value::Text("south")),
+    ("west", Value::Text("west")),
+    ("east", Value::Text("east")),
+]);
+
+const REPEATER_STATES: &[StateMapping] = &[
+    StateMapping {
+        java: "delay",
+        bedrock: "repeater_delay",
+        conversion: Conversion::Int(-1),
+    },
+    StateMapping {
+        java: "facing",
+        bedrock: "direction",
+        conversion: DIRECTION,
+    },
+];
+
+const COMPARATOR_STATES: &[StateMapping] = &[
+    StateMapping {
+        java: "facing",
+        bedrock: "direction",
+        conversion: DIRECTION,
+    },
+    StateMapping {
+        java: "mode",
+        bedrock: "output_subtract_bit",
+        conversion: Conversion::Table(&[("compare", Value::Byte(0)), ("subtract", Value::Byte(1))]),
+    },
+    StateMapping {
+        java: "powered",
+        bedrock: "output_lit_bit",
+        conversion: BOOL,
+    },
+];
+
+const WALL_TORCH_STATES: &[StateMapping] = &[StateMapping {
+    java: "facing",
+    bedrock: "torch_facing_direction",
+    conversion: TORCH_FACING,
+}];
+
+const FACING_STATES: &[StateMapping] = &[StateMapping {
+    java: "facing",
+    bedrock: "facing_direction",
+    conversion: FACING,
+}];
+
+const DISPENSER_STATES: &[StateMapping] = &[
+    StateMapping {
+        java: "facing",
+        bedrock: "facing_direction",
+        conversion: FACING,
+    },
+    StateMapping {
+        java: "triggered",
+        bedrock: "triggered_bit",
+        conversion: BOOL,
+    },
+];
+
+const WALL_BUTTON_STATES: &[StateMapping] = &[
+    StateMapping {
+        java: "facing",
+        bedrock: "facing_direction",
+        conversion: WALL_FACING,
+    },
+    StateMapping {
+        java: "powered",
+        bedrock: "button_pressed_bit",
+        conversion: BOOL,
+    },
+];
+
+const BUTTON_STATES: &[StateMapping] = &[StateMapping {
+    java: "powered",
+    bedrock: "button_pressed_bit",
+    conversion: BOOL,
+}];
+
+const LIQUID_STATES: &[StateMapping] = &[StateMapping {
+    java: "level",
+    bedrock: "liquid_depth",
+    conversion: Conversion::Int(0),
+}];
+
+const LEVER_POWERED: StateMapping = StateMapping {
+    java: "powered",
+    bedrock: "open_bit",
+    conversion: BOOL,
+};
+
+/// Blocks whose name or properties differ between the editions, following the
+/// Bedrock 1.20.0 names. The first entry matching a block is used in both directions,
+/// blocks that aren't listed keep their id and lose their properties
+const MAPPINGS: &[BlockMapping] = &[
+    BlockMapping {
+        java: "minecraft:redstone_wire",
+        java_fixed: &[],
+        bedrock: "minecraft:redstone_wire",
+        bedrock_fixed: &[],
+        states: &[StateMapping {
+            java: "power",
+            bedrock: "redstone_signal",
+            conversion: Conversion::Int(0),
+        }],
+    },
+    BlockMapping {
+        java: "minecraft:repeater",
+        java_fixed: &[("powered", "false")],
+        bedrock: "minecraft:unpowered_repeater",
+        bedrock_fixed: &[],
+        states: REPEATER_STATES,
+    },
+    BlockMapping {
+        java: "minecraft:repeater",
+        java_fixed: &[("powered", "true")],
+        bedrock: "minecraft:powered_repeater",
+        bedrock_fixed: &[],
+        states: REPEATER_STATES,
+    },
+    BlockMapping {
+        java: "minecraft:comparator",
+        java_fixed: &[],
+        bedrock: "minecraft:unpowered_comparator",
+        bedrock_fixed: &[],
+        states: COMPARATOR_STATES,
+    },
+    BlockMapping {
+        java: "minecraft:comparator",
+        java_fixed: &[],
+        bedrock: "minecraft:powered_comparator",
+        bedrock_fixed: &[],
+        states: COMPARATOR_STATES,
+    },
+    BlockMapping {
+        java: "minecraft:redstone_torch",
+        java_fixed: &[("lit", "true")],
+        bedrock: "minecraft:redstone_torch",
+        bedrock_fixed: &[("torch_facing_direction", Value::Text("top"))],
+        states: &[],
+    },
+    BlockMapping {
+        java: "minecraft:redstone_torch",
+        java_fixed: &[("lit", "false")],
+        bedrock: "minecraft:unlit_redstone_torch",
+        bedrock_fixed: &[("torch_facing_direction", Value::Text("top"))],
+        states: &[],
+    },
+    BlockMapping {
+        java: "minecraft:redstone_wall_torch",
+        java_fixed: &[("lit", "true")],
+        bedrock: "minecraft:redstone_torch",
+        bedrock_fixed: &[],
+        states: WALL_TORCH_STATES,
+    },
+    BlockMapping {
+        java: "minecraft:redstone_wall_torch",
+        java_fixed: &[("lit", "false")],
+        bedrock: "minecraft:unlit_redstone_torch",
+        bedrock_fixed: &[],
+        states: WALL_TORCH_STATES,
+    },
+    BlockMapping {
+        java: "minecraft:torch",
+        java_fixed: &[],
+        bedrock: "minecraft:torch",
+        bedrock_fixed: &[("torch_facing_direction", Value::Text("top"))],
+        states: &[],
+    },
+    BlockMapping {
+        java: "minecraft:wall_torch",
+        java_fixed: &[],
+        bedrock: "minecraft:torch",
+        bedrock_fixed: &[],
+        states: WALL_TORCH_STATES,
+    },
+    BlockMapping {
+        java: "minecraft:redstone_lamp",
+        java_fixed: &[("lit", "false")],
+        bedrock: "minecraft:redstone_lamp",
+        bedrock_fixed: &[],
+        states: &[],
+    },
+    BlockMapping {
+        java: "minecraft:redstone_lamp",
+        java_fixed: &[("lit", "true")],
+        bedrock: "minecraft:lit_redstone_lamp",
+        bedrock_fixed: &[],
+        states: &[],
+    },
+    BlockMapping {
+        java: "minecraft:lever",
+        java_fixed: &[("face", "wall")],
+        bedrock: "minecraft:lever",
+        bedrock_fixed: &[],
+        states: &[
+            StateMapping {
+                java: "facing",
+                bedrock: "lever_direction",
+                conversion: LEVER_FACING,
+            },
+            LEVER_POWERED,
+        ],
+    },
+    BlockMapping {
+        java: "minecraft:lever",
+        java_fixed: &[("face", "floor")],
+        bedrock: "minecraft:lever",
+        bedrock_fixed: &[],
+        states: &[
+            StateMapping {
+                java: "facing",
+                bedrock: "lever_direction",
+                conversion: Conversion::Table(&[
+                    ("north", Value::Text("up_north_south")),
+                    ("south", Value::Text("up_north_south")),
+                    ("east", Value::Text("up_east_west")),
+                    ("west", Value::Text("up_east_west")),
+                ]),
+            },
+            LEVER_POWERED,
+        ],
+    },
+    BlockMapping {
+        java: "minecraft:lever",
+        java_fixed: &[("face", "ceiling")],
+        bedrock: "minecraft:lever",
+        bedrock_fixed: &[],
+        states: &[
+            StateMapping {
+                java: "facing",
+                bedrock: "lever_direction",
+                conversion: Conversion::Table(&[
+                    ("north", Value::Text("down_north_south")),
+                    ("south", Value::Text("down_north_south")),
+                    ("east", Value::Text("down_east_west")),
+                    ("west", Value::Text("down_east_west")),
+                ]),
+            },
+            LEVER_POWERED,
+        ],
+    },
+    BlockMapping {
+        java: "minecraft:stone_button",
+        java_fixed: &[("face", "wall")],
+        bedrock: "minecraft:stone_button",
+        bedrock_fixed: &[],
+        states: WALL_BUTTON_STATES,
+    },
+    BlockMapping {
+        java: "minecraft:stone_button",
+        java_fixed: &[("face", "floor")],
+        bedrock: "minecraft:stone_button",
+        bedrock_fixed: &[("facing_direction", Value::Int(1))],
+        states: BUTTON_STATES,
+    },
+    BlockMapping {
+        java: "minecraft:stone_button",
+        java_fixed: &[("face", "ceiling")],
+        bedrock: "minecraft:stone_button",
+        bedrock_fixed: &[("facing_direction", Value::Int(0))],
+        states: BUTTON_STATES,
+    },
+    BlockMapping {
+        java: "minecraft:oak_button",
+        java_fixed: &[("face", "wall")],
+        bedrock: "minecraft:wooden_button",
+        bedrock_fixed: &[],
+        states: WALL_BUTTON_STATES,
+    },
+    BlockMapping {
+        java: "minecraft:oak_button",
+        java_fixed: &[("face", "floor")],
+        bedrock: "minecraft:wooden_button",
+        bedrock_fixed: &[("facing_direction", Value::Int(1))],
+        states: BUTTON_STATES,
+    },
+    BlockMapping {
+        java: "minecraft:oak_button",
+        java_fixed: &[("face", "ceiling")],
+        bedrock: "minecraft:wooden_button",
+        bedrock_fixed: &[("facing_direction", Value::Int(0))],
+        states: BUTTON_STATES,
+    },
+    BlockMapping {
+        java: "minecraft:piston",
+        java_fixed: &[],
+        bedrock: "minecraft:piston",
+        bedrock_fixed: &[],
+        states: FACING_STATES,
+    },
+    BlockMapping {
+        java: "minecraft:sticky_piston",
+        java_fixed: &[],
+        bedrock: "minecraft:sticky_piston",
+        bedrock_fixed: &[],
+        states: FACING_STATES,
+    },
+    BlockMapping {
+        java: "minecraft:observer",
+        java_fixed: &[],
+        bedrock: "minecraft:observer",
+        bedrock_fixed: &[],
+        states: &[
+            StateMapping {
+                java: "facing",
+                bedrock: "facing_direction",
+                conversion: FACING,
+            },
+            StateMapping {
+                java: "powered",
+                bedrock: "powered_bit",
+                conversion: BOOL,
+            },
+        ],
+    },
+    BlockMapping {
+        java: "minecraft:dispenser",
+        java_fixed: &[],
+        bedrock: "minecraft:dispenser",
+        bedrock_fixed: &[],
+        states: DISPENSER_STATES,
+    },
+    BlockMapping {
+        java: "minecraft:dropper",
+        java_fixed: &[],
+        bedrock: "minecraft:dropper",
+        bedrock_fixed: &[],
+        states: DISPENSER_STATES,
+    },
+    BlockMapping {
+        java: "minecraft:hopper",
+        java_fixed: &[],
+        bedrock: "minecraft:hopper",
+        bedrock_fixed: &[],
+        states: &[
+            StateMapping {
+                java: "facing",
+                bedrock: "facing_direction",
+                conversion: FACING,
+            },
+            StateMapping {
+                java: "enabled",
+                bedrock: "toggle_bit",
+                conversion: INVERTED_BOOL,
+            },
+        ],
+    },
+    BlockMapping {
+        java: "minecraft:barrel",
+        java_fixed: &[],
+        bedrock: "minecraft:barrel",
+        bedrock_fixed: &[],
+        states: &[
+            StateMapping {
+                java: "facing",
+                bedrock: "facing_direction",
+                conversion: FACING,
+            },
+            StateMapping {
+                java: "open",
+                bedrock: "open_bit",
+                conversion: BOOL,
+            },
+        ],
+    },
+    BlockMapping {
+        java: "minecraft:daylight_detector",
+        java_fixed: &[("inverted", "false")],
+        bedrock: "minecraft:daylight_detector",
+        bedrock_fixed: &[],
+        states: &[StateMapping {
+            java: "power",
+            bedrock: "redstone_signal",
+            conversion: Conversion::Int(0),
+        }],
+    },
+    BlockMapping {
+        java: "minecraft:daylight_detector",
+        java_fixed: &[("inverted", "true")],
+        bedrock: "minecraft:daylight_detector_inverted",
+        bedrock_fixed: &[],
+        states: &[StateMapping {
+            java: "power",
+            bedrock: "redstone_signal",
+            conversion: Conversion::Int(0),
+        }],
+    },
+    BlockMapping {
+        java: "minecraft:water",
+        java_fixed: &[],
+        bedrock: "minecraft:water",
+        bedrock_fixed: &[],
+        states: LIQUID_STATES,
+    },
+    BlockMapping {
+        java: "minecraft:lava",
+        java_fixed: &[],
+        bedrock: "minecraft:lava",
+        bedrock_fixed: &[],
+        states: LIQUID_STATES,
+    },
+    BlockMapping {
+        java: "minecraft:note_block",
+        java_fixed: &[],
+        bedrock: "minecraft:noteblock",
+        bedrock_fixed: &[],
+        states: &[],
+    },
+    BlockMapping {
+        java: "minecraft:slime_block",
+        java_fixed: &[],
+        bedrock: "minecraft:slime",
+        bedrock_fixed: &[],
+        states: &[],
+    },
+    BlockMapping {
+        java: "minecraft:grass_block",
+        java_fixed: &[],
+        bedrock: "minecraft:grass",
+        bedrock_fixed: &[],
+        states: &[],
+    },
+    BlockMapping {
+        java: "minecraft:terracotta",
+        java_fixed: &[],
+        bedrock: "minecraft:hardened_clay",
+        bedrock_fixed: &[],
+        states: &[],
+    },
+    BlockMapping {
+        java: "minecraft:stone",
+        java_fixed: &[],
+        bedrock: "minecraft:stone",
+        bedrock_fixed: &[("stone_type", Value::Text("stone"))],
+        states: &[],
+    },
+    BlockMapping {
+        java: "minecraft:granite",
+        java_fixed: &[],
+        bedrock: "minecraft:stone",
+        bedrock_fixed: &[("stone_type", Value::Text("granite"))],
+        states: &[],
+    },
+    BlockMapping {
+        java: "minecraft:polished_granite",
+        java_fixed: &[],
+        bedrock: "minecraft:stone",
+        bedrock_fixed: &[("stone_type", Value::Text("granite_smooth"))],
+        states: &[],
+    },
+    BlockMapping {
+        java: "minecraft:diorite",
+        java_fixed: &[],
+        bedrock: "minecraft:stone",
+        bedrock_fixed: &[("stone_type", Value::Text("diorite"))],
+        states: &[],
+    },
+    BlockMapping {
+        java: "minecraft:polished_diorite",
+        java_fixed: &[],
+        bedrock: "minecraft:stone",
+        bedrock_fixed: &[("stone_type", Value::Text("diorite_smooth"))],
+        states: &[],
+    },
+    BlockMapping {
+        java: "minecraft:andesite",
+        java_fixed: &[],
+        bedrock: "minecraft:stone",
+        bedrock_fixed: &[("stone_type", Value::Text("andesite"))],
+        states: &[],
+    },
+    BlockMapping {
+        java: "minecraft:polished_andesite",
+        java_fixed: &[],
+        bedrock: "minecraft:stone",
+        bedrock_fixed: &[("stone_type", Value::Text("andesite_smooth"))],
+        states: &[],
+    },
+    BlockMapping {
+        java: "minecraft:oak_planks",
+        java_fixed: &[],
+        bedrock: "minecraft:planks",
+        bedrock_fixed: &[("wood_type", Value::Text("oak"))],
+        states: &[],
+    },
+    BlockMapping {
+        java: "minecraft:spruce_planks",
+        java_fixed: &[],
+        bedrock: "minecraft:planks",
+        bedrock_fixed: &[("wood_type", Value::Text("spruce"))],
+        states: &[],
+    },
+    BlockMapping {
+        java: "minecraft:birch_planks",
+        java_fixed: &[],
+        bedrock: "minecraft:planks",
+        bedrock_fixed: &[("wood_type", Value::Text("birch"))],
+        states: &[],
+    },
+    BlockMapping {
+        java: "minecraft:jungle_planks",
+        java_fixed: &[],
+        bedrock: "minecraft:planks",
+        bedrock_fixed: &[("wood_type", Value::Text("jungle"))],
+        states: &[],
+    },
+    BlockMapping {
+        java: "minecraft:acacia_planks",
+        java_fixed: &[],
+        bedrock: "minecraft:planks",
+        bedrock_fixed: &[("wood_type", Value::Text("acacia"))],
+        states: &[],
+    },
+    BlockMapping {
+        java: "minecraft:dark_oak_planks",
+        java_fixed: &[],
+        bedrock: "minecraft:planks",
+        bedrock_fixed: &[("wood_type", Value::Text("dark_oak"))],
+        states: &[],
+    },
+];
+
+/// Java dye colors and their Bedrock names
+const COLORS: &[(&str, &str)] = &[
+    ("white", "white"),
+    ("orange", "orange"),
+    ("magenta", "magenta"),
+    ("light_blue", "light_blue"),
+    ("yellow", "yellow"),
+    ("lime", "lime"),
+    ("pink", "pink"),
+    ("gray", "gray"),
+    ("light_gray", "silver"),
+    ("cyan", "cyan"),
+    ("purple", "purple"),
+    ("blue", "blue"),
+    ("brown", "brown"),
+    ("green", "green"),
+    ("red", "red"),
+    ("black", "black"),
+];
+
+/// Java blocks that exist in every color, and the single Bedrock block
+/// that holds the color in its "color" state
+const COLORED_BLOCKS: &[(&str, &str)] = &[
+    ("concrete", "minecraft:concrete"),
+    ("concrete_powder", "minecraft:concrete_powder"),
+    ("stained_glass", "minecraft:stained_glass"),
+    ("stained_glass_pane", "minecraft:stained_glass_pane"),
+    ("terracotta", "minecraft:stained_hardened_clay"),
+    ("shulker_box", "minecraft:shulker_box"),
+];
+
+/// Converts a full Java block id into the name and states of the corresponding Bedrock block
+pub fn java_to_bedrock(block: &str) -> (String, NbtCompound) {
+    let state = BlockState::parse(block);
+
+    for mapping in MAPPINGS.iter().filter(|mapping| mapping.java == state.id) {
+        let selected = mapping
+            .java_fixed
+            .iter()
+            .all(|&(key, value)| state.property(key).unwrap_or(value) == value);
+        if !selected {
+            continue;
+        }
+
+        let mut states = NbtCompound::new();
+        for &(key, value) in mapping.bedrock_fixed {
+            states.insert(key, value.to_tag());
+        }
+
+        let converted = mapping.states.iter().all(|mapping| {
+            let Some(value) = state.property(mapping.java) else {
+                return true;
+            };
+            match to_bedrock_value(&mapping.conversion, value) {
+                Some(tag) => {
+                    states.insert(mapping.bedrock, tag);
+                    true
+                }
+                None => false,
+            }
+        });
+        if converted {
+            return (mapping.bedrock.to_string(), states);
+        }
+    }
+
+    let path = state.id.trim_start_matches("minecraft:");
+    for &(java_color, bedrock_color) in COLORS {
+        let Some(base) = path
+            .strip_prefix(java_color)
+            .and_then(|base| base.strip_prefix('_'))
+        else {
+            continue;
+        };
+        if let Some(&(_, bedrock)) = COLORED_BLOCKS.iter().find(|&&(java, _)| java == base) {
+            let mut states = NbtCompound::new();
+            states.insert("color", bedrock_color);
+            return (bedrock.to_string(), states);
+        }
+    }
+
+    (state.id, NbtCompound::new())
+}
+
+/// Converts the name and states of a Bedrock block into a full Java block id
+pub fn bedrock_to_java(name: &str, states: &NbtCompound) -> String {
+    for mapping in MAPPINGS.iter().filter(|mapping| mapping.bedrock == name) {
+        let selected =
+            mapping
+                .bedrock_fixed
+                .iter()
+                .all(|&(key, value)| match states.inner().get(key) {
+                    Some(tag) => value.matches(tag),
+                    None => true,
+                });
+        if !selected {
+            continue;
+        }
+
+        let mut state = BlockState::parse(mapping.java);
+        for &(key, value) in mapping.java_fixed {
+            state.set_property(key, value);
+        }
+
+        let converted = mapping.states.iter().all(|mapping| {
+            let Some(tag) = states.inner().get(mapping.bedrock) else {
+                return true;
+            };
+            match to_java_value(&mapping.conversion, tag) {
+                Some(value) => {
+                    state.set_property(mapping.java, &value);
+                    true
+                }
+                None => false,
+            }
+        });
+        if converted {
+            return state.to_string();
+        }
+    }
+
+    if let Some(&(base, _)) = COLORED_BLOCKS.iter().find(|&&(_, bedrock)| bedrock == name) {
+        if let Ok(bedrock_color) = states.get::<_, &str>("color") {
+            if let Some(&(java_color, _)) =
+                COLORS.iter().find(|&&(_, color)| color == bedrock_color)
+            {
+                return format!("minecraft:{}_{}", java_color, base);
+            }
+        }
+    }
+
+    name.to_string()
+}
+
+fn to_bedrock_value(conversion: &Conversion, value: &str) -> Option<NbtTag> {
+    match conversion {
+        Conversion::Int(shift) => value
+            .parse::<i32>()
+            .ok()
+            .map(|int| NbtTag::Int(int + shift)),
+        Conversion::Table(table) => table
+            .iter()
+            .find(|&&(java, _)| java == value)
+            .map(|&(_, bedrock)| bedrock.to_tag()),
+    }
+}
+
+fn to_java_value(conversion: &Conversion, tag: &NbtTag) -> Option<String> {
+    match (conversion, tag) {
+        (Conversion::Int(shift), NbtTag::Int(int)) => Some((int - shift).to_string()),
+        (Conversion::Table(table), tag) => table
+            .iter()
+            .find(|&&(_, bedrock)| bedrock.matches(tag))
+            .map(|&(java, _)| java.to_string()),
+        _ => None,
+    }
+}
@@ -0,0 +1,254 @@
+use std::{collections::HashMap, sync::OnceLock};
+
+use crate::block_state::BlockState;
+use crate::versions::McVersion;
+
+/// The embedded list of blocks, see the header of the file for its format
+const REGISTRY: &str = include_str!("block_registry.txt");
+
+/// Everything the registry knows about a block
+#[derive(Debug)]
+pub struct BlockInfo {
+    pub id: String,
+    /// DataVersion of the first release containing the block, under any of its names
+    pub since: i32,
+    /// Every property the block has in the latest version
+    pub properties: Vec<PropertyInfo>,
+}
+
+/// A property of a block, along with the versions it and its values exist in
+#[derive(Debug)]
+pub struct PropertyInfo {
+    pub name: String,
+    /// DataVersion of the first release where the block has the property
+    pub since: i32,
+    /// Allowed values along with the DataVersion of the first release allowing them,
+    /// the first value being the default
+    pub values: Vec<(String, i32)>,
+}
+
+impl PropertyInfo {
+    /// Returns true if the block has the property in the given version
+    pub fn exists_in(&self, version: McVersion) -> bool {
+        self.since <= version.data_version()
+    }
+
+    /// Returns the values the property allows in the given version
+    pub fn values_in(&self, version: McVersion) -> Vec<&str> {
+        self.values
+            .iter()
+            .filter(|&&(_, since)| since <= version.data_version())
+            .map(|(value, _)| value.as_str())
+            .collect()
+    }
+
+    /// Returns the value the property has when it isn't specified
+    pub fn default_value(&self) -> &str {
+        &self.values[0].0
+    }
+}
+
+impl BlockInfo {
+    /// Returns true if the block can be placed in the given version
+    pub fn exists_in(&self, version: McVersion) -> bool {
+        self.since <= version.data_version()
+    }
+
+    /// Returns a property of the block, or None if the block doesn't have it in the given version
+    pub fn property(&self, name: &str, version: McVersion) -> Option<&PropertyInfo> {
+        self.properties
+            .iter()
+            .find(|property| property.name == name && property.exists_in(version))
+    }
+
+    /// Returns the allowed values of a property in the given version,
+    /// or None if the block doesn't have it in that version
+    pub fn allowed_values(&self, property: &str, version: McVersion) -> Option<Vec<&str>> {
+        self.property(property, version)
+            .map(|property| property.values_in(version))
+    }
+
+    /// Returns the value a property has when it isn't specified, or None if the block
+    /// doesn't have it in the given version
+    pub fn default_value(&self, property: &str, version: McVersion) -> Option<&str> {
+        self.property(property, version)
+            .map(|property| property.default_value())
+    }
+}
+
+/// Looks a block up by its id, such as "minecraft:repeater". Blocks are known by their
+/// current names, the names older versions use are handled by the renames module
+pub fn block_info(id: &str) -> Option<&'static BlockInfo> {
+    static BLOCKS: OnceLock<HashMap<String, BlockInfo>> = OnceLock::new();
+
+    BLOCKS.get_or_init(parse_registry).get(id)
+}
+
+/// How many properties a block is written with
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PropertyForm {
+    /// The properties exactly as they were given
+    #[default]
+    AsWritten,
+    /// Every property of the block, missing ones filled in with their defaults
+    Full,
+    /// Only the properties that differ from their defaults
+    Minimal,
+}
+
+/// Rewrites a block in the given property form, using the properties the block has in the
+/// given version and leaving out the ones it only gets later. Properties come out in the
+/// order of the registry, followed by any the registry doesn't know, so equal blocks end up
+/// with equal names. Blocks that aren't in the registry are left as they are
+pub fn normalize_properties(block: &str, form: PropertyForm, version: McVersion) -> String {
+    if form == PropertyForm::AsWritten {
+        return block.to_string();
+    }
+
+    let state = BlockState::parse(block);
+    let Some(info) = block_info(&state.id) else {
+        return block.to_string();
+    };
+
+    let mut properties = vec![];
+    for property in info.properties.iter().filter(|p| p.exists_in(version)) {
+        let default = property.default_value();
+        let value = state.property(&property.name).unwrap_or(default);
+        if form == PropertyForm::Full || value != default {
+            properties.push((property.name.clone(), value.to_string()));
+        }
+    }
+    for (name, value) in state.properties.iter() {
+        if !info
+            .properties
+            .iter()
+            .any(|property| property.name == *name)
+        {
+            properties.push((name.clone(), value.clone()));
+        }
+    }
+
+    BlockState {
+        id: state.id,
+        properties,
+    }
+    .to_string()
+}
+
+fn parse_registry() -> HashMap<String, BlockInfo> {
+    let mut blocks = HashMap::new();
+
+    for line in REGISTRY
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+    {
+        let mut parts = line.split(' ');
+        let id = format!("minecraft:{}", parts.next().unwrap_or_default());
+        let since = parts
+            .next()
+            .and_then(|since| since.parse().ok())
+            .unwrap_or(0);
+
+        let properties = parts
+            .filter_map(|property| property.split_once('='))
+            .map(|(name, values)| {
+                let (name, since) = split_since(name, since);
+                PropertyInfo {
+                    name: name.to_string(),
+                    since,
+                    values: values
+                        .split(',')
+                        .map(|value| {
+                            let (value, since) = split_since(value, since);
+                            (value.to_string(), since)
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+
+        blocks.insert(
+            id.clone(),
+            BlockInfo {
+                id,
+                since,
+                properties,
+            },
+        );
+    }
+
+    blocks
+}
+
+/// Splits the DataVersion off an entry like "bit@1952", falling back to the given one
+fn split_since(entry: &str, since: i32) -> (&str, i32) {
+    match entry.split_once('@') {
+        Some((entry, entry_since)) => (entry, entry_since.parse().unwrap_or(since)),
+        None => (entry, since),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::versions::{JE_1_15_2, JE_1_16, JE_1_20_1};
+
+    #[test]
+    fn reads_properties_and_the_versions_adding_them() {
+        let lantern = block_info("minecraft:lantern").unwrap();
+        assert_eq!(lantern.since, 1952);
+        assert_eq!(lantern.default_value("hanging", JE_1_15_2), Some("false"));
+        assert!(lantern.property("waterlogged", JE_1_15_2).is_none());
+        assert_eq!(
+            lantern.allowed_values("waterlogged", JE_1_16),
+            Some(vec!["false", "true"])
+        );
+        assert!(block_info("minecraft:stonee").is_none());
+    }
+
+    #[test]
+    fn writes_full_and_minimal_properties_in_registry_order() {
+        let repeater = "minecraft:repeater[powered=true,facing=east,delay=1]";
+        assert_eq!(
+            normalize_properties(repeater, PropertyForm::Full, JE_1_20_1),
+            "minecraft:repeater[delay=1,facing=east,locked=false,powered=true]"
+        );
+        assert_eq!(
+            normalize_properties(repeater, PropertyForm::Minimal, JE_1_20_1),
+            "minecraft:repeater[facing=east,powered=true]"
+        );
+        assert_eq!(
+            normalize_properties(repeater, PropertyForm::AsWritten, JE_1_20_1),
+            repeater
+        );
+    }
+
+    #[test]
+    fn leaves_out_properties_the_version_doesnt_have() {
+        let lantern = "minecraft:lantern[waterlogged=true,hanging=true]";
+        assert_eq!(
+            normalize_properties(lantern, PropertyForm::Full, JE_1_15_2),
+            "minecraft:lantern[hanging=true]"
+        );
+        assert_eq!(
+            normalize_properties(lantern, PropertyForm::Full, JE_1_16),
+            "minecraft:lantern[hanging=true,waterlogged=true]"
+        );
+    }
+
+    #[test]
+    fn keeps_unknown_blocks_and_properties() {
+        assert_eq!(
+            normalize_properties("minecraft:stonee[a=b]", PropertyForm::Full, JE_1_20_1),
+            "minecraft:stonee[a=b]"
+        );
+        assert_eq!(
+            normalize_properties(
+                "minecraft:lantern[color=red]",
+                PropertyForm::Minimal,
+                JE_1_20_1
+            ),
+            "minecraft:lantern[color=red]"
+        );
+    }
+}
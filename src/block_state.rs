@@ -0,0 +1,286 @@
+use std::fmt;
+
+/// A block id split into its parts, such as "minecraft:repeater[delay=1,facing=north]"
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockState {
+    pub id: String,
+    pub properties: Vec<(String, String)>,
+}
+
+impl BlockState {
+    /// Splits a full in-game block id into the id and its list of properties.
+    /// Ids without a namespace get "minecraft:"
+    pub fn parse(block: &str) -> BlockState {
+        let block = block.trim();
+        let (id, properties) = match block.split_once('[') {
+            Some((id, properties)) => (id, properties.trim_end_matches(']')),
+            None => (block, ""),
+        };
+
+        BlockState {
+            id: namespaced(id),
+            properties: parse_properties(properties),
+        }
+    }
+
+    /// Returns the value of a property, if the block state has it
+    pub fn property(&self, name: &str) -> Option<&str> {
+        self.properties
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Sets the value of a property, adding it if the block state doesn't have it yet
+    pub fn set_property(&mut self, name: &str, value: &str) {
+        match self.properties.iter_mut().find(|(key, _)| key == name) {
+            Some((_, old_value)) => *old_value = value.to_string(),
+            None => self.properties.push((name.to_string(), value.to_string())),
+        }
+    }
+
+    /// Returns the block state turned clockwise (seen from above) by the given
+    /// number of quarter turns, rotating its facing, axis, rotation, shape and
+    /// per-side connection properties
+    pub fn rotate_y(&self, quarter_turns: u8) -> BlockState {
+        let turns = quarter_turns % 4;
+        let mut rotated = self.clone();
+
+        for (key, value) in rotated.properties.iter_mut() {
+            let new_value = match key.as_str() {
+                "facing" | "horizontal_facing" => rotate_direction(value, turns).to_string(),
+                "axis" if turns % 2 == 1 => match value.as_str() {
+                    "x" => "z".to_string(),
+                    "z" => "x".to_string(),
+                    _ => value.clone(),
+                },
+                "rotation" => match value.parse::<u8>() {
+                    Ok(rotation) => ((rotation + 4 * turns) % 16).to_string(),
+                    Err(_) => value.clone(),
+                },
+                "shape" => rotate_shape(value, turns),
+                _ => value.clone(),
+            };
+            *value = new_value;
+        }
+
+        // Per-side properties such as the connections of fences or redstone wire move to the rotated side
+        for (key, _) in rotated.properties.iter_mut() {
+            if matches!(key.as_str(), "north" | "east" | "south" | "west") {
+                *key = rotate_direction(key, turns).to_string();
+            }
+        }
+
+        rotated
+    }
+}
+
+/// Turns a horizontal direction clockwise (seen from above) by the given number of
+/// quarter turns, other values such as "up" are returned unchanged
+fn rotate_direction(direction: &str, quarter_turns: u8) -> &str {
+    const DIRECTIONS: [&str; 4] = ["north", "east", "south", "west"];
+
+    match DIRECTIONS.iter().position(|&d| d == direction) {
+        Some(index) => DIRECTIONS[(index + quarter_turns as usize) % 4],
+        None => direction,
+    }
+}
+
+/// Rotates a shape made of directions, such as the "north_east" or "ascending_west" of rails,
+/// writing north or south first as the game does. Other shapes, such as the "inner_left"
+/// of stairs, don't change with the rotation
+fn rotate_shape(shape: &str, quarter_turns: u8) -> String {
+    let parts: Vec<&str> = shape
+        .split('_')
+        .map(|part| rotate_direction(part, quarter_turns))
+        .collect();
+
+    match parts.as_slice() {
+        ["ascending", direction] => format!("ascending_{}", direction),
+        [a, b] if [*a, *b].iter().all(|d| matches!(*d, "north" | "south")) => {
+            "north_south".to_string()
+        }
+        [a, b] if [*a, *b].iter().all(|d| matches!(*d, "east" | "west")) => "east_west".to_string(),
+        [a @ ("east" | "west"), b @ ("north" | "south")] => format!("{}_{}", b, a),
+        _ => parts.join("_"),
+    }
+}
+
+impl fmt::Display for BlockState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.id)?;
+
+        if !self.properties.is_empty() {
+            let properties: Vec<String> = self
+                .properties
+                .iter()
+                .map(|(key, value)| format!("{}={}", key, value))
+                .collect();
+            write!(f, "[{}]", properties.join(","))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Selects blocks by id and properties, both of which may contain "*" wildcards,
+/// such as "*_planks" or "minecraft:repeater[delay=*]".
+/// Properties that aren't listed can have any value
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockMatcher {
+    id: String,
+    properties: Vec<(String, String)>,
+}
+
+impl BlockMatcher {
+    /// Parses a matcher. Ids without a namespace get "minecraft:", except for a lone "*"
+    pub fn parse(input: &str) -> BlockMatcher {
+        let state = BlockState::parse(input);
+        let id = match state.id.as_str() {
+            "minecraft:*" if !input.contains(':') => "*".to_string(),
+            _ => state.id,
+        };
+
+        BlockMatcher {
+            id,
+            properties: state.properties,
+        }
+    }
+
+    /// Returns true if the given full in-game block id is selected by this matcher
+    pub fn matches(&self, block: &str) -> bool {
+        let state = BlockState::parse(block);
+
+        glob_matches(&self.id, &state.id)
+            && self
+                .properties
+                .iter()
+                .all(|(key, pattern)| match state.property(key) {
+                    Some(value) => glob_matches(pattern, value),
+                    None => false,
+                })
+    }
+}
+
+/// Applies a block from a pattern on top of an existing block. Blocks consisting only
+/// of properties, such as "[delay=1]", update those properties of the existing block,
+/// any other block simply replaces it
+pub fn apply_block(existing: &str, new: &str) -> String {
+    let new = new.trim();
+    if !new.starts_with('[') {
+        return new.to_string();
+    }
+
+    let mut state = BlockState::parse(existing);
+    for (key, value) in parse_properties(new.trim_start_matches('[').trim_end_matches(']')) {
+        state.set_property(&key, &value);
+    }
+
+    state.to_string()
+}
+
+/// Prepends the "minecraft:" namespace to block ids that don't have one,
+/// keeping the properties that may follow the id
+pub(crate) fn namespaced(block: &str) -> String {
+    let id = block.split('[').next().unwrap_or(block);
+    match id.contains(':') {
+        true => block.to_string(),
+        false => format!("minecraft:{}", block),
+    }
+}
+
+/// Parses a comma separated list of key=value pairs
+fn parse_properties(properties: &str) -> Vec<(String, String)> {
+    properties
+        .split(',')
+        .filter_map(|property| property.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
+
+/// Matches text against a pattern where "*" stands for any sequence of characters
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    if parts.len() == 1 {
+        return pattern == text;
+    }
+
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if !text.starts_with(first) || text.len() < first.len() + last.len() || !text.ends_with(last) {
+        return false;
+    }
+
+    let mut rest = &text[first.len()..text.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_ids_with_wildcards() {
+        let planks = BlockMatcher::parse("*_planks");
+        assert!(planks.matches("minecraft:oak_planks"));
+        assert!(planks.matches("spruce_planks"));
+        assert!(!planks.matches("minecraft:oak_log"));
+
+        let any = BlockMatcher::parse("*");
+        assert!(any.matches("minecraft:air"));
+        assert!(any.matches("create:cogwheel[axis=x]"));
+
+        let modded = BlockMatcher::parse("create:*");
+        assert!(modded.matches("create:cogwheel"));
+        assert!(!modded.matches("minecraft:stone"));
+    }
+
+    #[test]
+    fn matches_listed_properties_only() {
+        let repeaters = BlockMatcher::parse("repeater[delay=*,facing=north]");
+        assert!(repeaters.matches("minecraft:repeater[delay=3,facing=north,locked=false]"));
+        assert!(!repeaters.matches("minecraft:repeater[delay=3,facing=east]"));
+        assert!(!repeaters.matches("minecraft:repeater[facing=north]"));
+        assert!(!repeaters.matches("minecraft:comparator[delay=1,facing=north]"));
+    }
+
+    #[test]
+    fn glob_needs_every_part_in_order() {
+        assert!(glob_matches("a*b*c", "a-b-c"));
+        assert!(glob_matches("a*b*c", "abc"));
+        assert!(!glob_matches("a*b*c", "a-c-b"));
+        assert!(!glob_matches("ab*ba", "aba"));
+        assert!(glob_matches("**", ""));
+    }
+
+    #[test]
+    fn applies_blocks_and_properties() {
+        let repeater = "minecraft:repeater[delay=1,facing=north]";
+        assert_eq!(apply_block(repeater, "minecraft:stone"), "minecraft:stone");
+        assert_eq!(
+            apply_block(repeater, "[delay=4,locked=true]"),
+            "minecraft:repeater[delay=4,facing=north,locked=true]"
+        );
+        assert_eq!(apply_block("stone", "[]"), "minecraft:stone");
+    }
+
+    #[test]
+    fn parses_and_prints_states() {
+        let state = BlockState::parse(" lever[face=floor, powered=true] ");
+        assert_eq!(state.id, "minecraft:lever");
+        assert_eq!(state.property("powered"), Some("true"));
+        assert_eq!(state.property("facing"), None);
+        assert_eq!(
+            state.to_string(),
+            "minecraft:lever[face=floor,powered=true]"
+        );
+        assert_eq!(namespaced("create:shaft[axis=y]"), "create:shaft[axis=y]");
+        assert_eq!(namespaced("sign[text=a:b]"), "minecraft:sign[text=a:b]");
+    }
+}
@@ -0,0 +1,371 @@
+use quartz_nbt::{compound, NbtCompound, NbtTag};
+
+use crate::mcschematic::signs::TextComponent;
+use crate::mcschematic::{Coords, MCSchematic};
+use crate::versions::{McVersion, JE_1_13, JE_1_14, JE_1_20_1};
+
+/// Directions a command block can face
+const FACINGS: [&str; 6] = ["down", "up", "north", "east", "south", "west"];
+
+/// How a command block runs its command
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CommandBlockKind {
+    /// Runs once every time it gets powered
+    #[default]
+    Impulse,
+    /// Runs after the command block pointing into it ran
+    Chain,
+    /// Runs every game tick while powered
+    Repeating,
+}
+
+impl CommandBlockKind {
+    /// Returns the in-game id of the block
+    pub fn block_id(self) -> &'static str {
+        match self {
+            CommandBlockKind::Impulse => "minecraft:command_block",
+            CommandBlockKind::Chain => "minecraft:chain_command_block",
+            CommandBlockKind::Repeating => "minecraft:repeating_command_block",
+        }
+    }
+}
+
+/// A command block of a chain, see MCSchematic::place_command_chain
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CommandBlock {
+    pub kind: CommandBlockKind,
+    /// The command without a leading slash, such as "say hi"
+    pub command: String,
+    /// Only run if the command block behind this one succeeded
+    pub conditional: bool,
+    /// Run without being powered by redstone ("Always Active" in the game)
+    pub auto: bool,
+    /// Name shown as the sender of the command's messages instead of "@"
+    pub name: Option<String>,
+}
+
+impl CommandBlock {
+    /// Returns an impulse command block, which needs redstone to run
+    pub fn impulse(command: &str) -> CommandBlock {
+        CommandBlock {
+            kind: CommandBlockKind::Impulse,
+            command: command.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns an always active chain command block
+    pub fn chain(command: &str) -> CommandBlock {
+        CommandBlock {
+            kind: CommandBlockKind::Chain,
+            command: command.to_string(),
+            auto: true,
+            ..Default::default()
+        }
+    }
+
+    /// Returns a repeating command block, which needs redstone to run
+    pub fn repeating(command: &str) -> CommandBlock {
+        CommandBlock {
+            kind: CommandBlockKind::Repeating,
+            command: command.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the command block made conditional, see CommandBlock::conditional
+    pub fn conditional(mut self) -> CommandBlock {
+        self.conditional = true;
+        self
+    }
+
+    /// Returns the command block made always active, see CommandBlock::auto
+    pub fn always_active(mut self) -> CommandBlock {
+        self.auto = true;
+        self
+    }
+
+    /// Returns the command block with the given name, see CommandBlock::name
+    pub fn named(mut self, name: &str) -> CommandBlock {
+        self.name = Some(name.to_string());
+        self
+    }
+}
+
+/// Options for MCSchematic::place_command_chain
+#[derive(Clone, Debug)]
+pub struct CommandChainOptions {
+    /// The version the chain gets saved for, which decides the layout of its nbt
+    pub version: McVersion,
+    /// Direction a chain of a single command block faces: "north", "east", "south",
+    /// "west", "up" or "down". Longer chains face along their path
+    pub facing: String,
+}
+
+impl Default for CommandChainOptions {
+    fn default() -> Self {
+        CommandChainOptions {
+            version: JE_1_20_1,
+            facing: "east".to_string(),
+        }
+    }
+}
+
+impl MCSchematic {
+    /// Places a chain of command blocks along a path. Every command block faces the next
+    /// cell of the path, so each one triggers the next, and the last one keeps the direction
+    /// of the last step. The path may turn in any direction, including up and down.
+    ///
+    /// A conditional command block checks the block behind it, so it can't be placed
+    /// where the path turns
+    ///
+    /// path - cells of the chain in order, each one next to the previous one
+    ///
+    /// blocks - one command block for every cell of the path
+    ///
+    /// options - version and the facing of single command blocks, see CommandChainOptions
+    pub fn place_command_chain(
+        &mut self,
+        path: &[Coords],
+        blocks: &[CommandBlock],
+        options: &CommandChainOptions,
+    ) -> Result<(), String> {
+        if path.len() != blocks.len() {
+            return Err(format!(
+                "The path has {} cells for {} command blocks",
+                path.len(),
+                blocks.len()
+            ));
+        }
+        if !FACINGS.contains(&options.facing.as_str()) {
+            return Err(format!("Command blocks can't face {}", options.facing));
+        }
+        for (index, &cell) in path.iter().enumerate() {
+            if path[..index].contains(&cell) {
+                return Err(format!("The path goes through {:?} twice", cell));
+            }
+        }
+
+        // Direction of every step along the path, the last block repeating the last one
+        let mut facings = vec![];
+        for pair in path.windows(2) {
+            let Some(facing) = step_direction(pair[0], pair[1]) else {
+                return Err(format!("{:?} isn't next to {:?}", pair[1], pair[0]));
+            };
+            facings.push(facing);
+        }
+        facings.push(facings.last().copied().unwrap_or(options.facing.as_str()));
+
+        for (index, block) in blocks.iter().enumerate() {
+            let incoming = index.checked_sub(1).map(|previous| facings[previous]);
+            if block.conditional && incoming.is_some_and(|incoming| incoming != facings[index]) {
+                return Err(format!(
+                    "The conditional command block at {:?} is on a turn of the path",
+                    path[index]
+                ));
+            }
+        }
+
+        for ((&cell, block), facing) in path.iter().zip(blocks).zip(facings) {
+            self.set_block_entity(
+                cell,
+                &format!(
+                    "{}[conditional={},facing={}]",
+                    block.kind.block_id(),
+                    block.conditional,
+                    facing
+                ),
+                command_block_entity(block, options.version),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Generates the block entity of a command block in the layout of the given version
+fn command_block_entity(block: &CommandBlock, version: McVersion) -> NbtCompound {
+    let mut entity = compound!(
+        "Id": "minecraft:command_block",
+        "Command": block.command.as_str(),
+        "auto": NbtTag::Byte(block.auto as i8),
+        "powered": NbtTag::Byte(0),
+        "conditionMet": NbtTag::Byte(0),
+        "SuccessCount": 0,
+        "TrackOutput": NbtTag::Byte(1)
+    );
+
+    // Names are JSON text since 1.13, plain text before
+    if let Some(name) = &block.name {
+        match version >= JE_1_13 {
+            true => entity.insert("CustomName", TextComponent::new(name).to_json()),
+            false => entity.insert("CustomName", name.as_str()),
+        }
+    }
+    // Chain command blocks remember the tick they last ran in since 1.14,
+    // so they run at most once per tick
+    if version >= JE_1_14 {
+        entity.insert("UpdateLastExecution", NbtTag::Byte(1));
+    }
+
+    entity
+}
+
+/// Returns the direction of a step between two neighbouring cells,
+/// or None for cells that aren't next to each other
+fn step_direction(from: Coords, to: Coords) -> Option<&'static str> {
+    let step = (
+        to.0.checked_sub(from.0)?,
+        to.1.checked_sub(from.1)?,
+        to.2.checked_sub(from.2)?,
+    );
+    match step {
+        (0, -1, 0) => Some("down"),
+        (0, 1, 0) => Some("up"),
+        (0, 0, -1) => Some("north"),
+        (1, 0, 0) => Some("east"),
+        (0, 0, 1) => Some("south"),
+        (-1, 0, 0) => Some("west"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::versions::JE_1_12_2;
+
+    #[test]
+    fn faces_along_the_path() {
+        let mut schematic = MCSchematic::new();
+        let path = [(0, 0, 0), (1, 0, 0), (1, 1, 0), (1, 1, -1), (1, 0, -1)];
+        let blocks = [
+            CommandBlock::repeating("say 1"),
+            CommandBlock::chain("say 2"),
+            CommandBlock::chain("say 3"),
+            CommandBlock::chain("say 4"),
+            CommandBlock::chain("say 5").conditional(),
+        ];
+        schematic
+            .place_command_chain(&path, &blocks, &CommandChainOptions::default())
+            .unwrap();
+
+        assert_eq!(
+            schematic.get_block((0, 0, 0)),
+            "minecraft:repeating_command_block[conditional=false,facing=east]"
+        );
+        assert_eq!(
+            schematic.get_block((1, 0, 0)),
+            "minecraft:chain_command_block[conditional=false,facing=up]"
+        );
+        assert_eq!(
+            schematic.get_block((1, 1, 0)),
+            "minecraft:chain_command_block[conditional=false,facing=north]"
+        );
+        assert_eq!(
+            schematic.get_block((1, 1, -1)),
+            "minecraft:chain_command_block[conditional=false,facing=down]"
+        );
+        // The last command block keeps the direction of the last step
+        assert_eq!(
+            schematic.get_block((1, 0, -1)),
+            "minecraft:chain_command_block[conditional=true,facing=down]"
+        );
+
+        let entity = schematic.get_block_entity((1, 0, 0)).unwrap();
+        assert_eq!(entity.get::<_, &str>("Command").unwrap(), "say 2");
+        assert_eq!(entity.get::<_, i8>("auto").unwrap(), 1);
+    }
+
+    #[test]
+    fn single_command_blocks_use_the_facing_option() {
+        let mut schematic = MCSchematic::new();
+        let options = CommandChainOptions {
+            facing: "up".to_string(),
+            ..Default::default()
+        };
+        schematic
+            .place_command_chain(&[(3, 4, 5)], &[CommandBlock::impulse("say hi")], &options)
+            .unwrap();
+        assert_eq!(
+            schematic.get_block((3, 4, 5)),
+            "minecraft:command_block[conditional=false,facing=up]"
+        );
+
+        let options = CommandChainOptions {
+            facing: "sideways".to_string(),
+            ..Default::default()
+        };
+        assert!(schematic
+            .place_command_chain(&[(0, 0, 0)], &[CommandBlock::impulse("say hi")], &options)
+            .is_err());
+    }
+
+    #[test]
+    fn rejects_conditional_blocks_on_turns() {
+        let mut schematic = MCSchematic::new();
+        let path = [(0, 0, 0), (1, 0, 0), (1, 0, 1)];
+        let options = CommandChainOptions::default();
+
+        let on_turn = [
+            CommandBlock::impulse("say 1"),
+            CommandBlock::chain("say 2").conditional(),
+            CommandBlock::chain("say 3"),
+        ];
+        assert!(schematic
+            .place_command_chain(&path, &on_turn, &options)
+            .is_err());
+        assert_eq!(schematic.get_block((0, 0, 0)), "minecraft:air");
+
+        // The first command block has nothing behind it to turn from
+        let after_turn = [
+            CommandBlock::impulse("say 1").conditional(),
+            CommandBlock::chain("say 2"),
+            CommandBlock::chain("say 3").conditional(),
+        ];
+        assert!(schematic
+            .place_command_chain(&path, &after_turn, &options)
+            .is_ok());
+    }
+
+    #[test]
+    fn rejects_broken_paths() {
+        let mut schematic = MCSchematic::new();
+        let options = CommandChainOptions::default();
+        let blocks = [CommandBlock::impulse("say 1"), CommandBlock::chain("say 2")];
+
+        for path in [
+            [(0, 0, 0), (2, 0, 0)],
+            [(0, 0, 0), (1, 1, 0)],
+            [(0, 0, 0), (0, 0, 0)],
+            [(i16::MIN, 0, 0), (i16::MAX, 0, 0)],
+        ] {
+            assert!(
+                schematic
+                    .place_command_chain(&path, &blocks, &options)
+                    .is_err(),
+                "{:?}",
+                path
+            );
+        }
+        assert!(schematic
+            .place_command_chain(&[(0, 0, 0)], &blocks, &options)
+            .is_err());
+    }
+
+    #[test]
+    fn writes_entities_for_the_version() {
+        let block = CommandBlock::impulse("say hi").named("Bob");
+
+        let entity = command_block_entity(&block, JE_1_20_1);
+        assert_eq!(
+            entity.get::<_, &str>("CustomName").unwrap(),
+            TextComponent::new("Bob").to_json()
+        );
+        assert_eq!(entity.get::<_, i8>("UpdateLastExecution").unwrap(), 1);
+
+        let entity = command_block_entity(&block, JE_1_12_2);
+        assert_eq!(entity.get::<_, &str>("CustomName").unwrap(), "Bob");
+        assert!(entity.get::<_, i8>("UpdateLastExecution").is_err());
+    }
+}
@@ -0,0 +1,550 @@
+use quartz_nbt::{compound, NbtCompound, NbtList, NbtTag};
+
+use crate::block_state::BlockState;
+use crate::mcschematic::{Coords, MCSchematic};
+
+/// Returns the item list of a container with the given number of slots,
+/// filled with just enough items for a comparator to read the given signal strength.
+/// The items go into the first slots, up to a full stack per slot
+///
+/// slots - number of slots of the container, such as 27 for a barrel or 5 for a hopper
+///
+/// signal_strength - the comparator output, from 0 (empty) to 15 (full)
+///
+/// item - full in-game id of the item, such as "minecraft:redstone"
+///
+/// stack_size - how many of the item fit in a slot, 64 for most items
+///
+/// Fails for strengths no number of items gives, which happens when the container holds
+/// fewer than 14 items in total, such as a hopper of unstackable items
+pub fn items_for_signal_strength(
+    slots: u8,
+    signal_strength: u8,
+    item: &str,
+    stack_size: u8,
+) -> Result<NbtList, String> {
+    if signal_strength > 15 {
+        return Err(format!("Signal strength {} is above 15", signal_strength));
+    }
+    if stack_size == 0 || stack_size > i8::MAX as u8 {
+        return Err(format!("Items can't stack up to {}", stack_size));
+    }
+
+    // A comparator reads 1 + floor(fullness * 14) from a container with any items in it,
+    // where fullness is the sum of count / stack size over all slots, divided by the slots
+    let capacity = slots as u32 * stack_size as u32;
+    let mut count = match signal_strength {
+        0 => 0,
+        strength => (capacity * (strength as u32 - 1)).div_ceil(14).max(1),
+    };
+    let read = match count {
+        0 => 0,
+        count if count > capacity => 0,
+        count => (1 + count * 14 / capacity).min(15),
+    };
+    if read != signal_strength as u32 {
+        return Err(format!(
+            "No number of items gives signal strength {} in {} slots of {}",
+            signal_strength, slots, stack_size
+        ));
+    }
+
+    let mut items = NbtList::new();
+    for slot in 0..slots {
+        if count == 0 {
+            break;
+        }
+        let stack = count.min(stack_size as u32);
+        items.push(compound!(
+            "Slot": NbtTag::Byte(slot as i8),
+            "id": item,
+            "Count": NbtTag::Byte(stack as i8)
+        ));
+        count -= stack;
+    }
+
+    Ok(items)
+}
+
+/// Item containers a comparator reads the fullness of, with their number of slots
+const CONTAINER_SLOTS: &[(&str, u8)] = &[
+    ("chest", 27),
+    ("trapped_chest", 27),
+    ("barrel", 27),
+    ("shulker_box", 27),
+    ("dispenser", 9),
+    ("dropper", 9),
+    ("hopper", 5),
+    ("brewing_stand", 5),
+    ("furnace", 3),
+    ("blast_furnace", 3),
+    ("smoker", 3),
+];
+
+/// Returns the number of slots of an item container a comparator can read,
+/// or None for any other block
+///
+/// block - full in-game id of the block, block states are ignored
+pub fn container_slots(block: &str) -> Option<u8> {
+    let id = BlockState::parse(block).id;
+    let name = id.trim_start_matches("minecraft:");
+    let name = if name.ends_with("shulker_box") {
+        "shulker_box"
+    } else {
+        name
+    };
+
+    CONTAINER_SLOTS
+        .iter()
+        .find(|(container, _)| *container == name)
+        .map(|&(_, slots)| slots)
+}
+
+/// Returns how many of an item fit in one slot: 1 for tools, armor and other unstackable
+/// items, 16 for the few items stacking up to 16 and 64 for everything else
+pub fn stack_size(item: &str) -> u8 {
+    #[rustfmt::skip]
+    const UNSTACKABLE_SUFFIXES: &[&str] = &[
+        "_sword", "_pickaxe", "_axe", "_shovel", "_hoe", "_helmet", "_chestplate",
+        "_leggings", "_boots", "_horse_armor", "_bucket", "_boat", "_raft", "minecart",
+        "shulker_box", "potion", "_on_a_stick", "_banner_pattern", "bundle",
+    ];
+    #[rustfmt::skip]
+    const UNSTACKABLE: &[&str] = &[
+        "bow", "crossbow", "trident", "shield", "elytra", "totem_of_undying", "saddle",
+        "fishing_rod", "flint_and_steel", "shears", "mace", "brush", "spyglass",
+        "writable_book", "enchanted_book", "knowledge_book", "cake",
+    ];
+    const STACK_OF_16_SUFFIXES: &[&str] = &["_sign", "_banner"];
+    #[rustfmt::skip]
+    const STACK_OF_16: &[&str] = &[
+        "ender_pearl", "snowball", "egg", "bucket", "honey_bottle", "armor_stand",
+        "written_book",
+    ];
+
+    let name = item.trim_start_matches("minecraft:");
+    if name.starts_with("music_disc_")
+        || UNSTACKABLE.contains(&name)
+        || UNSTACKABLE_SUFFIXES
+            .iter()
+            .any(|suffix| name.ends_with(suffix))
+    {
+        1
+    } else if STACK_OF_16.contains(&name)
+        || STACK_OF_16_SUFFIXES
+            .iter()
+            .any(|suffix| name.ends_with(suffix))
+    {
+        16
+    } else {
+        64
+    }
+}
+
+/// Returns the signal strength a comparator reads from an item container holding the
+/// given items, the inverse of items_for_signal_strength
+///
+/// slots - number of slots of the container, see container_slots
+///
+/// items - the "Items" list of the container's block entity
+pub fn signal_strength_of_items(slots: u8, items: &NbtList) -> u8 {
+    let mut fullness = 0.0;
+    let mut any = false;
+    for item in items.iter() {
+        let NbtTag::Compound(item) = item else {
+            continue;
+        };
+        // Item stacks store a byte "Count" up to 1.20.4 and an int "count" since 1.20.5
+        let count = match (item.get::<_, i8>("Count"), item.get::<_, i32>("count")) {
+            (Ok(count), _) => count as f32,
+            (_, Ok(count)) => count as f32,
+            _ => 1.0,
+        };
+        if count <= 0.0 {
+            continue;
+        }
+        let id = item.get::<_, &str>("id").unwrap_or("minecraft:air");
+        fullness += count / stack_size(id) as f32;
+        any = true;
+    }
+
+    if !any || slots == 0 {
+        return 0;
+    }
+    (1.0 + (fullness / slots as f32 * 14.0).floor()).min(15.0) as u8
+}
+
+/// Signal strength a comparator reads from a jukebox playing each music disc
+#[rustfmt::skip]
+const MUSIC_DISCS: &[(&str, u8)] = &[
+    ("13", 1), ("cat", 2), ("blocks", 3), ("chirp", 4), ("far", 5), ("mall", 6),
+    ("mellohi", 7), ("stal", 8), ("strad", 9), ("ward", 10), ("11", 11), ("wait", 12),
+    ("pigstep", 13), ("otherside", 14), ("5", 15), ("relic", 14), ("creator", 12),
+    ("creator_music_box", 11), ("precipice", 13), ("tears", 10), ("lava_chicken", 9),
+];
+
+/// A block a comparator can read, see MCSchematic::comparator_signals
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContainerSignal {
+    pub coords: Coords,
+    /// Full in-game id of the block
+    pub block: String,
+    /// What a comparator reading the block outputs, from 0 to 15
+    pub signal_strength: u8,
+}
+
+/// Returns the signal strength a comparator reads from a block, or None for blocks
+/// comparators don't read. Covers item containers (by how full they are), jukeboxes
+/// (by their disc), composters (by their level) and lecterns (by the open page).
+/// Both halves of a double chest are read together by MCSchematic::comparator_signal
+///
+/// block - full in-game id of the block, including its block states
+///
+/// entity - block entity data of the block, if it has any
+pub fn comparator_signal(block: &str, entity: Option<&NbtCompound>) -> Option<u8> {
+    if let Some(slots) = container_slots(block) {
+        let items = entity.and_then(|entity| entity.get::<_, &NbtList>("Items").ok());
+        return Some(items.map_or(0, |items| signal_strength_of_items(slots, items)));
+    }
+
+    let state = BlockState::parse(block);
+    match state.id.trim_start_matches("minecraft:") {
+        "composter" => Some(
+            state
+                .property("level")
+                .and_then(|level| level.parse::<u8>().ok())
+                .map_or(0, |level| level.min(8)),
+        ),
+        "jukebox" => {
+            let disc = entity
+                .and_then(|entity| entity.get::<_, &NbtCompound>("RecordItem").ok())
+                .and_then(|item| item.get::<_, &str>("id").ok())
+                .map(|id| id.trim_start_matches("minecraft:music_disc_"));
+            Some(
+                MUSIC_DISCS
+                    .iter()
+                    .find(|(name, _)| Some(*name) == disc)
+                    .map_or(0, |&(_, signal)| signal),
+            )
+        }
+        "lectern" => Some(entity.map_or(0, lectern_signal)),
+        _ => None,
+    }
+}
+
+/// A comparator reads 1 + floor(page / (pages - 1) * 14) from a lectern holding a book,
+/// or 15 for books with less than two pages
+fn lectern_signal(entity: &NbtCompound) -> u8 {
+    let Ok(book) = entity.get::<_, &NbtCompound>("Book") else {
+        return 0;
+    };
+
+    // Books keep their pages in the "tag" compound up to 1.20.4 and in their
+    // written or writable book content component since 1.20.5
+    let pages = book
+        .get::<_, &NbtCompound>("tag")
+        .and_then(|tag| tag.get::<_, &NbtList>("pages"))
+        .ok()
+        .or_else(|| {
+            let components = book.get::<_, &NbtCompound>("components").ok()?;
+            [
+                "minecraft:written_book_content",
+                "minecraft:writable_book_content",
+            ]
+            .iter()
+            .find_map(|&key| {
+                components
+                    .get::<_, &NbtCompound>(key)
+                    .and_then(|content| content.get::<_, &NbtList>("pages"))
+                    .ok()
+            })
+        })
+        .map_or(0, |pages| pages.len());
+    let page = entity.get::<_, i32>("Page").unwrap_or(0).max(0);
+
+    let fullness = match pages {
+        0 | 1 => 1.0,
+        pages => (page as f32 / (pages - 1) as f32).min(1.0),
+    };
+    1 + (fullness * 14.0).floor() as u8
+}
+
+/// Returns the offset from one half of a double chest to the other one,
+/// or None for single chests
+fn chest_partner_offset(state: &BlockState) -> Option<Coords> {
+    match (state.property("type")?, state.property("facing")?) {
+        ("left", "north") | ("right", "south") => Some((1, 0, 0)),
+        ("left", "south") | ("right", "north") => Some((-1, 0, 0)),
+        ("left", "east") | ("right", "west") => Some((0, 0, 1)),
+        ("left", "west") | ("right", "east") => Some((0, 0, -1)),
+        _ => None,
+    }
+}
+
+impl MCSchematic {
+    /// Returns the signal strength a comparator reads from the block at the given coords,
+    /// or None for blocks comparators don't read, see comparator_signal.
+    /// Double chests count the items of both halves
+    pub fn comparator_signal(&self, coords: Coords) -> Option<u8> {
+        let block = self.get_block(coords);
+        let entity = self.get_block_entity(coords);
+
+        let state = BlockState::parse(block);
+        if let Some((dx, dy, dz)) = chest_partner_offset(&state) {
+            let partner = (coords.0 + dx, coords.1 + dy, coords.2 + dz);
+            if BlockState::parse(self.get_block(partner)).id == state.id {
+                let mut items = NbtList::new();
+                for half in [entity, self.get_block_entity(partner)]
+                    .into_iter()
+                    .flatten()
+                {
+                    if let Ok(half_items) = half.get::<_, &NbtList>("Items") {
+                        items.inner_mut().extend(half_items.iter().cloned());
+                    }
+                }
+                return Some(signal_strength_of_items(54, &items));
+            }
+        }
+
+        comparator_signal(block, entity)
+    }
+
+    /// Lists every block of the schematic a comparator can read, with the signal strength
+    /// it reads, sorted by their coords. Useful to check the contents of loaded schematics,
+    /// such as the values stored in a ROM
+    pub fn comparator_signals(&self) -> Vec<ContainerSignal> {
+        let mut signals: Vec<ContainerSignal> = self
+            .blocks()
+            .filter_map(|(coords, block)| {
+                self.comparator_signal(coords)
+                    .map(|signal_strength| ContainerSignal {
+                        coords,
+                        block: block.to_string(),
+                        signal_strength,
+                    })
+            })
+            .collect();
+        signals.sort_by_key(|signal| signal.coords);
+        signals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts the items of a list built by items_for_signal_strength
+    fn item_count(items: &NbtList) -> u32 {
+        items
+            .iter_map::<&NbtCompound>()
+            .flatten()
+            .map(|item| item.get::<_, i8>("Count").unwrap() as u32)
+            .sum()
+    }
+
+    /// Swaps the item for one stacking up to the given size, so the read-back matches
+    fn set_stack_size(items: &NbtList, stack_size: u8) -> NbtList {
+        let id = match stack_size {
+            1 => "minecraft:diamond_sword",
+            16 => "minecraft:ender_pearl",
+            _ => "minecraft:redstone",
+        };
+        let mut items = items.clone();
+        for item in items.iter_mut() {
+            if let NbtTag::Compound(item) = item {
+                item.insert("id", id);
+            }
+        }
+        items
+    }
+
+    #[test]
+    fn fills_containers_for_every_signal_strength() {
+        for (slots, stack_size) in [(27, 64), (9, 64), (5, 64), (27, 16), (27, 1), (3, 64)] {
+            for strength in 0..=15 {
+                let items =
+                    items_for_signal_strength(slots, strength, "minecraft:redstone", stack_size)
+                        .unwrap();
+                let read = signal_strength_of_items(slots, &set_stack_size(&items, stack_size));
+                assert_eq!(read, strength, "{} slots of {}", slots, stack_size);
+            }
+        }
+    }
+
+    #[test]
+    fn uses_the_fewest_items_in_the_first_slots() {
+        let items = items_for_signal_strength(27, 1, "minecraft:redstone", 64).unwrap();
+        assert_eq!(item_count(&items), 1);
+
+        let items = items_for_signal_strength(27, 3, "minecraft:redstone", 64).unwrap();
+        assert_eq!(item_count(&items), 247);
+        assert_eq!(items.len(), 4);
+        let last: &NbtCompound = items.get(3).unwrap();
+        assert_eq!(last.get::<_, i8>("Slot").unwrap(), 3);
+        assert_eq!(last.get::<_, i8>("Count").unwrap(), 55);
+
+        let items = items_for_signal_strength(5, 15, "minecraft:redstone", 64).unwrap();
+        assert_eq!(item_count(&items), 320);
+        assert!(items_for_signal_strength(5, 0, "minecraft:redstone", 64)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn rejects_strengths_the_container_cant_give() {
+        assert!(items_for_signal_strength(27, 16, "minecraft:redstone", 64).is_err());
+        assert!(items_for_signal_strength(27, 5, "minecraft:redstone", 0).is_err());
+        assert!(items_for_signal_strength(27, 5, "minecraft:redstone", 200).is_err());
+        assert!(items_for_signal_strength(0, 5, "minecraft:redstone", 64).is_err());
+
+        // A hopper holds 5 unstackable items, which give 3, 6, 9, 12 and 15
+        let reachable: Vec<u8> = (0..=15)
+            .filter(|&strength| {
+                items_for_signal_strength(5, strength, "minecraft:diamond_sword", 1).is_ok()
+            })
+            .collect();
+        assert_eq!(reachable, [0, 3, 6, 9, 12, 15]);
+    }
+
+    #[test]
+    fn knows_container_slots_and_stack_sizes() {
+        assert_eq!(
+            container_slots("minecraft:chest[facing=north,type=single]"),
+            Some(27)
+        );
+        assert_eq!(
+            container_slots("minecraft:red_shulker_box[facing=up]"),
+            Some(27)
+        );
+        assert_eq!(container_slots("minecraft:hopper"), Some(5));
+        assert_eq!(container_slots("minecraft:smoker"), Some(3));
+        assert_eq!(container_slots("minecraft:stone"), None);
+
+        assert_eq!(stack_size("minecraft:redstone"), 64);
+        assert_eq!(stack_size("minecraft:ender_pearl"), 16);
+        assert_eq!(stack_size("minecraft:oak_sign"), 16);
+        assert_eq!(stack_size("minecraft:netherite_sword"), 1);
+        assert_eq!(stack_size("minecraft:music_disc_cat"), 1);
+        assert_eq!(stack_size("minecraft:white_shulker_box"), 1);
+    }
+
+    #[test]
+    fn reads_the_fullness_of_items() {
+        let items = NbtList::from(vec![
+            NbtTag::Compound(compound!("Slot": 0i8, "id": "minecraft:redstone", "Count": 64i8)),
+            // Item counts since 1.20.5
+            NbtTag::Compound(compound!("slot": 1i8, "id": "minecraft:ender_pearl", "count": 16)),
+            NbtTag::Compound(compound!("Slot": 2i8, "id": "minecraft:stone", "Count": 0i8)),
+        ]);
+        // Two full slots of 27: 1 + floor(2 / 27 * 14)
+        assert_eq!(signal_strength_of_items(27, &items), 2);
+        assert_eq!(signal_strength_of_items(2, &items), 15);
+        assert_eq!(signal_strength_of_items(0, &items), 0);
+        assert_eq!(signal_strength_of_items(27, &NbtList::new()), 0);
+
+        let sword = NbtList::from(vec![NbtTag::Compound(compound!(
+            "Slot": 0i8,
+            "id": "minecraft:diamond_sword",
+            "Count": 1i8
+        ))]);
+        assert_eq!(signal_strength_of_items(5, &sword), 3);
+    }
+
+    #[test]
+    fn reads_jukeboxes_composters_and_lecterns() {
+        assert_eq!(comparator_signal("minecraft:stone", None), None);
+        assert_eq!(
+            comparator_signal("minecraft:chest[facing=north]", None),
+            Some(0)
+        );
+
+        assert_eq!(
+            comparator_signal("minecraft:composter[level=3]", None),
+            Some(3)
+        );
+        assert_eq!(
+            comparator_signal("minecraft:composter[level=8]", None),
+            Some(8)
+        );
+        assert_eq!(comparator_signal("minecraft:composter", None), Some(0));
+
+        let jukebox = |disc: &str| {
+            let entity = compound!("RecordItem": compound!("id": disc, "Count": 1i8));
+            comparator_signal("minecraft:jukebox[has_record=true]", Some(&entity))
+        };
+        assert_eq!(jukebox("minecraft:music_disc_cat"), Some(2));
+        assert_eq!(jukebox("minecraft:music_disc_5"), Some(15));
+        assert_eq!(jukebox("minecraft:music_disc_pigstep"), Some(13));
+        assert_eq!(comparator_signal("minecraft:jukebox", None), Some(0));
+
+        let pages = NbtList::from(vec!["1", "2", "3", "4", "5"]);
+        let lectern = |book: NbtCompound, page: i32| {
+            let entity = compound!("Book": book, "Page": page);
+            comparator_signal("minecraft:lectern[has_book=true]", Some(&entity))
+        };
+        let book =
+            compound!("id": "minecraft:written_book", "tag": compound!("pages": pages.clone()));
+        assert_eq!(lectern(book.clone(), 0), Some(1));
+        assert_eq!(lectern(book.clone(), 2), Some(8));
+        assert_eq!(lectern(book, 4), Some(15));
+        // Book content components since 1.20.5
+        let book = compound!(
+            "id": "minecraft:writable_book",
+            "components": compound!(
+                "minecraft:writable_book_content": compound!("pages": pages)
+            )
+        );
+        assert_eq!(lectern(book, 1), Some(4));
+        let one_page = compound!("tag": compound!("pages": NbtList::from(vec!["1"])));
+        assert_eq!(lectern(one_page, 0), Some(15));
+        assert_eq!(comparator_signal("minecraft:lectern", None), Some(0));
+    }
+
+    #[test]
+    fn reads_double_chests_together() {
+        let full = items_for_signal_strength(27, 15, "minecraft:redstone", 64).unwrap();
+        let mut schematic = MCSchematic::new();
+        schematic.set_block_entity(
+            (0, 0, 0),
+            "minecraft:chest[facing=north,type=left]",
+            compound!("Items": full.clone()),
+        );
+        schematic.set_block((1, 0, 0), "minecraft:chest[facing=north,type=right]");
+        // Not the other half of a chest of a different kind
+        schematic.set_block_entity(
+            (0, 0, 5),
+            "minecraft:chest[facing=north,type=left]",
+            compound!("Items": full),
+        );
+        schematic.set_block(
+            (1, 0, 5),
+            "minecraft:trapped_chest[facing=north,type=right]",
+        );
+        schematic.set_block((2, 0, 5), "minecraft:composter[level=5]");
+        schematic.set_block((3, 0, 5), "minecraft:stone");
+
+        // Half of 54 slots: 1 + floor(0.5 * 14)
+        assert_eq!(schematic.comparator_signal((0, 0, 0)), Some(8));
+        assert_eq!(schematic.comparator_signal((1, 0, 0)), Some(8));
+        assert_eq!(schematic.comparator_signal((0, 0, 5)), Some(15));
+        assert_eq!(schematic.comparator_signal((1, 0, 5)), Some(0));
+        assert_eq!(schematic.comparator_signal((3, 0, 5)), None);
+
+        let signals = schematic.comparator_signals();
+        let read: Vec<(Coords, u8)> = signals
+            .iter()
+            .map(|signal| (signal.coords, signal.signal_strength))
+            .collect();
+        assert_eq!(
+            read,
+            [
+                ((0, 0, 0), 8),
+                ((0, 0, 5), 15),
+                ((1, 0, 0), 8),
+                ((1, 0, 5), 0),
+                ((2, 0, 5), 5)
+            ]
+        );
+        assert_eq!(signals[4].block, "minecraft:composter[level=5]");
+    }
+}
@@ -0,0 +1,475 @@
+use crate::block_state::BlockState;
+use crate::mcschematic::{Coords, MCSchematic};
+
+/// Stands for the solid block of GateOptions in the cell layouts
+const BLOCK: &str = "$block";
+
+const REPEATER: &str = "minecraft:repeater[delay=1,facing=west]";
+const SUBTRACTOR: &str = "minecraft:comparator[facing=west,mode=subtract]";
+
+/// A redstone cell drawn facing east: signals come in from the west (-x) and leave
+/// towards the east (+x), with the components at y = 1 on top of a support layer at y = 0.
+/// Redstone wire lists its connections, since pasting doesn't update block shapes
+struct CellLayout {
+    blocks: &'static [(Coords, &'static str)],
+    inputs: &'static [Coords],
+    /// Output of the standard and of the compact variant
+    output: Coords,
+    compact_output: Coords,
+}
+
+const NOT_LAYOUT: CellLayout = CellLayout {
+    blocks: &[
+        ((0, 1, 0), REPEATER),
+        ((1, 1, 0), BLOCK),
+        ((2, 1, 0), "minecraft:redstone_wall_torch[facing=east]"),
+        ((3, 1, 0), REPEATER),
+    ],
+    inputs: &[(0, 1, 0)],
+    output: (3, 1, 0),
+    compact_output: (2, 1, 0),
+};
+
+#[rustfmt::skip]
+const OR_LAYOUT: CellLayout = CellLayout {
+    blocks: &[
+        ((0, 1, 0), REPEATER),
+        ((0, 1, 2), REPEATER),
+        ((1, 1, 0), "minecraft:redstone_wire[east=none,north=none,south=side,west=side]"),
+        ((1, 1, 1), "minecraft:redstone_wire[east=side,north=side,south=side,west=none]"),
+        ((1, 1, 2), "minecraft:redstone_wire[east=none,north=side,south=none,west=side]"),
+        ((2, 1, 1), REPEATER),
+    ],
+    inputs: &[(0, 1, 0), (0, 1, 2)],
+    output: (2, 1, 1),
+    compact_output: (1, 1, 1),
+};
+
+// Torches on both input blocks feed a dust line, which turns off the output torch
+// unless both inputs are on
+#[rustfmt::skip]
+const AND_LAYOUT: CellLayout = CellLayout {
+    blocks: &[
+        ((0, 1, 0), REPEATER),
+        ((0, 1, 2), REPEATER),
+        ((1, 1, 0), BLOCK),
+        ((1, 1, 1), BLOCK),
+        ((1, 1, 2), BLOCK),
+        ((1, 2, 0), "minecraft:redstone_torch"),
+        ((1, 2, 1), "minecraft:redstone_wire[east=none,north=side,south=side,west=none]"),
+        ((1, 2, 2), "minecraft:redstone_torch"),
+        ((2, 1, 1), "minecraft:redstone_wall_torch[facing=east]"),
+        ((3, 1, 1), REPEATER),
+    ],
+    inputs: &[(0, 1, 0), (0, 1, 2)],
+    output: (3, 1, 1),
+    compact_output: (2, 1, 1),
+};
+
+// Two subtracting comparators compute A and not B, and B and not A, merged into the output.
+// A reaches the side of the second comparator over a repeater standing on the block
+// carrying B, which doesn't power the repeater above it
+#[rustfmt::skip]
+const XOR_LAYOUT: CellLayout = CellLayout {
+    blocks: &[
+        ((0, 1, 0), REPEATER),
+        ((0, 1, 2), REPEATER),
+        ((1, 1, 0), BLOCK),
+        ((1, 1, 1), BLOCK),
+        ((1, 1, 2), BLOCK),
+        ((1, 1, 3), BLOCK),
+        ((1, 2, 0), "minecraft:redstone_wire[east=none,north=side,south=side,west=none]"),
+        ((1, 2, 1), "minecraft:redstone_wire[east=none,north=side,south=side,west=none]"),
+        ((1, 2, 2), "minecraft:repeater[delay=1,facing=north]"),
+        ((1, 2, 3), "minecraft:redstone_wire[east=none,north=side,south=side,west=none]"),
+        ((1, 1, 4), "minecraft:redstone_wire[east=side,north=up,south=none,west=none]"),
+        ((2, 1, 4), "minecraft:redstone_wire[east=side,north=none,south=none,west=side]"),
+        ((3, 1, 4), "minecraft:redstone_wire[east=none,north=side,south=none,west=side]"),
+        ((3, 1, 3), "minecraft:repeater[delay=1,facing=south]"),
+        ((2, 1, 0), SUBTRACTOR),
+        ((2, 1, 1), "minecraft:repeater[delay=1,facing=south]"),
+        ((2, 1, 2), "minecraft:redstone_wire[east=side,north=side,south=none,west=none]"),
+        ((3, 1, 2), SUBTRACTOR),
+        ((3, 1, 0), "minecraft:redstone_wire[east=side,north=none,south=none,west=side]"),
+        ((4, 1, 0), "minecraft:redstone_wire[east=none,north=none,south=side,west=side]"),
+        ((4, 1, 1), "minecraft:redstone_wire[east=side,north=side,south=side,west=none]"),
+        ((4, 1, 2), "minecraft:redstone_wire[east=none,north=side,south=none,west=side]"),
+        ((5, 1, 1), REPEATER),
+    ],
+    inputs: &[(0, 1, 0), (0, 1, 2)],
+    output: (5, 1, 1),
+    compact_output: (4, 1, 1),
+};
+
+// A repeater locked from the side while the enable input is off
+const LATCH_LAYOUT: CellLayout = CellLayout {
+    blocks: &[
+        ((0, 1, 0), REPEATER),
+        ((1, 1, 0), REPEATER),
+        ((2, 1, 0), REPEATER),
+        ((1, 1, 1), "minecraft:repeater[delay=1,facing=south]"),
+        ((1, 1, 2), "minecraft:redstone_wall_torch[facing=north]"),
+        ((1, 1, 3), BLOCK),
+        ((0, 1, 3), REPEATER),
+    ],
+    inputs: &[(0, 1, 0), (0, 1, 3)],
+    output: (2, 1, 0),
+    compact_output: (1, 1, 0),
+};
+
+// Two latches in a row, the first one locked while the input is on and the second one
+// while it's off. The first takes the inverted output fed back along the far side, which
+// the second passes on when the input turns on. The delay of 2 keeps the new value of the
+// first latch from reaching the second one before it's locked
+#[rustfmt::skip]
+const T_FLIP_FLOP_LAYOUT: CellLayout = CellLayout {
+    blocks: &[
+        ((0, 1, 0), REPEATER),
+        ((1, 1, 0), "minecraft:redstone_wire[east=side,north=none,south=side,west=side]"),
+        ((2, 1, 0), "minecraft:redstone_wire[east=side,north=none,south=none,west=side]"),
+        ((3, 1, 0), BLOCK),
+        ((1, 1, 1), "minecraft:redstone_wire[east=none,north=side,south=side,west=none]"),
+        ((3, 1, 1), "minecraft:redstone_wall_torch[facing=south]"),
+        ((1, 1, 2), "minecraft:repeater[delay=1,facing=north]"),
+        ((3, 1, 2), "minecraft:repeater[delay=1,facing=north]"),
+        ((0, 1, 3), "minecraft:redstone_wire[east=side,north=none,south=side,west=none]"),
+        ((1, 1, 3), REPEATER),
+        ((2, 1, 3), "minecraft:repeater[delay=2,facing=west]"),
+        ((3, 1, 3), REPEATER),
+        ((4, 1, 3), BLOCK),
+        ((5, 1, 3), REPEATER),
+        ((0, 1, 4), "minecraft:redstone_wire[east=side,north=side,south=none,west=none]"),
+        ((1, 1, 4), "minecraft:redstone_wire[east=side,north=none,south=none,west=side]"),
+        ((2, 1, 4), "minecraft:redstone_wire[east=side,north=none,south=none,west=side]"),
+        ((3, 1, 4), "minecraft:repeater[delay=1,facing=east]"),
+        ((4, 1, 4), "minecraft:redstone_wall_torch[facing=south]"),
+    ],
+    inputs: &[(0, 1, 0)],
+    output: (5, 1, 3),
+    compact_output: (3, 1, 3),
+};
+
+/// Logic cells available from MCSchematic::place_gate
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GateKind {
+    /// One input, on while the input is off
+    Not,
+    /// Two inputs, on while both are on
+    And,
+    /// Two inputs, on while any of them is on
+    Or,
+    /// Two inputs, on while exactly one of them is on
+    Xor,
+    /// D latch with the inputs D and E: follows D while E is on and keeps its value while E is off
+    Latch,
+    /// One input, toggles every time the input turns on
+    TFlipFlop,
+}
+
+impl GateKind {
+    fn layout(self) -> &'static CellLayout {
+        match self {
+            GateKind::Not => &NOT_LAYOUT,
+            GateKind::And => &AND_LAYOUT,
+            GateKind::Or => &OR_LAYOUT,
+            GateKind::Xor => &XOR_LAYOUT,
+            GateKind::Latch => &LATCH_LAYOUT,
+            GateKind::TFlipFlop => &T_FLIP_FLOP_LAYOUT,
+        }
+    }
+
+    /// Returns the number of inputs of the gate
+    pub fn input_count(self) -> usize {
+        self.layout().inputs.len()
+    }
+
+    /// Returns the length of the standard cell along the facing direction and its width
+    pub fn size(self) -> (i16, i16) {
+        let blocks = self.layout().blocks.iter();
+        let length = blocks.clone().map(|&((x, _, _), _)| x + 1).max();
+        let width = blocks.map(|&((_, _, z), _)| z + 1).max();
+
+        (length.unwrap_or(0), width.unwrap_or(0))
+    }
+}
+
+/// Size variants of the cells
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CellVariant {
+    /// Ends in a repeater, so the output has a full signal strength and can't feed back into the cell
+    #[default]
+    Standard,
+    /// Leaves the output repeater out, the output being the last component of the cell
+    Compact,
+}
+
+/// Options for MCSchematic::place_gate
+#[derive(Clone, Debug)]
+pub struct GateOptions {
+    /// Direction the signals flow in: "north", "east", "south" or "west"
+    pub facing: String,
+    /// Standard or compact, see CellVariant
+    pub variant: CellVariant,
+    /// Solid block carrying the signals inside the cell
+    pub block: String,
+    /// Block below the components
+    pub support_block: String,
+}
+
+impl Default for GateOptions {
+    fn default() -> Self {
+        GateOptions {
+            facing: "east".to_string(),
+            variant: CellVariant::Standard,
+            block: "minecraft:stone".to_string(),
+            support_block: "minecraft:smooth_stone".to_string(),
+        }
+    }
+}
+
+/// Where a placed gate gets its inputs and gives its output
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GatePorts {
+    /// Positions of the input repeaters, in the order listed by GateKind. Each one is
+    /// fed from the block behind it, against the facing direction
+    pub inputs: Vec<Coords>,
+    /// Position of the last component, whose signal goes into the block in front of it
+    pub output: Coords,
+    /// Lowest and highest corners of the box taken by the cell
+    pub corners: (Coords, Coords),
+}
+
+impl MCSchematic {
+    /// Places a logic cell and returns the coords of its ports
+    ///
+    /// origin - position of the support block below the first input,
+    /// the cell extends in the facing direction and to the right of it
+    ///
+    /// kind - which gate to place, see GateKind
+    ///
+    /// options - direction, variant and blocks, see GateOptions
+    pub fn place_gate(
+        &mut self,
+        origin: Coords,
+        kind: GateKind,
+        options: &GateOptions,
+    ) -> Result<GatePorts, String> {
+        let turns = match options.facing.as_str() {
+            "east" => 0,
+            "south" => 1,
+            "west" => 2,
+            "north" => 3,
+            facing => return Err(format!("Gates can't face {}", facing)),
+        };
+        let layout = kind.layout();
+        let to_world = |coords: Coords| cell_to_world(origin, coords, turns);
+
+        let mut lowest = to_world((0, 0, 0));
+        let mut highest = lowest;
+        for &(coords, block) in layout.blocks {
+            let output_repeater = coords == layout.output && coords != layout.compact_output;
+            if options.variant == CellVariant::Compact && output_repeater {
+                continue;
+            }
+
+            let block = match block {
+                BLOCK => options.block.clone(),
+                block => BlockState::parse(block).rotate_y(turns).to_string(),
+            };
+            let position = to_world(coords);
+            self.set_block(position, &block);
+            if coords.1 == 1 {
+                self.set_block(to_world((coords.0, 0, coords.2)), &options.support_block);
+            }
+
+            for corner in [position, to_world((coords.0, 0, coords.2))] {
+                lowest = (
+                    lowest.0.min(corner.0),
+                    lowest.1.min(corner.1),
+                    lowest.2.min(corner.2),
+                );
+                highest = (
+                    highest.0.max(corner.0),
+                    highest.1.max(corner.1),
+                    highest.2.max(corner.2),
+                );
+            }
+        }
+
+        let output = match options.variant {
+            CellVariant::Standard => layout.output,
+            CellVariant::Compact => layout.compact_output,
+        };
+        Ok(GatePorts {
+            inputs: layout
+                .inputs
+                .iter()
+                .map(|&coords| to_world(coords))
+                .collect(),
+            output: to_world(output),
+            corners: (lowest, highest),
+        })
+    }
+}
+
+/// Turns coords of a cell layout, drawn facing east, into world coords for a cell
+/// turned clockwise (seen from above) by the given number of quarter turns
+pub(crate) fn cell_to_world(origin: Coords, (x, y, z): Coords, quarter_turns: u8) -> Coords {
+    let (mut x, mut z) = (x, z);
+    for _ in 0..quarter_turns % 4 {
+        (x, z) = (-z, x);
+    }
+
+    (origin.0 + x, origin.1 + y, origin.2 + z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::Simulation;
+
+    /// Places a gate facing east with a floor lever behind every input,
+    /// and returns the simulation, the levers and the output
+    fn simulate(kind: GateKind, variant: CellVariant) -> (Simulation, Vec<Coords>, Coords) {
+        let mut schematic = MCSchematic::new();
+        let options = GateOptions {
+            variant,
+            ..Default::default()
+        };
+        let ports = schematic.place_gate((0, 0, 0), kind, &options).unwrap();
+
+        let mut levers = vec![];
+        for &(x, y, z) in &ports.inputs {
+            schematic.set_block((x - 1, y - 1, z), "minecraft:stone");
+            schematic.set_block(
+                (x - 1, y, z),
+                "minecraft:lever[face=floor,facing=east,powered=false]",
+            );
+            levers.push((x - 1, y, z));
+        }
+
+        let mut simulation = Simulation::new(&schematic);
+        simulation.run_until_stable(100).unwrap();
+        (simulation, levers, ports.output)
+    }
+
+    /// Sets the levers and returns the output once the gate is stable
+    fn output_for(
+        simulation: &mut Simulation,
+        levers: &[Coords],
+        inputs: &[bool],
+        output: Coords,
+    ) -> bool {
+        for (&lever, &on) in levers.iter().zip(inputs) {
+            simulation.set_lever(lever, on).unwrap();
+        }
+        simulation.run_until_stable(100).unwrap();
+        simulation.is_powered(output)
+    }
+
+    fn assert_truth_table(kind: GateKind, table: &[(&[bool], bool)]) {
+        for variant in [CellVariant::Standard, CellVariant::Compact] {
+            let (mut simulation, levers, output) = simulate(kind, variant);
+            for &(inputs, expected) in table {
+                let actual = output_for(&mut simulation, &levers, inputs, output);
+                assert_eq!(
+                    actual, expected,
+                    "{:?} {:?} with {:?}",
+                    kind, variant, inputs
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn not_inverts() {
+        assert_truth_table(
+            GateKind::Not,
+            &[(&[false], true), (&[true], false), (&[false], true)],
+        );
+    }
+
+    #[test]
+    fn and_truth_table() {
+        assert_truth_table(
+            GateKind::And,
+            &[
+                (&[false, false], false),
+                (&[true, false], false),
+                (&[false, true], false),
+                (&[true, true], true),
+                (&[false, false], false),
+            ],
+        );
+    }
+
+    #[test]
+    fn or_truth_table() {
+        assert_truth_table(
+            GateKind::Or,
+            &[
+                (&[false, false], false),
+                (&[true, false], true),
+                (&[false, true], true),
+                (&[true, true], true),
+                (&[false, false], false),
+            ],
+        );
+    }
+
+    #[test]
+    fn xor_truth_table() {
+        assert_truth_table(
+            GateKind::Xor,
+            &[
+                (&[false, false], false),
+                (&[true, false], true),
+                (&[false, true], true),
+                (&[true, true], false),
+                (&[false, false], false),
+            ],
+        );
+    }
+
+    #[test]
+    fn latch_sets_and_resets() {
+        // Inputs are D and E. E turns off before D changes, as D would win the race otherwise
+        assert_truth_table(
+            GateKind::Latch,
+            &[
+                (&[true, false], false),
+                (&[true, true], true),
+                (&[false, true], false),
+                (&[true, true], true),
+                (&[true, false], true),
+                (&[false, false], true),
+                (&[false, true], false),
+                (&[false, false], false),
+                (&[true, false], false),
+            ],
+        );
+    }
+
+    #[test]
+    fn t_flip_flop_toggles() {
+        assert_truth_table(
+            GateKind::TFlipFlop,
+            &[
+                (&[false], false),
+                (&[true], true),
+                (&[false], true),
+                (&[true], false),
+                (&[false], false),
+                (&[true], true),
+            ],
+        );
+    }
+
+    #[test]
+    fn sizes_cover_the_layouts() {
+        assert_eq!(GateKind::Not.size(), (4, 1));
+        assert_eq!(GateKind::Xor.size(), (6, 5));
+        assert_eq!(GateKind::TFlipFlop.size(), (6, 5));
+        assert_eq!(GateKind::TFlipFlop.input_count(), 1);
+    }
+}
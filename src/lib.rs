@@ -0,0 +1,17 @@
+pub mod bedrock_blocks;
+pub mod block_registry;
+pub mod block_state;
+pub mod command_blocks;
+pub mod containers;
+pub mod gates;
+pub mod map_art;
+pub mod materials;
+pub mod mcschematic;
+pub mod netlist;
+pub mod note_blocks;
+pub mod pattern;
+pub mod renames;
+pub mod rom;
+pub mod simulation;
+pub mod versions;
+pub mod wiring;
@@ -1,9 +1,8 @@
-mod mcschematic;
-use mcschematic::*;
+use nbt::mcschematic::*;
 
 use quartz_nbt;
 
-mod versions;
+use nbt::versions;
 
 fn main() {
     let mut schematic = MCSchematic::new();
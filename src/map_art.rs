@@ -0,0 +1,405 @@
+use std::fs::File;
+
+use crate::block_registry::block_info;
+use crate::block_state::BlockState;
+use crate::mcschematic::MCSchematic;
+use crate::versions::McVersion;
+
+type Rgb = [u8; 3];
+
+/// Base colors of maps together with a block showing that color from above.
+/// Colors whose block doesn't exist in a version can't be used by it
+const MAP_COLORS: &[(Rgb, &str)] = &[
+    ([127, 178, 56], "minecraft:grass_block"),
+    ([247, 233, 163], "minecraft:sandstone"),
+    ([199, 199, 199], "minecraft:mushroom_stem"),
+    ([255, 0, 0], "minecraft:redstone_block"),
+    ([160, 160, 255], "minecraft:packed_ice"),
+    ([167, 167, 167], "minecraft:iron_block"),
+    ([0, 124, 0], "minecraft:oak_leaves[persistent=true]"),
+    ([255, 255, 255], "minecraft:snow_block"),
+    ([164, 168, 184], "minecraft:clay"),
+    ([151, 109, 77], "minecraft:dirt"),
+    ([112, 112, 112], "minecraft:cobblestone"),
+    ([143, 119, 72], "minecraft:oak_planks"),
+    ([255, 252, 245], "minecraft:quartz_block"),
+    ([216, 127, 51], "minecraft:orange_concrete"),
+    ([178, 76, 216], "minecraft:magenta_concrete"),
+    ([102, 153, 216], "minecraft:light_blue_concrete"),
+    ([229, 229, 51], "minecraft:yellow_concrete"),
+    ([127, 204, 25], "minecraft:lime_concrete"),
+    ([242, 127, 165], "minecraft:pink_concrete"),
+    ([76, 76, 76], "minecraft:gray_concrete"),
+    ([153, 153, 153], "minecraft:light_gray_concrete"),
+    ([76, 127, 153], "minecraft:cyan_concrete"),
+    ([127, 63, 178], "minecraft:purple_concrete"),
+    ([51, 76, 178], "minecraft:blue_concrete"),
+    ([102, 76, 51], "minecraft:brown_concrete"),
+    ([102, 127, 51], "minecraft:green_concrete"),
+    ([153, 51, 51], "minecraft:red_concrete"),
+    ([25, 25, 25], "minecraft:black_concrete"),
+    ([250, 238, 77], "minecraft:gold_block"),
+    ([92, 219, 213], "minecraft:diamond_block"),
+    ([74, 128, 255], "minecraft:lapis_block"),
+    ([0, 217, 58], "minecraft:emerald_block"),
+    ([129, 86, 49], "minecraft:spruce_planks"),
+    ([112, 2, 0], "minecraft:netherrack"),
+    ([209, 177, 161], "minecraft:white_terracotta"),
+    ([159, 82, 36], "minecraft:orange_terracotta"),
+    ([149, 87, 108], "minecraft:magenta_terracotta"),
+    ([112, 108, 138], "minecraft:light_blue_terracotta"),
+    ([186, 133, 36], "minecraft:yellow_terracotta"),
+    ([103, 117, 53], "minecraft:lime_terracotta"),
+    ([160, 77, 78], "minecraft:pink_terracotta"),
+    ([57, 41, 35], "minecraft:gray_terracotta"),
+    ([135, 107, 98], "minecraft:light_gray_terracotta"),
+    ([87, 92, 92], "minecraft:cyan_terracotta"),
+    ([122, 73, 88], "minecraft:purple_terracotta"),
+    ([76, 62, 92], "minecraft:blue_terracotta"),
+    ([76, 50, 35], "minecraft:brown_terracotta"),
+    ([76, 82, 42], "minecraft:green_terracotta"),
+    ([142, 60, 46], "minecraft:red_terracotta"),
+    ([37, 22, 16], "minecraft:black_terracotta"),
+    ([189, 48, 49], "minecraft:crimson_nylium"),
+    ([148, 63, 97], "minecraft:crimson_planks"),
+    ([92, 25, 29], "minecraft:crimson_hyphae"),
+    ([22, 126, 134], "minecraft:warped_nylium"),
+    ([58, 142, 140], "minecraft:warped_planks"),
+    ([86, 44, 62], "minecraft:warped_hyphae"),
+    ([20, 180, 133], "minecraft:warped_wart_block"),
+    ([100, 100, 100], "minecraft:cobbled_deepslate"),
+    ([216, 175, 147], "minecraft:raw_iron_block"),
+];
+
+/// Brightness a map shows a block with, depending on the height of the block north of it:
+/// higher (darker), the same height (normal) or lower (brighter), out of 255
+const SHADES: [u16; 3] = [180, 220, 255];
+
+/// How the blocks of a map art are laid out
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MapArtStyle {
+    /// All blocks on the same height, only showing the normal shade of every color
+    #[default]
+    Flat,
+    /// Blocks raised or lowered compared to their northern neighbour,
+    /// using all three shades of every color
+    Staircase,
+}
+
+/// Options for map_art and map_art_from_png
+#[derive(Clone, Debug)]
+pub struct MapArtOptions {
+    /// The version the map art is built in, which decides the available colors
+    pub version: McVersion,
+    /// Flat or staircased, see MapArtStyle
+    pub style: MapArtStyle,
+    /// Spread the difference between each pixel and its block color to the next
+    /// pixels (Floyd–Steinberg dithering) instead of always picking the nearest color
+    pub dithering: bool,
+    /// Block placed in a row north of the image, which the shade of the first row
+    /// depends on. Maps aligned to the grid start right below this row
+    pub reference_block: String,
+}
+
+impl Default for MapArtOptions {
+    fn default() -> Self {
+        MapArtOptions {
+            version: crate::versions::JE_1_20_1,
+            style: MapArtStyle::Flat,
+            dithering: false,
+            reference_block: "minecraft:cobblestone".to_string(),
+        }
+    }
+}
+
+/// A color as seen on a map: a block together with the shade it's shown in
+struct MapColor {
+    block: &'static str,
+    shade: usize,
+    rgb: Rgb,
+}
+
+/// Converts a PNG file into a map art schematic, see map_art
+///
+/// file_path - location and name of the image
+///
+/// options - version, style and dithering, see MapArtOptions
+pub fn map_art_from_png(file_path: &str, options: &MapArtOptions) -> Result<MCSchematic, String> {
+    let Ok(file_in) = File::open(file_path) else {
+        return Err(format!("Failed to open {}", file_path));
+    };
+
+    let mut decoder = png::Decoder::new(file_in);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let Ok(mut reader) = decoder.read_info() else {
+        return Err(format!("{} is not a valid PNG file", file_path));
+    };
+
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let Ok(info) = reader.next_frame(&mut buffer) else {
+        return Err(format!("Failed to decode {}", file_path));
+    };
+
+    let pixels: Vec<[u8; 4]> = match info.color_type {
+        png::ColorType::Rgba => buffer
+            .chunks_exact(4)
+            .map(|p| [p[0], p[1], p[2], p[3]])
+            .collect(),
+        png::ColorType::Rgb => buffer
+            .chunks_exact(3)
+            .map(|p| [p[0], p[1], p[2], 255])
+            .collect(),
+        png::ColorType::GrayscaleAlpha => buffer
+            .chunks_exact(2)
+            .map(|p| [p[0], p[0], p[0], p[1]])
+            .collect(),
+        png::ColorType::Grayscale => buffer.iter().map(|&p| [p, p, p, 255]).collect(),
+        png::ColorType::Indexed => return Err("Indexed colors weren't expanded".to_string()),
+    };
+
+    map_art(
+        info.width as usize,
+        info.height as usize,
+        &pixels[..info.width as usize * info.height as usize],
+        options,
+    )
+}
+
+/// Converts an image into a map art schematic. The image goes along x from left to right
+/// and along z from top to bottom (north to south), like on a map. Pixels that are mostly
+/// transparent are left empty
+///
+/// width, height - size of the image in pixels
+///
+/// pixels - the RGBA pixels row by row, starting at the top left corner
+///
+/// options - version, style and dithering, see MapArtOptions
+pub fn map_art(
+    width: usize,
+    height: usize,
+    pixels: &[[u8; 4]],
+    options: &MapArtOptions,
+) -> Result<MCSchematic, String> {
+    if pixels.len() != width * height {
+        return Err(format!(
+            "Expected {} pixels for a {}x{} image, got {}",
+            width * height,
+            width,
+            height,
+            pixels.len()
+        ));
+    }
+
+    let palette = map_palette(options);
+    if palette.is_empty() {
+        return Err(format!(
+            "No map colors are available in {}",
+            options.version
+        ));
+    }
+
+    // Pick a color for every pixel, carrying the error over to the next pixels when dithering
+    let mut errors = vec![[0i32; 3]; pixels.len()];
+    let mut chosen: Vec<Option<&MapColor>> = vec![None; pixels.len()];
+    for z in 0..height {
+        for x in 0..width {
+            let index = z * width + x;
+            let [r, g, b, a] = pixels[index];
+            if a < 128 {
+                continue;
+            }
+
+            let target = [r as i32, g as i32, b as i32];
+            let wanted: [i32; 3] =
+                std::array::from_fn(|c| (target[c] + errors[index][c]).clamp(0, 255));
+            let color = nearest_color(&palette, wanted);
+            chosen[index] = Some(color);
+
+            if !options.dithering {
+                continue;
+            }
+            let error: [i32; 3] = std::array::from_fn(|c| wanted[c] - color.rgb[c] as i32);
+            for (dx, dz, weight) in [(1, 0, 7), (-1, 1, 3), (0, 1, 5), (1, 1, 1)] {
+                let (nx, nz) = (x as i64 + dx, z as i64 + dz);
+                if nx < 0 || nx >= width as i64 || nz >= height as i64 {
+                    continue;
+                }
+                let neighbour = &mut errors[nz as usize * width + nx as usize];
+                for c in 0..3 {
+                    neighbour[c] += error[c] * weight / 16;
+                }
+            }
+        }
+    }
+
+    // Stack every column so each block is lower, level with or higher than the one north of it
+    let mut schematic = MCSchematic::new();
+    for x in 0..width {
+        let mut heights = vec![0i16; height + 1];
+        for z in 0..height {
+            heights[z + 1] = match chosen[z * width + x] {
+                Some(color) => heights[z] + color.shade as i16 - 1,
+                None => heights[z],
+            };
+        }
+        let lowest = *heights.iter().min().unwrap_or(&0);
+
+        schematic.set_block((x as i16, heights[0] - lowest, 0), &options.reference_block);
+        for z in 0..height {
+            if let Some(color) = chosen[z * width + x] {
+                let coords = (x as i16, heights[z + 1] - lowest, z as i16 + 1);
+                schematic.set_block(coords, color.block);
+            }
+        }
+    }
+
+    Ok(schematic)
+}
+
+/// Returns every color the options allow, with the blocks that exist in their version
+fn map_palette(options: &MapArtOptions) -> Vec<MapColor> {
+    let shades: &[usize] = match options.style {
+        MapArtStyle::Flat => &[1],
+        MapArtStyle::Staircase => &[0, 1, 2],
+    };
+
+    let mut palette = vec![];
+    for &(rgb, block) in MAP_COLORS {
+        let id = BlockState::parse(block).id;
+        if !block_info(&id).is_some_and(|info| info.exists_in(options.version)) {
+            continue;
+        }
+
+        for &shade in shades {
+            palette.push(MapColor {
+                block,
+                shade,
+                rgb: rgb.map(|c| (c as u16 * SHADES[shade] / 255) as u8),
+            });
+        }
+    }
+
+    palette
+}
+
+/// Returns the color closest to the given one, by the squared distance of their components
+fn nearest_color(palette: &[MapColor], rgb: [i32; 3]) -> &MapColor {
+    palette
+        .iter()
+        .min_by_key(|color| {
+            (0..3)
+                .map(|c| (color.rgb[c] as i32 - rgb[c]).pow(2))
+                .sum::<i32>()
+        })
+        .expect("the palette isn't empty")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::versions::JE_1_16_5;
+
+    const WHITE: [u8; 4] = [255, 255, 255, 255];
+    const RED: [u8; 4] = [255, 0, 0, 255];
+    const CLEAR: [u8; 4] = [0, 0, 0, 0];
+
+    #[test]
+    fn lays_flat_maps_south_of_the_reference_row() {
+        let pixels = [RED, WHITE, CLEAR, RED];
+        let schematic = map_art(2, 2, &pixels, &MapArtOptions::default()).unwrap();
+
+        assert_eq!(schematic.get_block((0, 0, 0)), "minecraft:cobblestone");
+        assert_eq!(schematic.get_block((1, 0, 0)), "minecraft:cobblestone");
+        assert_eq!(schematic.get_block((0, 0, 1)), "minecraft:redstone_block");
+        assert_eq!(schematic.get_block((1, 0, 1)), "minecraft:snow_block");
+        assert_eq!(schematic.get_block((0, 0, 2)), "minecraft:air");
+        assert_eq!(schematic.get_block((1, 0, 2)), "minecraft:redstone_block");
+        assert_eq!(schematic.get_dimensions().height_y, 1);
+    }
+
+    #[test]
+    fn staircases_columns_for_the_brighter_and_darker_shades() {
+        let options = MapArtOptions {
+            style: MapArtStyle::Staircase,
+            ..Default::default()
+        };
+        // Full white is snow one block higher than its northern neighbour,
+        // while a darker white is snow one block lower
+        let dark = [180, 180, 180, 255];
+        let schematic = map_art(1, 3, &[WHITE, WHITE, dark], &options).unwrap();
+
+        assert_eq!(schematic.get_block((0, 0, 0)), "minecraft:cobblestone");
+        assert_eq!(schematic.get_block((0, 1, 1)), "minecraft:snow_block");
+        assert_eq!(schematic.get_block((0, 2, 2)), "minecraft:snow_block");
+        assert_eq!(schematic.get_block((0, 1, 3)), "minecraft:snow_block");
+    }
+
+    #[test]
+    fn dithering_mixes_the_nearest_colors() {
+        let gray = [190, 190, 190, 255];
+        let blocks = |dithering: bool| {
+            let options = MapArtOptions {
+                dithering,
+                ..Default::default()
+            };
+            let schematic = map_art(4, 1, &[gray; 4], &options).unwrap();
+            (0..4)
+                .map(|x| schematic.get_block((x, 0, 1)).to_string())
+                .collect::<Vec<String>>()
+        };
+
+        assert_eq!(blocks(false), vec!["minecraft:mushroom_stem"; 4]);
+        let dithered = blocks(true);
+        assert_eq!(dithered[0], "minecraft:mushroom_stem");
+        assert!(dithered[1..]
+            .iter()
+            .any(|block| block != "minecraft:mushroom_stem"));
+    }
+
+    #[test]
+    fn only_uses_blocks_of_the_version() {
+        let gray = [86, 86, 86, 255];
+        let schematic = map_art(1, 1, &[gray], &MapArtOptions::default()).unwrap();
+        assert_eq!(
+            schematic.get_block((0, 0, 1)),
+            "minecraft:cobbled_deepslate"
+        );
+
+        let options = MapArtOptions {
+            version: JE_1_16_5,
+            ..Default::default()
+        };
+        let schematic = map_art(1, 1, &[gray], &options).unwrap();
+        assert_ne!(
+            schematic.get_block((0, 0, 1)),
+            "minecraft:cobbled_deepslate"
+        );
+        assert_ne!(schematic.get_block((0, 0, 1)), "minecraft:air");
+    }
+
+    #[test]
+    fn reads_png_files() {
+        let path = std::env::temp_dir().join(format!("nbt_{}_map_art.png", std::process::id()));
+        let path = path.to_string_lossy().into_owned();
+        {
+            let file = File::create(&path).unwrap();
+            let mut encoder = png::Encoder::new(file, 2, 1);
+            encoder.set_color(png::ColorType::Rgb);
+            let mut writer = encoder.write_header().unwrap();
+            writer
+                .write_image_data(&[255, 0, 0, 255, 255, 255])
+                .unwrap();
+        }
+        let schematic = map_art_from_png(&path, &MapArtOptions::default()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(schematic.get_block((0, 0, 1)), "minecraft:redstone_block");
+        assert_eq!(schematic.get_block((1, 0, 1)), "minecraft:snow_block");
+        assert!(map_art_from_png(&path, &MapArtOptions::default()).is_err());
+    }
+
+    #[test]
+    fn rejects_pixel_counts_not_matching_the_size() {
+        assert!(map_art(2, 2, &[RED; 3], &MapArtOptions::default()).is_err());
+    }
+}
@@ -0,0 +1,393 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::fs;
+
+use quartz_nbt::{NbtList, NbtTag};
+
+use crate::block_state::BlockState;
+use crate::containers::stack_size;
+use crate::mcschematic::MCSchematic;
+
+/// Blocks without an item, which are left out of the bill of materials
+#[rustfmt::skip]
+const NO_ITEM: &[&str] = &[
+    "air", "cave_air", "void_air", "water", "lava", "bubble_column", "fire", "soul_fire",
+    "nether_portal", "end_portal", "end_gateway", "moving_piston", "piston_head",
+    "frosted_ice", "structure_void",
+];
+
+/// Blocks placed with a different item, such as crops with their seeds
+#[rustfmt::skip]
+const PLACED_WITH: &[(&str, &str)] = &[
+    ("redstone_wire", "redstone"),
+    ("tripwire", "string"),
+    ("wheat", "wheat_seeds"),
+    ("carrots", "carrot"),
+    ("potatoes", "potato"),
+    ("beetroots", "beetroot_seeds"),
+    ("pumpkin_stem", "pumpkin_seeds"),
+    ("attached_pumpkin_stem", "pumpkin_seeds"),
+    ("melon_stem", "melon_seeds"),
+    ("attached_melon_stem", "melon_seeds"),
+    ("torchflower_crop", "torchflower_seeds"),
+    ("pitcher_crop", "pitcher_pod"),
+    ("cocoa", "cocoa_beans"),
+    ("sweet_berry_bush", "sweet_berries"),
+    ("cave_vines", "glow_berries"),
+    ("cave_vines_plant", "glow_berries"),
+    ("kelp_plant", "kelp"),
+    ("twisting_vines_plant", "twisting_vines"),
+    ("weeping_vines_plant", "weeping_vines"),
+    ("bamboo_sapling", "bamboo"),
+    ("big_dripleaf_stem", "big_dripleaf"),
+    ("tall_seagrass", "seagrass"),
+    ("water_cauldron", "cauldron"),
+    ("lava_cauldron", "cauldron"),
+    ("powder_snow_cauldron", "cauldron"),
+    ("farmland", "dirt"),
+    ("dirt_path", "dirt"),
+];
+
+/// Blocks on walls placed with the item of their standing variant
+#[rustfmt::skip]
+const WALL_VARIANTS: &[(&str, &str)] = &[
+    ("_wall_torch", "_torch"),
+    ("wall_torch", "torch"),
+    ("_wall_hanging_sign", "_hanging_sign"),
+    ("_wall_sign", "_sign"),
+    ("_wall_banner", "_banner"),
+    ("_wall_head", "_head"),
+    ("_wall_skull", "_skull"),
+    ("_wall_fan", "_fan"),
+];
+
+/// Properties holding how many items a single block is made of
+const ITEM_COUNTS: &[&str] = &[
+    "candles",
+    "pickles",
+    "eggs",
+    "layers",
+    "flower_amount",
+    "segment_amount",
+];
+
+/// Number of stacks a shulker box holds
+const SHULKER_BOX_SLOTS: u64 = 27;
+
+/// Options for MCSchematic::bill_of_materials_with
+#[derive(Clone, Debug, Default)]
+pub struct MaterialOptions {
+    /// Also list the items stored in containers
+    pub container_contents: bool,
+}
+
+/// An item of a bill of materials together with how many of it are needed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Material {
+    /// Full in-game id of the item, such as "minecraft:redstone"
+    pub item: String,
+    pub count: u64,
+}
+
+impl Material {
+    /// Splits the count into full shulker boxes, full stacks and the remaining items
+    pub fn breakdown(&self) -> (u64, u64, u64) {
+        let stack_size = stack_size(&self.item) as u64;
+        let stacks = self.count / stack_size;
+
+        (
+            stacks / SHULKER_BOX_SLOTS,
+            stacks % SHULKER_BOX_SLOTS,
+            self.count % stack_size,
+        )
+    }
+}
+
+/// The items needed to build a schematic, see MCSchematic::bill_of_materials
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BillOfMaterials {
+    /// Items placed as blocks, the most needed first
+    pub blocks: Vec<Material>,
+    /// Items stored in containers, the most needed first. Empty unless
+    /// MaterialOptions::container_contents is set
+    pub contents: Vec<Material>,
+}
+
+impl BillOfMaterials {
+    /// Returns the bill of materials as CSV, one line per item with its group
+    /// ("blocks" or "contents"), count and the breakdown of Material::breakdown
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("group,item,count,shulker_boxes,stacks,items\n");
+        for (group, materials) in [("blocks", &self.blocks), ("contents", &self.contents)] {
+            for material in materials {
+                let (shulker_boxes, stacks, items) = material.breakdown();
+                let _ = writeln!(
+                    csv,
+                    "{},{},{},{},{},{}",
+                    group, material.item, material.count, shulker_boxes, stacks, items
+                );
+            }
+        }
+
+        csv
+    }
+
+    /// Saves the bill of materials as a CSV file, see BillOfMaterials::to_csv
+    ///
+    /// file_path - location and name of the .csv file
+    pub fn save_csv(&self, file_path: &str) -> Result<String, String> {
+        match fs::write(file_path, self.to_csv()) {
+            Ok(()) => Ok(format!("Saved to {}", file_path)),
+            Err(_) => Err(format!("Failed to save {}", file_path)),
+        }
+    }
+}
+
+impl MCSchematic {
+    /// Lists the items needed to build the schematic in survival, without the contents
+    /// of containers, see MCSchematic::bill_of_materials_with
+    pub fn bill_of_materials(&self) -> BillOfMaterials {
+        self.bill_of_materials_with(&MaterialOptions::default())
+    }
+
+    /// Lists the items needed to build the schematic in survival. Blocks are counted by
+    /// the item placing them: redstone wire needs redstone, double slabs two slabs, doors,
+    /// beds and tall plants count once, potted plants need the pot and the plant.
+    /// Block states such as waterlogged don't change the items
+    ///
+    /// options - whether container contents are listed, see MaterialOptions
+    pub fn bill_of_materials_with(&self, options: &MaterialOptions) -> BillOfMaterials {
+        let mut blocks = HashMap::new();
+        let mut contents = HashMap::new();
+
+        for (coords, block) in self.blocks() {
+            for (item, count) in items_for_block(block) {
+                *blocks.entry(item).or_insert(0) += count;
+            }
+
+            if !options.container_contents {
+                continue;
+            }
+            let items = self
+                .get_block_entity(coords)
+                .and_then(|entity| entity.get::<_, &NbtList>("Items").ok());
+            for item in items.into_iter().flat_map(|items| items.iter()) {
+                let NbtTag::Compound(item) = item else {
+                    continue;
+                };
+                let Ok(id) = item.get::<_, &str>("id") else {
+                    continue;
+                };
+                // Item stacks store a byte "Count" up to 1.20.4 and an int "count" since 1.20.5
+                let count = match (item.get::<_, i8>("Count"), item.get::<_, i32>("count")) {
+                    (Ok(count), _) => count.max(0) as u64,
+                    (_, Ok(count)) => count.max(0) as u64,
+                    _ => 1,
+                };
+                *contents.entry(id.to_string()).or_insert(0) += count;
+            }
+        }
+
+        BillOfMaterials {
+            blocks: sorted_materials(blocks),
+            contents: sorted_materials(contents),
+        }
+    }
+}
+
+/// Returns the items placing a block, usually one of the block's own item
+fn items_for_block(block: &str) -> Vec<(String, u64)> {
+    let state = BlockState::parse(block);
+    let Some((namespace, name)) = state.id.split_once(':') else {
+        return vec![];
+    };
+    let item = |name: &str| format!("{}:{}", namespace, name);
+
+    if NO_ITEM.contains(&name) {
+        return vec![];
+    }
+    // Only the lower half of doors and tall plants and the foot of beds count
+    if state.property("half") == Some("upper") || state.property("part") == Some("head") {
+        return vec![];
+    }
+
+    if let Some(plant) = name.strip_prefix("potted_") {
+        return vec![(item("flower_pot"), 1), (item(plant), 1)];
+    }
+    if let Some(candle) = name.strip_suffix("_cake") {
+        if candle.ends_with("candle") {
+            return vec![(item("cake"), 1), (item(candle), 1)];
+        }
+    }
+
+    let count = match (name.ends_with("_slab"), state.property("type")) {
+        (true, Some("double")) => 2,
+        _ => ITEM_COUNTS
+            .iter()
+            .find_map(|&property| state.property(property)?.parse().ok())
+            .unwrap_or(1),
+    };
+
+    if let Some(&(_, placed_with)) = PLACED_WITH.iter().find(|(placed, _)| *placed == name) {
+        return vec![(item(placed_with), count)];
+    }
+    for &(wall, standing) in WALL_VARIANTS {
+        if let Some(prefix) = name.strip_suffix(wall) {
+            return vec![(item(&format!("{}{}", prefix, standing)), count)];
+        }
+    }
+
+    vec![(item(name), count)]
+}
+
+/// Turns counted items into materials, the most needed first and then by name
+fn sorted_materials(counts: HashMap<String, u64>) -> Vec<Material> {
+    let mut materials: Vec<Material> = counts
+        .into_iter()
+        .map(|(item, count)| Material { item, count })
+        .collect();
+    materials.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.item.cmp(&b.item)));
+    materials
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quartz_nbt::compound;
+
+    fn count_of(materials: &[Material], item: &str) -> u64 {
+        materials
+            .iter()
+            .find(|material| material.item == item)
+            .map_or(0, |material| material.count)
+    }
+
+    #[test]
+    fn counts_blocks_by_the_item_placing_them() {
+        let mut schematic = MCSchematic::new();
+        schematic.set_block(
+            (0, 0, 0),
+            "minecraft:stone_slab[type=double,waterlogged=false]",
+        );
+        schematic.set_block(
+            (1, 0, 0),
+            "minecraft:stone_slab[type=bottom,waterlogged=true]",
+        );
+        schematic.set_block((2, 0, 0), "minecraft:oak_door[half=lower,facing=east]");
+        schematic.set_block((2, 1, 0), "minecraft:oak_door[half=upper,facing=east]");
+        schematic.set_block((3, 0, 0), "minecraft:red_bed[part=foot,facing=north]");
+        schematic.set_block((3, 0, -1), "minecraft:red_bed[part=head,facing=north]");
+        schematic.set_block((4, 0, 0), "minecraft:wall_torch[facing=east]");
+        schematic.set_block(
+            (5, 0, 0),
+            "minecraft:redstone_wall_torch[facing=east,lit=true]",
+        );
+        schematic.set_block((6, 0, 0), "minecraft:soul_wall_torch[facing=east]");
+        schematic.set_block((7, 0, 0), "minecraft:torch");
+        schematic.set_block((8, 0, 0), "minecraft:redstone_wire[power=3]");
+        schematic.set_block((9, 0, 0), "minecraft:redstone_wire[power=0]");
+        schematic.set_block((10, 0, 0), "minecraft:sea_pickle[pickles=3]");
+        schematic.set_block((11, 0, 0), "minecraft:potted_cactus");
+        schematic.set_block((12, 0, 0), "minecraft:water[level=0]");
+        schematic.set_block((13, 0, 0), "minecraft:oak_wall_sign[facing=east]");
+
+        let bill = schematic.bill_of_materials();
+        assert!(bill.contents.is_empty());
+        assert_eq!(count_of(&bill.blocks, "minecraft:stone_slab"), 3);
+        assert_eq!(count_of(&bill.blocks, "minecraft:oak_door"), 1);
+        assert_eq!(count_of(&bill.blocks, "minecraft:red_bed"), 1);
+        assert_eq!(count_of(&bill.blocks, "minecraft:torch"), 2);
+        assert_eq!(count_of(&bill.blocks, "minecraft:redstone_torch"), 1);
+        assert_eq!(count_of(&bill.blocks, "minecraft:soul_torch"), 1);
+        assert_eq!(count_of(&bill.blocks, "minecraft:redstone"), 2);
+        assert_eq!(count_of(&bill.blocks, "minecraft:sea_pickle"), 3);
+        assert_eq!(count_of(&bill.blocks, "minecraft:flower_pot"), 1);
+        assert_eq!(count_of(&bill.blocks, "minecraft:cactus"), 1);
+        assert_eq!(count_of(&bill.blocks, "minecraft:oak_sign"), 1);
+        assert!(bill
+            .blocks
+            .iter()
+            .all(|material| !material.item.contains("wall")
+                && material.item != "minecraft:water"
+                && material.item != "minecraft:air"));
+
+        // The most needed first, then by name
+        assert_eq!(bill.blocks[0].item, "minecraft:sea_pickle");
+        assert_eq!(bill.blocks[1].item, "minecraft:stone_slab");
+        assert_eq!(bill.blocks[2].item, "minecraft:redstone");
+        assert_eq!(bill.blocks[3].item, "minecraft:torch");
+    }
+
+    #[test]
+    fn lists_container_contents_on_request() {
+        let mut schematic = MCSchematic::new();
+        schematic.set_block_entity(
+            (0, 0, 0),
+            "minecraft:chest[facing=north]",
+            compound!("Items": NbtList::from(vec![
+                NbtTag::Compound(compound!("Slot": 0i8, "id": "minecraft:redstone", "Count": 64i8)),
+                NbtTag::Compound(compound!("Slot": 1i8, "id": "minecraft:redstone", "Count": 10i8)),
+                // Item counts since 1.20.5
+                NbtTag::Compound(compound!("slot": 2, "id": "minecraft:diamond", "count": 3)),
+            ])),
+        );
+
+        assert!(schematic.bill_of_materials().contents.is_empty());
+        let bill = schematic.bill_of_materials_with(&MaterialOptions {
+            container_contents: true,
+        });
+        assert_eq!(
+            bill.blocks,
+            [Material {
+                item: "minecraft:chest".to_string(),
+                count: 1
+            }]
+        );
+        assert_eq!(
+            bill.contents,
+            [
+                Material {
+                    item: "minecraft:redstone".to_string(),
+                    count: 74
+                },
+                Material {
+                    item: "minecraft:diamond".to_string(),
+                    count: 3
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn breaks_counts_into_shulker_boxes_and_stacks() {
+        let material = |item: &str, count| Material {
+            item: item.to_string(),
+            count,
+        };
+        assert_eq!(
+            material("minecraft:stone", 64 * 27 + 64 * 2 + 5).breakdown(),
+            (1, 2, 5)
+        );
+        assert_eq!(material("minecraft:stone", 63).breakdown(), (0, 0, 63));
+        assert_eq!(material("minecraft:ender_pearl", 33).breakdown(), (0, 2, 1));
+        assert_eq!(material("minecraft:oak_door", 1).breakdown(), (0, 0, 1));
+        assert_eq!(material("minecraft:iron_sword", 28).breakdown(), (1, 1, 0));
+
+        let bill = BillOfMaterials {
+            blocks: vec![material("minecraft:stone", 70)],
+            contents: vec![material("minecraft:ender_pearl", 16)],
+        };
+        assert_eq!(
+            bill.to_csv(),
+            "group,item,count,shulker_boxes,stacks,items\n\
+             blocks,minecraft:stone,70,0,1,6\n\
+             contents,minecraft:ender_pearl,16,0,1,0\n"
+        );
+
+        let path = std::env::temp_dir().join(format!("nbt_{}_materials.csv", std::process::id()));
+        let path = path.to_str().unwrap();
+        bill.save_csv(path).unwrap();
+        assert_eq!(fs::read_to_string(path).unwrap(), bill.to_csv());
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -1,25 +1,144 @@
+mod mcstructure;
+pub mod signs;
+pub mod text;
+pub mod validation;
+
 use std::{
     cmp::{max, min},
     collections::HashMap,
-    fs::OpenOptions,
+    fs::{File, OpenOptions},
 };
 
-use quartz_nbt::{self, compound, io::Flavor, NbtCompound, NbtTag};
+use crate::block_registry::{normalize_properties, PropertyForm};
+use crate::block_state::{apply_block, BlockMatcher, BlockState};
+use crate::pattern::Pattern;
+use crate::renames;
+use crate::versions::McVersion;
+use quartz_nbt::{self, compound, io::Flavor, NbtCompound, NbtList, NbtTag};
 
-type BlockPalette<'a> = HashMap<&'a str, i32>;
+type BlockPalette = HashMap<String, i32>;
 type BlockData = HashMap<(i16, i16, i16), i32>;
+type BlockEntities = HashMap<(i16, i16, i16), NbtCompound>;
+type BiomePalette = HashMap<String, i32>;
+type BiomeData = HashMap<(i16, i16, i16), i32>;
+type Entities = Vec<(EntityPos, NbtCompound)>;
 
-type Coords = (i16, i16, i16);
+pub type Coords = (i16, i16, i16);
+pub type EntityPos = (f64, f64, f64);
+type BlockMask<'a> = Box<dyn Fn(Coords, &str) -> bool + 'a>;
 type Byte = i8;
 
 const ADD: fn(i16, i16) -> i16 = |a, b| a + b;
+const SUB: fn(i16, i16) -> i16 = |a, b| a - b;
 const SUB_PLUS_1: fn(i16, i16) -> i16 = |a, b| a - b + 1;
 
+pub const AIR: &str = "minecraft:air";
+/// Biome written for the positions no biome was set at, when the schematic has biomes
+pub const DEFAULT_BIOME: &str = "minecraft:plains";
+
+/// One of the three axes of the Minecraft coordinate system
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+impl Axis {
+    /// Returns the coordinate lying on this axis
+    pub fn component(self, (x, y, z): Coords) -> i16 {
+        match self {
+            Axis::X => x,
+            Axis::Y => y,
+            Axis::Z => z,
+        }
+    }
+}
+
+/// Returns the id of a block without its properties
+fn block_id(block: &str) -> &str {
+    block.split('[').next().unwrap_or(block)
+}
+
+/// Generates the block palette nbt compound tag based on the
+/// list of block names, indexed by their palette index
+fn generate_palette_tag(names: &[String]) -> NbtCompound {
+    let mut palette = NbtCompound::new();
+    for (index, name) in names.iter().enumerate() {
+        palette.insert(name, NbtTag::Int(index as i32));
+    }
+
+    return palette;
+}
+
+/// Reads a palette tag into the list of names, indexed by their palette index
+fn read_palette(palette_tag: &NbtCompound) -> Result<Vec<String>, String> {
+    let mut names = vec![None; palette_tag.len()];
+    for (name, index) in palette_tag.inner() {
+        let Ok(index) = i32::try_from(index) else {
+            return Err(format!("Invalid palette index of {}", name));
+        };
+        let Some(slot) = names.get_mut(index as usize) else {
+            return Err(format!("Palette index of {} is out of range", name));
+        };
+        *slot = Some(name.clone());
+    }
+
+    names
+        .into_iter()
+        .collect::<Option<Vec<String>>>()
+        .ok_or_else(|| "Palette indices must not repeat".to_string())
+}
+
+/// Decodes the given number of varint-encoded palette indices, checking them against the palette size
+fn read_indices(bytes: &[Byte], count: usize, palette_size: usize) -> Result<Vec<usize>, String> {
+    // Every index takes at least one byte, which limits what a malformed count can allocate
+    let mut indices = Vec::with_capacity(count.min(bytes.len()));
+    let mut position = 0;
+
+    for _ in 0..count {
+        let Some((index, length)) = usize::from_varint(&bytes[position..]) else {
+            return Err("Data ends before the schematic is filled".to_string());
+        };
+        if index >= palette_size {
+            return Err(format!("Unknown palette index {}", index));
+        }
+        position += length;
+        indices.push(index);
+    }
+
+    Ok(indices)
+}
+
+/// Reads a tag of the expected type from a compound, describing what's wrong otherwise
+fn read_tag<'t, T>(nbt: &'t NbtCompound, name: &str) -> Result<T, String>
+where
+    T: TryFrom<&'t NbtTag>,
+{
+    match nbt.inner().get(name).map(T::try_from) {
+        Some(Ok(value)) => Ok(value),
+        _ => Err(format!("Missing or invalid \"{}\" tag", name)),
+    }
+}
+
 /// Executes another function on pairs of tuple entries and returns the resulting tuple
 fn on_tuple<T>(f: fn(T, T) -> T, lhs: (T, T, T), rhs: (T, T, T)) -> (T, T, T) {
     (f(lhs.0, rhs.0), f(lhs.1, rhs.1), f(lhs.2, rhs.2))
 }
 
+/// Moves coords by a number of strides, returning None if they end up outside of the range of i16
+pub(crate) fn step_coords(from: Coords, stride: Coords, count: u32) -> Option<Coords> {
+    let step = |from: i16, stride: i16| i16::try_from(from as i64 + stride as i64 * count as i64);
+    match (
+        step(from.0, stride.0),
+        step(from.1, stride.1),
+        step(from.2, stride.2),
+    ) {
+        (Ok(x), Ok(y), Ok(z)) => Some((x, y, z)),
+        _ => None,
+    }
+}
+
 /// Trait responsible for converting a numeric type to a vector of bytes, according to the varint format
 /// and vice versa.
 /// https://github.com/SpongePowered/Schematic-Specification/blob/master/versions/schematic-2.md
@@ -30,6 +149,12 @@ fn on_tuple<T>(f: fn(T, T) -> T, lhs: (T, T, T), rhs: (T, T, T)) -> (T, T, T) {
 /// found with Sponge's implementation for retrieving data and storing data."
 pub trait Varint {
     fn to_varint(self) -> Vec<Byte>;
+
+    /// Decodes the number at the start of the bytes, returning it together with
+    /// the amount of bytes it took, or None if the bytes end in the middle of it
+    fn from_varint(bytes: &[Byte]) -> Option<(Self, usize)>
+    where
+        Self: Sized;
 }
 
 impl Varint for usize {
@@ -55,35 +180,116 @@ impl Varint for usize {
         }
         return output;
     }
+
+    fn from_varint(bytes: &[Byte]) -> Option<(usize, usize)> {
+        let mut value = 0;
+
+        for (length, &byte) in bytes.iter().enumerate().take(5) {
+            value |= ((byte & 127) as usize) << (length * 7);
+
+            if byte >= 0 {
+                return Some((value, length + 1));
+            }
+        }
+
+        None
+    }
+}
+
+/// Size of a schematic along each axis. The Sponge format calls the size
+/// along x the Width, along y the Height and along z the Length
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Dimensions {
+    pub width_x: i16,
+    pub height_y: i16,
+    pub length_z: i16,
+}
+
+impl Dimensions {
+    /// Returns the number of blocks inside the box
+    pub fn volume(&self) -> usize {
+        self.width_x as usize * self.height_y as usize * self.length_z as usize
+    }
+
+    /// Returns the index of the given coords, relative to the lowest corner, in a block data array:
+    /// x + z * Width + y * Width * Length
+    pub fn index(&self, (x, y, z): Coords) -> usize {
+        let (width, length) = (self.width_x as usize, self.length_z as usize);
+
+        x as usize + z as usize * width + y as usize * width * length
+    }
+
+    /// Returns the coords relative to the lowest corner stored under an index
+    /// of a block data array, the inverse of Dimensions::index
+    pub fn coords(&self, index: usize) -> Coords {
+        let (width, length) = (self.width_x as usize, self.length_z as usize);
+
+        let x = index % width;
+        let z = (index / width) % length;
+        let y = index / (width * length);
+        (x as i16, y as i16, z as i16)
+    }
+
+    /// Iterates over all coords relative to the lowest corner, in block data order
+    pub fn positions(self) -> impl Iterator<Item = Coords> {
+        (0..self.volume()).map(move |index| self.coords(index))
+    }
+}
+
+/// Revision of the Sponge schematic specification a schematic is written in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SpongeVersion {
+    /// Version 2, with biomes stored per column
+    #[default]
+    V2,
+    /// Version 3, with the blocks and biomes in their own compounds and biomes stored per block
+    V3,
 }
 
 /// Main schematic struct containing all properties of the schematic
-pub struct MCSchematic<'a> {
-    block_palette: BlockPalette<'a>,
+#[derive(Clone)]
+pub struct MCSchematic {
+    block_palette: BlockPalette,
+    /// Block names of the palette indexed by their palette index, so looking up
+    /// the block at some coords doesn't have to search the palette
+    block_names: Vec<String>,
     block_data: BlockData,
+    block_entities: BlockEntities,
+
+    biome_palette: BiomePalette,
+    biome_data: BiomeData,
+
+    entities: Entities,
 
     lowest_coords: Coords,
     highest_coords: Coords,
 
-    length: i16,
-    width: i16,
-    height: i16,
+    dimensions: Dimensions,
 }
 
-impl<'a> MCSchematic<'a> {
+impl Default for MCSchematic {
+    fn default() -> Self {
+        MCSchematic::new()
+    }
+}
+
+impl MCSchematic {
     /// Returns a new, empty instance of the MCSchematic structure.
     /// The block palette, schematic boundaries and the blocks list get updated
     /// as new blocks are placed in the schematic
-    pub fn new() -> MCSchematic<'a> {
+    pub fn new() -> MCSchematic {
         MCSchematic {
-            block_palette: BlockPalette::from([("minecraft:air", 0)]),
+            block_palette: BlockPalette::from([(AIR.to_string(), 0)]),
+            block_names: vec![AIR.to_string()],
             block_data: BlockData::new(),
+            block_entities: BlockEntities::new(),
+            biome_palette: BiomePalette::new(),
+            biome_data: BiomeData::new(),
+            entities: Entities::new(),
             lowest_coords: (0, 0, 0),
             highest_coords: (0, 0, 0),
 
-            length: 0,
-            height: 0,
-            width: 0,
+            dimensions: Dimensions::default(),
         }
     }
 
@@ -93,18 +299,15 @@ impl<'a> MCSchematic<'a> {
     /// coords - desired block coordinates: (x, y, z)
     ///
     /// block_data - full in-game id of the block, such as "minecraft::stone"
-    pub fn set_block(&mut self, coords: Coords, block_data: &'a str) {
-        // Store the current palette size
-        let palette_size = self.block_palette.len() as i32;
-
-        let palette_index = match self.block_palette.insert(block_data, palette_size) {
-            Some(_) => self.block_palette[block_data],
-            None => palette_size,
-        };
+    pub fn set_block(&mut self, coords: Coords, block_data: &str) {
+        let palette_index = self.palette_index(block_data);
 
         // Add the new block to the blocks list with the given coords and its index in the palette
         self.block_data.insert(coords, palette_index);
 
+        // Any block entity that was stored at these coords belonged to the replaced block
+        self.block_entities.remove(&coords);
+
         // Update the lowest and highest coords if needed
         if self.block_data.len() == 1 {
             self.lowest_coords = coords;
@@ -115,101 +318,1569 @@ impl<'a> MCSchematic<'a> {
         }
     }
 
+    /// Adds a new block together with its block entity data, such as the inventory
+    /// of a barrel or the text of a sign.
+    ///
+    /// coords - desired block coordinates: (x, y, z)
+    ///
+    /// block_data - full in-game id of the block, such as "minecraft:barrel[facing=up]"
+    ///
+    /// entity_data - the block entity nbt, without the "Pos" tag. "Id" defaults to
+    /// the block id without its properties if it's not provided
+    pub fn set_block_entity(&mut self, coords: Coords, block_data: &str, entity_data: NbtCompound) {
+        self.set_block(coords, block_data);
+        self.block_entities.insert(coords, entity_data);
+    }
+
+    /// Returns the full in-game id of the block at the given coords,
+    /// or "minecraft:air" if no block was placed there
+    pub fn get_block(&self, coords: Coords) -> &str {
+        match self.block_data.get(&coords) {
+            Some(&index) => self.palette_name(index),
+            None => AIR,
+        }
+    }
+
+    /// Iterates over the coords and full in-game ids of all placed blocks, in no particular order
+    pub fn blocks(&self) -> impl Iterator<Item = (Coords, &str)> {
+        self.block_data
+            .iter()
+            .map(|(&coords, &index)| (coords, self.palette_name(index)))
+    }
+
+    /// Sets the biome at the given coords, using a palette separate from the blocks.
+    /// Biomes don't extend the schematic boundaries, the ones set outside of the
+    /// placed blocks aren't saved
+    ///
+    /// coords - desired biome coordinates: (x, y, z)
+    ///
+    /// biome - full in-game id of the biome, such as "minecraft:plains"
+    pub fn set_biome(&mut self, coords: Coords, biome: &str) {
+        let palette_size = self.biome_palette.len() as i32;
+
+        let palette_index = *self
+            .biome_palette
+            .entry(biome.to_string())
+            .or_insert(palette_size);
+        self.biome_data.insert(coords, palette_index);
+    }
+
+    /// Returns the biome set at the given coords, if there is one
+    pub fn get_biome(&self, coords: Coords) -> Option<&str> {
+        let &index = self.biome_data.get(&coords)?;
+
+        self.biome_palette
+            .iter()
+            .find(|(_, &i)| i == index)
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Adds an entity, such as an armor stand or an item frame, at an exact position.
+    /// Entities don't extend the schematic boundaries
+    ///
+    /// pos - position of the entity in the same space as the block coords: (x, y, z)
+    ///
+    /// id - full in-game id of the entity, such as "minecraft:armor_stand"
+    ///
+    /// entity_data - the entity nbt, without the "Pos" and "Id" tags
+    pub fn add_entity(&mut self, pos: EntityPos, id: &str, entity_data: NbtCompound) {
+        let mut entity = entity_data;
+        entity.insert("Id", id);
+
+        self.entities.push((pos, entity));
+    }
+
+    /// Returns every entity along with its position, in the order they were added
+    pub fn get_entities(&self) -> &[(EntityPos, NbtCompound)] {
+        &self.entities
+    }
+
+    /// Fills the box spanned by the two given corners (inclusive) with blocks from a pattern
+    ///
+    /// corner_a, corner_b - opposite corners of the box: (x, y, z)
+    ///
+    /// pattern - the blocks to place, such as Pattern::from("minecraft:stone")
+    pub fn fill(&mut self, corner_a: Coords, corner_b: Coords, pattern: &Pattern) {
+        let (x1, y1, z1) = on_tuple(min, corner_a, corner_b);
+        let (x2, y2, z2) = on_tuple(max, corner_a, corner_b);
+
+        for y in y1..=y2 {
+            for z in z1..=z2 {
+                for x in x1..=x2 {
+                    let block =
+                        apply_block(self.get_block((x, y, z)), &pattern.block_at((x, y, z)));
+                    self.set_block((x, y, z), &block);
+                }
+            }
+        }
+    }
+
+    /// Replaces every block selected by a matcher with blocks from a pattern.
+    /// Block entities are kept as long as the block id stays the same.
+    ///
+    /// When the pattern is a single block, the matching palette entries get rewritten
+    /// instead of visiting every block, so swapping a material is cheap no matter how
+    /// many blocks use it. Air is only ever replaced position by position, including
+    /// the unset positions inside the schematic boundaries
+    ///
+    /// from - the blocks to replace, such as BlockMatcher::parse("minecraft:repeater[delay=*]")
+    ///
+    /// to - the new blocks, such as Pattern::parse("[delay=1]") to only change a property
+    pub fn replace(&mut self, from: &BlockMatcher, to: &Pattern) {
+        let names: Vec<String> = self
+            .palette_names()
+            .iter()
+            .map(|&name| name.to_string())
+            .collect();
+        let mut matched: Vec<i32> = (0..names.len() as i32)
+            .filter(|&index| from.matches(&names[index as usize]))
+            .collect();
+
+        if let Pattern::Block(block) = to {
+            for &index in matched.iter().filter(|&&index| index != 0) {
+                let old_name = &names[index as usize];
+                self.rename_palette_entry(old_name, &apply_block(old_name, block));
+            }
+            matched.retain(|&index| index == 0);
+        }
+
+        if matched.is_empty() {
+            return;
+        }
+
+        let positions: Vec<Coords> = if matched.contains(&0) {
+            self.get_dimensions()
+                .positions()
+                .map(|offset| on_tuple(ADD, offset, self.lowest_coords))
+                .filter(|coords| {
+                    matched.contains(&self.block_data.get(coords).copied().unwrap_or(0))
+                })
+                .collect()
+        } else {
+            self.block_data
+                .iter()
+                .filter(|(_, index)| matched.contains(index))
+                .map(|(&coords, _)| coords)
+                .collect()
+        };
+
+        for coords in positions {
+            let block = apply_block(self.get_block(coords), &to.block_at(coords));
+            let entity = self.block_entities.remove(&coords);
+            let same_id = block_id(&block) == block_id(self.get_block(coords));
+
+            self.set_block(coords, &block);
+            if let (Some(entity), true) = (entity, same_id) {
+                self.block_entities.insert(coords, entity);
+            }
+        }
+    }
+
+    /// Returns the block entity data stored at the given coords, if there is any
+    pub fn get_block_entity(&self, coords: Coords) -> Option<&NbtCompound> {
+        self.block_entities.get(&coords)
+    }
+
+    /// Pastes another schematic into this one, merging the block palettes
+    /// and copying the block entities along with the blocks.
+    ///
+    /// other - the schematic to paste; its whole bounding box is copied,
+    /// with unset positions treated as air
+    ///
+    /// at - the coords in this schematic where the lowest corner of `other` ends up
+    ///
+    /// options - which blocks get pasted, see PasteOptions
+    pub fn paste(&mut self, other: &MCSchematic, at: Coords, options: &PasteOptions) {
+        if other.block_data.is_empty() {
+            return;
+        }
+
+        for offset in other.get_dimensions().positions() {
+            let source = on_tuple(ADD, offset, other.lowest_coords);
+            let target = on_tuple(ADD, offset, at);
+            let block = other.get_block(source);
+
+            if options.skip_air && block == AIR {
+                continue;
+            }
+            if options.only_replace_air && self.get_block(target) != AIR {
+                continue;
+            }
+            if let Some(mask) = &options.mask {
+                if !mask(target, block) {
+                    continue;
+                }
+            }
+
+            match other.block_entities.get(&source) {
+                Some(entity) => self.set_block_entity(target, block, entity.clone()),
+                None => self.set_block(target, block),
+            }
+            if let Some(biome) = other.get_biome(source) {
+                self.set_biome(target, biome);
+            }
+        }
+
+        let offset = on_tuple(SUB, at, other.lowest_coords);
+        for (pos, entity) in other.entities.iter() {
+            self.entities
+                .push((translate_entity(*pos, offset), entity.clone()));
+        }
+    }
+
+    /// Returns a new schematic containing a copy of the blocks, block entities, biomes
+    /// and entities inside the box spanned by the two given corners (inclusive).
+    /// The copied blocks keep their original coords
+    pub fn copy_region(&self, corner_a: Coords, corner_b: Coords) -> MCSchematic {
+        let lowest = on_tuple(min, corner_a, corner_b);
+        let highest = on_tuple(max, corner_a, corner_b);
+        let names = self.palette_names();
+
+        let mut region = MCSchematic::new();
+        for (&coords, &index) in self.block_data.iter() {
+            if on_tuple(max, coords, lowest) != coords || on_tuple(min, coords, highest) != coords {
+                continue;
+            }
+
+            match self.block_entities.get(&coords) {
+                Some(entity) => {
+                    region.set_block_entity(coords, names[index as usize], entity.clone())
+                }
+                None => region.set_block(coords, names[index as usize]),
+            }
+        }
+        for &coords in self.biome_data.keys() {
+            if on_tuple(max, coords, lowest) == coords && on_tuple(min, coords, highest) == coords {
+                region.set_biome(coords, self.get_biome(coords).unwrap_or(DEFAULT_BIOME));
+            }
+        }
+        for (pos, entity) in self.entities.iter() {
+            let block = (
+                pos.0.floor() as i16,
+                pos.1.floor() as i16,
+                pos.2.floor() as i16,
+            );
+            if on_tuple(max, block, lowest) == block && on_tuple(min, block, highest) == block {
+                region.entities.push((*pos, entity.clone()));
+            }
+        }
+
+        region
+    }
+
+    /// Repeats the whole schematic along a single direction.
+    ///
+    /// count - total number of instances, including the original one
+    ///
+    /// offset - distance between the lowest corners of two consecutive instances
+    pub fn stack(&mut self, count: u16, offset: Coords) -> Result<(), String> {
+        self.array((count, 1, 1), (offset, (0, 0, 0), (0, 0, 0)))
+    }
+
+    /// Repeats the whole schematic along up to three directions, forming a 3D grid of copies.
+    ///
+    /// counts - total number of instances along each of the three directions, including the original one
+    ///
+    /// strides - offset between two consecutive instances for each of the three directions
+    pub fn array(
+        &mut self,
+        counts: (u16, u16, u16),
+        strides: (Coords, Coords, Coords),
+    ) -> Result<(), String> {
+        let cell = self.clone();
+        self.array_with(&cell, self.lowest_coords, counts, strides, |_, _, _| {})
+    }
+
+    /// Places a 3D grid of copies of another schematic (or of a region taken with copy_region)
+    /// and lets the caller customize each of them.
+    ///
+    /// cell - the schematic to repeat. Only the placed blocks get copied, so unset
+    /// positions don't overwrite neighbouring instances
+    ///
+    /// origin - the coords where the lowest corner of the first instance ends up
+    ///
+    /// counts - total number of instances along each of the three directions
+    ///
+    /// strides - offset between two consecutive instances for each of the three directions
+    ///
+    /// per_instance - called after each copy is placed with the schematic, the instance index
+    /// and the offset that was added to the cell coords, e.g. to set a different ROM value per cell
+    ///
+    /// Returns an error without placing anything if an instance would end up outside of the
+    /// range of coords
+    pub fn array_with<F>(
+        &mut self,
+        cell: &MCSchematic,
+        origin: Coords,
+        counts: (u16, u16, u16),
+        strides: (Coords, Coords, Coords),
+        mut per_instance: F,
+    ) -> Result<(), String>
+    where
+        F: FnMut(&mut MCSchematic, (u16, u16, u16), Coords),
+    {
+        if cell.block_data.is_empty() {
+            return Ok(());
+        }
+
+        // Work out every offset first, so nothing is placed if one of them doesn't fit
+        let lowest = cell.lowest_coords;
+        let base = match (
+            origin.0.checked_sub(lowest.0),
+            origin.1.checked_sub(lowest.1),
+            origin.2.checked_sub(lowest.2),
+        ) {
+            (Some(x), Some(y), Some(z)) => Some((x, y, z)),
+            _ => None,
+        };
+        let mut instances = vec![];
+        for k in 0..counts.2 {
+            for j in 0..counts.1 {
+                for i in 0..counts.0 {
+                    let offset = base
+                        .and_then(|offset| step_coords(offset, strides.0, i as u32))
+                        .and_then(|offset| step_coords(offset, strides.1, j as u32))
+                        .and_then(|offset| step_coords(offset, strides.2, k as u32))
+                        .filter(|&offset| {
+                            step_coords(cell.lowest_coords, offset, 1).is_some()
+                                && step_coords(cell.highest_coords, offset, 1).is_some()
+                        });
+                    let Some(offset) = offset else {
+                        return Err(format!(
+                            "Instance {:?} doesn't fit in the range of coords",
+                            (i, j, k)
+                        ));
+                    };
+                    instances.push(((i, j, k), offset));
+                }
+            }
+        }
+
+        let names = cell.palette_names();
+        for (instance, offset) in instances {
+            for (&coords, &index) in cell.block_data.iter() {
+                self.set_block(on_tuple(ADD, coords, offset), names[index as usize]);
+            }
+            for (&coords, entity) in cell.block_entities.iter() {
+                self.block_entities
+                    .insert(on_tuple(ADD, coords, offset), entity.clone());
+            }
+            for &coords in cell.biome_data.keys() {
+                let biome = cell.get_biome(coords).unwrap_or(DEFAULT_BIOME);
+                self.set_biome(on_tuple(ADD, coords, offset), biome);
+            }
+            for (pos, entity) in cell.entities.iter() {
+                self.entities
+                    .push((translate_entity(*pos, offset), entity.clone()));
+            }
+
+            per_instance(self, instance, offset);
+        }
+
+        Ok(())
+    }
+
+    /// Turns the whole schematic clockwise (seen from above) by the given number of
+    /// quarter turns around the y axis. The lowest corner stays in place, while block
+    /// states, block entities, biomes and entities are rotated along with the blocks
+    pub fn rotate_y(&mut self, quarter_turns: u8) {
+        let turns = quarter_turns % 4;
+        if turns == 0 {
+            return;
+        }
+
+        let (lx, _, lz) = self.lowest_coords;
+        let Dimensions {
+            width_x, length_z, ..
+        } = self.get_dimensions();
+
+        // Rotates coords relative to the lowest corner, `size` being 1 for blocks and 0 for entities
+        let rotate = |(x, z): (f64, f64), size: f64| {
+            let (mut x, mut z) = (x, z);
+            let (mut width, mut length) = (width_x as f64, length_z as f64);
+            for _ in 0..turns {
+                (x, z) = (length - size - z, x);
+                (width, length) = (length, width);
+            }
+            (x, z)
+        };
+        let rotate_coords = |(x, y, z): Coords| {
+            let (x, z) = rotate(((x - lx) as f64, (z - lz) as f64), 1.0);
+            (x as i16 + lx, y, z as i16 + lz)
+        };
+
+        let names: Vec<String> = self
+            .palette_names()
+            .iter()
+            .map(|&name| BlockState::parse(name).rotate_y(turns).to_string())
+            .collect();
+        let block_data = std::mem::take(&mut self.block_data);
+        let block_entities = std::mem::take(&mut self.block_entities);
+        self.block_palette = BlockPalette::from([(AIR.to_string(), 0)]);
+        self.block_names = vec![AIR.to_string()];
+
+        for (coords, index) in block_data {
+            self.set_block(rotate_coords(coords), &names[index as usize]);
+        }
+        for (coords, entity) in block_entities {
+            self.block_entities.insert(rotate_coords(coords), entity);
+        }
+
+        self.biome_data = std::mem::take(&mut self.biome_data)
+            .into_iter()
+            .map(|(coords, index)| (rotate_coords(coords), index))
+            .collect();
+
+        for (pos, entity) in self.entities.iter_mut() {
+            let (x, z) = rotate((pos.0 - lx as f64, pos.2 - lz as f64), 0.0);
+            *pos = (x + lx as f64, pos.1, z + lz as f64);
+            rotate_entity_data(entity, turns);
+        }
+    }
+
     /// Saves the generated schematic to the desired path
     ///
     /// file_path - location and name of the target file
     ///
-    /// version - MC version id, use constants from mcschematic::versions
-    pub fn save(&mut self, file_path: &'a str, version: i32) -> Result<String, String> {
+    /// version - MC version, use constants from versions or McVersion::from_name
+    pub fn save(&mut self, file_path: &str, version: McVersion) -> Result<String, String> {
+        self.save_with(file_path, version, &SaveOptions::default())
+    }
+
+    /// Saves the generated schematic to the desired path, see MCSchematic::save
+    ///
+    /// options - how the blocks and biomes are written, see SaveOptions
+    pub fn save_with(
+        &mut self,
+        file_path: &str,
+        version: McVersion,
+        options: &SaveOptions,
+    ) -> Result<String, String> {
+        // Drop the unused palette entries and put the rest in a deterministic order
+        self.compact_palette();
+
+        // Store the dimensions of the generated schematic
+        self.dimensions = self.get_dimensions();
+
+        // Rename the blocks to what the target version calls them, before the file
+        // gets overwritten, as some blocks may not exist in that version
+        let (names, remap) = self.export_palette(version, options)?;
+
         // Open the target schematic file with the provided name
         let Ok(mut file_out) = OpenOptions::new()
             .write(true)
             .create(true)
+            .truncate(true)
             .open(file_path)
         else {
             return Err("Failed to save schematic".to_string());
         };
-
-        // Store the dimensions of the generated schematic
-        (self.length, self.height, self.width) = self.get_dimensions();
-
-        let palette_tag = self.generate_palette_tag();
+        let palette_tag = generate_palette_tag(&names);
 
         // Create the BlockData from the accumulated list of blocks
-        let block_data_tag = self.generate_block_data_tag();
+        let block_data_tag = self.generate_block_data_tag(&remap);
 
         // Create the BlockEntities tag
-        let block_entities_tag = self.generate_block_entities_tag();
+        let block_entities_tag = self.generate_block_entities_tag(options.format);
+
+        // Create the biome palette and data, if any biomes were set
+        let biome_tags = self.generate_biome_tags(options.format);
+
+        // Create the Entities tag
+        let entities_tag = self.generate_entities_tag(options.format);
+
+        let metadata = compound!(
+            "MCSchematicMetadata" : {
+                "Mitochondria": "is the powerhouse of a cell"
+            }
+        );
 
         // Create a new nbt root
-        let nbt: NbtCompound = compound!(
-            "DataVersion": NbtTag::Int(version),
-            "Version": NbtTag::Int(2),
+        let (root_name, nbt) = match options.format {
+            SpongeVersion::V2 => {
+                let mut nbt: NbtCompound = compound!(
+                    "DataVersion": NbtTag::Int(version.data_version()),
+                    "Version": NbtTag::Int(2),
+
+                    "PaletteMax": NbtTag::Int(names.len() as i32),
+                    "Palette": palette_tag,
 
-            "PalettteMax": NbtTag::Int(self.block_palette.len() as i32),
-            "Palette": palette_tag,
+                    "BlockData": block_data_tag,
+                    "BlockEntities": block_entities_tag,
 
-            "BlockData": block_data_tag,
-            "BlockEntities": block_entities_tag,
+                    "Metadata": metadata,
 
-            "Metadata": {
-                "MCSchematicMetadata" : {
-                    "Mitochondria": "is the powerhouse of a cell"
+                    "Width": NbtTag::Short(self.dimensions.width_x),
+                    "Height": NbtTag::Short(self.dimensions.height_y),
+                    "Length": NbtTag::Short(self.dimensions.length_z)
+                );
+                if let Some((biome_palette_tag, biome_data_tag)) = biome_tags {
+                    nbt.insert(
+                        "BiomePaletteMax",
+                        NbtTag::Int(biome_palette_tag.len() as i32),
+                    );
+                    nbt.insert("BiomePalette", biome_palette_tag);
+                    nbt.insert("BiomeData", biome_data_tag);
+                }
+                if !entities_tag.is_empty() {
+                    nbt.insert("Entities", entities_tag);
                 }
-            },
 
-            "Length": NbtTag::Short(self.length),
-            "Height": NbtTag::Short(self.height),
-            "Width": NbtTag::Short(self.width)
-        );
+                ("Schematic", nbt)
+            }
+            SpongeVersion::V3 => {
+                let mut schematic: NbtCompound = compound!(
+                    "Version": NbtTag::Int(3),
+                    "DataVersion": NbtTag::Int(version.data_version()),
 
-        quartz_nbt::io::write_nbt(&mut file_out, Some("Schematic"), &nbt, Flavor::GzCompressed)
-            .expect("TODO: panic message");
+                    "Metadata": metadata,
 
-        Ok(format!("Saved to {}", file_path))
+                    "Width": NbtTag::Short(self.dimensions.width_x),
+                    "Height": NbtTag::Short(self.dimensions.height_y),
+                    "Length": NbtTag::Short(self.dimensions.length_z),
+
+                    "Blocks": {
+                        "Palette": palette_tag,
+                        "Data": block_data_tag,
+                        "BlockEntities": block_entities_tag
+                    }
+                );
+                if let Some((biome_palette_tag, biome_data_tag)) = biome_tags {
+                    schematic.insert(
+                        "Biomes",
+                        compound!("Palette": biome_palette_tag, "Data": biome_data_tag),
+                    );
+                }
+                if !entities_tag.is_empty() {
+                    schematic.insert("Entities", entities_tag);
+                }
+
+                ("", compound!("Schematic": schematic))
+            }
+        };
+
+        match quartz_nbt::io::write_nbt(&mut file_out, Some(root_name), &nbt, Flavor::GzCompressed)
+        {
+            Ok(()) => Ok(format!("Saved to {}", file_path)),
+            Err(_) => Err(format!("Failed to save {}", file_path)),
+        }
     }
 
-    /// Generates the block palette nbt compound tag based on the
-    /// list of blocks used in the generating program
-    fn generate_palette_tag(&self) -> NbtCompound {
-        let mut palette = NbtCompound::new();
-        for (&name, &index) in self.block_palette.iter() {
-            palette.insert(name, NbtTag::Int(index));
+    /// Loads a Sponge schematic (version 2 or 3) from the desired path.
+    /// The lowest corner of the loaded schematic ends up at (0, 0, 0)
+    ///
+    /// file_path - location and name of the schematic file
+    pub fn load(file_path: &str) -> Result<MCSchematic, String> {
+        let Ok(mut file_in) = File::open(file_path) else {
+            return Err(format!("Failed to open {}", file_path));
+        };
+        let Ok((root, _)) = quartz_nbt::io::read_nbt(&mut file_in, Flavor::GzCompressed) else {
+            return Err(format!("{} is not a valid schematic file", file_path));
+        };
+
+        // Version 3 wraps everything in a "Schematic" compound
+        let (nbt, format) = match root.get::<_, &NbtCompound>("Schematic") {
+            Ok(nbt) => (nbt, SpongeVersion::V3),
+            Err(_) => (&root, SpongeVersion::V2),
+        };
+
+        let dimensions = Dimensions {
+            width_x: read_tag(nbt, "Width")?,
+            height_y: read_tag(nbt, "Height")?,
+            length_z: read_tag(nbt, "Length")?,
+        };
+        if dimensions.width_x < 0 || dimensions.height_y < 0 || dimensions.length_z < 0 {
+            return Err(format!(
+                "Invalid dimensions {}x{}x{}",
+                dimensions.width_x, dimensions.height_y, dimensions.length_z
+            ));
+        }
+
+        let data_version: i32 = read_tag(nbt, "DataVersion")?;
+        let mut schematic = MCSchematic::new();
+
+        let (blocks, data_name) = match format {
+            SpongeVersion::V2 => (nbt, "BlockData"),
+            SpongeVersion::V3 => (read_tag(nbt, "Blocks")?, "Data"),
+        };
+
+        // Map the palette indices of the file to the ones of the new schematic,
+        // renaming blocks saved by older versions to their current names
+        let names = read_palette(read_tag(blocks, "Palette")?)?;
+        let mut remap = vec![];
+        for name in names.iter() {
+            remap.push(schematic.palette_index(&renames::upgrade(name, data_version)?));
+        }
+
+        // Decode the block data, leaving air out of the blocks list
+        let block_data_tag: &[Byte] = read_tag(blocks, data_name)?;
+        let indices = read_indices(block_data_tag, dimensions.volume(), remap.len())?;
+        for (index, palette_index) in indices.into_iter().enumerate() {
+            if remap[palette_index] != 0 {
+                schematic
+                    .block_data
+                    .insert(dimensions.coords(index), remap[palette_index]);
+            }
+        }
+
+        // Block entities keep everything apart from their position,
+        // version 3 moves their data into a "Data" compound
+        if let Ok(block_entities_tag) = blocks.get::<_, &NbtList>("BlockEntities") {
+            for entity in block_entities_tag.iter_map::<&NbtCompound>().flatten() {
+                let pos: &[i32] = read_tag(entity, "Pos")?;
+                let [x, y, z] = pos else {
+                    return Err("Block entity Pos must hold three coords".to_string());
+                };
+
+                let mut entity_data = match entity.get::<_, &NbtCompound>("Data") {
+                    Ok(data) => data.clone(),
+                    Err(_) => entity.clone(),
+                };
+                if let Ok(id) = entity.get::<_, &str>("Id") {
+                    entity_data.insert("Id", id);
+                }
+                entity_data.inner_mut().shift_remove("Pos");
+                schematic
+                    .block_entities
+                    .insert((*x as i16, *y as i16, *z as i16), entity_data);
+            }
+        }
+
+        // Biomes are stored per column in version 2 and per block in version 3
+        let biomes = match format {
+            SpongeVersion::V2 if nbt.contains_key("BiomePalette") => Some((
+                read_tag(nbt, "BiomePalette")?,
+                read_tag(nbt, "BiomeData")?,
+                Dimensions {
+                    height_y: 1,
+                    ..dimensions
+                },
+            )),
+            SpongeVersion::V3 if nbt.contains_key("Biomes") => {
+                let biomes: &NbtCompound = read_tag(nbt, "Biomes")?;
+                Some((
+                    read_tag(biomes, "Palette")?,
+                    read_tag(biomes, "Data")?,
+                    dimensions,
+                ))
+            }
+            _ => None,
+        };
+        if let Some((biome_palette_tag, biome_data_tag, biome_dimensions)) = biomes {
+            let biome_names = read_palette(biome_palette_tag)?;
+            let indices =
+                read_indices(biome_data_tag, biome_dimensions.volume(), biome_names.len())?;
+
+            for (index, palette_index) in indices.into_iter().enumerate() {
+                let (x, _, z) = biome_dimensions.coords(index);
+                match format {
+                    SpongeVersion::V2 => {
+                        for y in 0..dimensions.height_y {
+                            schematic.set_biome((x, y, z), &biome_names[palette_index]);
+                        }
+                    }
+                    SpongeVersion::V3 => {
+                        let coords = dimensions.coords(index);
+                        schematic.set_biome(coords, &biome_names[palette_index]);
+                    }
+                }
+            }
+        }
+
+        // Entities keep everything apart from their position, version 3 moves
+        // their data into a "Data" compound like it does for block entities
+        if let Ok(entities_tag) = nbt.get::<_, &NbtList>("Entities") {
+            for entity in entities_tag.iter_map::<&NbtCompound>().flatten() {
+                let pos: &NbtList = read_tag(entity, "Pos")?;
+                let pos: Vec<f64> = pos.iter_map::<f64>().flatten().collect();
+                let [x, y, z] = pos[..] else {
+                    return Err("Entity Pos must hold three coords".to_string());
+                };
+
+                let mut entity_data = match entity.get::<_, &NbtCompound>("Data") {
+                    Ok(data) => data.clone(),
+                    Err(_) => entity.clone(),
+                };
+                entity_data.inner_mut().shift_remove("Id");
+                entity_data.inner_mut().shift_remove("Pos");
+                let id: &str = read_tag(entity, "Id")?;
+                schematic.add_entity((x, y, z), id, entity_data);
+            }
+        }
+
+        if dimensions.volume() > 0 {
+            schematic.highest_coords = on_tuple(
+                SUB,
+                (dimensions.width_x, dimensions.height_y, dimensions.length_z),
+                (1, 1, 1),
+            );
         }
+        schematic.dimensions = dimensions;
 
-        return palette;
+        Ok(schematic)
     }
 
     /// Generates the block data object, which is a byte array of varint-encoded numbers
     /// that correspond to indexes in the block palette
     ///
-    /// The entries are indexed by: x + z * Width + y * Width * Length relative to the lowest coords
-    fn generate_block_data_tag(&self) -> NbtTag {
+    /// The entries are indexed by: x + z * Width + y * Width * Length relative to the lowest coords,
+    /// see Dimensions::index
+    ///
+    /// remap - the index written for each palette index, see MCSchematic::export_palette
+    fn generate_block_data_tag(&self, remap: &[i32]) -> NbtTag {
         let mut bytes: Vec<Byte> = vec![];
 
-        for y in 0..self.height {
-            for z in 0..self.width {
-                for x in 0..self.length {
-                    let coords = on_tuple(ADD, (x, y, z), self.lowest_coords);
+        for offset in self.dimensions.positions() {
+            let coords = on_tuple(ADD, offset, self.lowest_coords);
+
+            bytes.extend(match self.block_data.get(&coords) {
+                Some(&v) => (remap[v as usize] as usize).to_varint(),
+                None => vec![0],
+            });
+        }
+
+        return NbtTag::ByteArray(bytes);
+    }
+
+    /// Generates the list of block entities, each one holding its position
+    /// relative to the lowest coords in the "Pos" tag. Version 3 keeps
+    /// "Pos" and "Id" next to a "Data" compound holding the rest
+    fn generate_block_entities_tag(&self, format: SpongeVersion) -> NbtList {
+        let mut block_entities = NbtList::new();
+
+        let mut positions: Vec<&Coords> = self.block_entities.keys().collect();
+        positions.sort_by_key(|&&(x, y, z)| (y, z, x));
 
-                    bytes.extend(match self.block_data.get(&coords) {
-                        Some(&v) => (v as usize).to_varint(),
-                        None => vec![0],
-                    });
+        for &coords in positions {
+            let entity_data = &self.block_entities[&coords];
+            let (x, y, z) = on_tuple(SUB, coords, self.lowest_coords);
+
+            let mut entity = entity_data.clone();
+            let id = match entity.inner_mut().shift_remove("Id") {
+                Some(id) => id,
+                None => NbtTag::String(block_id(self.get_block(coords)).to_string()),
+            };
+            let pos = NbtTag::IntArray(vec![x as i32, y as i32, z as i32]);
+
+            match format {
+                SpongeVersion::V2 => {
+                    entity.insert("Id", id);
+                    entity.insert("Pos", pos);
+                    block_entities.push(entity);
+                }
+                SpongeVersion::V3 => {
+                    block_entities.push(compound!("Pos": pos, "Id": id, "Data": entity));
                 }
             }
         }
 
-        return NbtTag::ByteArray(bytes);
+        block_entities
     }
 
-    fn generate_block_entities_tag(&self) -> NbtCompound {
-        return NbtCompound::new();
+    /// Generates the list of entities, each one holding its position relative to the
+    /// lowest coords in the "Pos" tag. Version 3 keeps "Pos" and "Id" next to a
+    /// "Data" compound holding the rest
+    fn generate_entities_tag(&self, format: SpongeVersion) -> NbtList {
+        let mut entities = NbtList::new();
+
+        for (pos, entity_data) in self.entities.iter() {
+            let (lx, ly, lz) = self.lowest_coords;
+            let pos = NbtList::from(vec![
+                NbtTag::Double(pos.0 - lx as f64),
+                NbtTag::Double(pos.1 - ly as f64),
+                NbtTag::Double(pos.2 - lz as f64),
+            ]);
+
+            let mut entity = entity_data.clone();
+            let id = entity
+                .inner_mut()
+                .shift_remove("Id")
+                .unwrap_or(NbtTag::String(String::new()));
+
+            match format {
+                SpongeVersion::V2 => {
+                    entity.insert("Id", id);
+                    entity.insert("Pos", pos);
+                    entities.push(entity);
+                }
+                SpongeVersion::V3 => {
+                    entities.push(compound!("Pos": pos, "Id": id, "Data": entity));
+                }
+            }
+        }
+
+        entities
     }
 
-    /// Returns a tuple containing the length, height and width of the schematic
+    /// Generates the biome palette and the biome data, or None if no biomes were set.
+    /// Version 2 stores one biome per column, indexed by x + z * Width, taking the lowest
+    /// biome set in each column. Version 3 stores one per block, indexed like the block data.
+    /// Positions without a biome get DEFAULT_BIOME
+    fn generate_biome_tags(&self, format: SpongeVersion) -> Option<(NbtCompound, NbtTag)> {
+        if self.biome_data.is_empty() {
+            return None;
+        }
+
+        let biome_at = |offset: Coords| {
+            let coords = on_tuple(ADD, offset, self.lowest_coords);
+            match format {
+                SpongeVersion::V2 => (0..self.dimensions.height_y)
+                    .find_map(|y| self.get_biome((coords.0, coords.1 + y, coords.2))),
+                SpongeVersion::V3 => self.get_biome(coords),
+            }
+            .unwrap_or(DEFAULT_BIOME)
+        };
+
+        let dimensions = match format {
+            SpongeVersion::V2 => Dimensions {
+                height_y: 1,
+                ..self.dimensions
+            },
+            SpongeVersion::V3 => self.dimensions,
+        };
+        let biomes: Vec<&str> = dimensions.positions().map(biome_at).collect();
+
+        // Sort the palette so the same biomes always produce the same palette
+        let mut names: Vec<String> = biomes.iter().map(|&biome| biome.to_string()).collect();
+        names.sort_unstable();
+        names.dedup();
+
+        let mut bytes: Vec<Byte> = vec![];
+        for biome in biomes {
+            let index = names.binary_search_by(|name| name.as_str().cmp(biome));
+            bytes.extend(index.unwrap_or(0).to_varint());
+        }
+
+        Some((generate_palette_tag(&names), NbtTag::ByteArray(bytes)))
+    }
+
+    /// Rewrites every block of the palette in the given property form, merging
+    /// entries that turn out to be the same block, such as "minecraft:repeater"
+    /// and "minecraft:repeater[delay=1,facing=north,locked=false,powered=false]"
+    ///
+    /// form - which properties are kept, see PropertyForm
+    ///
+    /// version - the version whose properties and defaults are used
+    pub fn normalize_properties(&mut self, form: PropertyForm, version: McVersion) {
+        let names: Vec<String> = self
+            .palette_names()
+            .iter()
+            .map(|&name| name.to_string())
+            .collect();
+
+        for name in names {
+            self.rename_palette_entry(&name, &normalize_properties(&name, form, version));
+        }
+    }
+
+    /// Returns the palette as the given version names the blocks, along with the index
+    /// each palette entry ends up at. Blocks that were renamed since that version or
+    /// only differ in the properties left out can end up sharing a name, in which case
+    /// they are merged into a single entry. Fails if a block doesn't exist in that version
+    fn export_palette(
+        &self,
+        version: McVersion,
+        options: &SaveOptions,
+    ) -> Result<(Vec<String>, Vec<i32>), String> {
+        let mut names: Vec<String> = vec![];
+        let mut remap = vec![];
+
+        for name in self.palette_names() {
+            let name = normalize_properties(name, options.properties, version);
+            let name = renames::downgrade(&name, version.data_version())?;
+            match names.iter().position(|existing| *existing == name) {
+                Some(index) => remap.push(index as i32),
+                None => {
+                    remap.push(names.len() as i32);
+                    names.push(name);
+                }
+            }
+        }
+
+        Ok((names, remap))
+    }
+
+    /// Removes the palette entries no block uses anymore and renumbers the rest,
+    /// keeping air at index 0 and sorting the other blocks by name,
+    /// so the same set of blocks always produces the same palette
+    pub fn compact_palette(&mut self) {
+        let names = self.palette_names();
+
+        let mut used = vec![false; names.len()];
+        for &index in self.block_data.values() {
+            used[index as usize] = true;
+        }
+
+        let mut kept: Vec<&str> = names
+            .iter()
+            .zip(used)
+            .filter(|&(&name, used)| used && name != AIR)
+            .map(|(&name, _)| name)
+            .collect();
+        kept.sort_unstable();
+
+        let mut palette = BlockPalette::from([(AIR.to_string(), 0)]);
+        let mut block_names = vec![AIR.to_string()];
+        for name in kept {
+            palette.insert(name.to_string(), palette.len() as i32);
+            block_names.push(name.to_string());
+        }
+
+        let remap: Vec<i32> = names
+            .iter()
+            .map(|&name| palette.get(name).copied().unwrap_or(0))
+            .collect();
+        for index in self.block_data.values_mut() {
+            *index = remap[*index as usize];
+        }
+
+        self.block_palette = palette;
+        self.block_names = block_names;
+    }
+
+    /// Renames a palette entry, so every block using it becomes the new block.
+    /// If the new block is already in the palette, the blocks are moved over to
+    /// its index and the old entry is left unused
+    fn rename_palette_entry(&mut self, old_name: &str, new_name: &str) {
+        if old_name == new_name {
+            return;
+        }
+        let Some(&index) = self.block_palette.get(old_name) else {
+            return;
+        };
+
+        // Block entities don't belong to the block anymore once its id changes
+        if block_id(old_name) != block_id(new_name) {
+            let block_data = &self.block_data;
+            self.block_entities
+                .retain(|coords, _| block_data.get(coords) != Some(&index));
+        }
+
+        match self.block_palette.get(new_name) {
+            Some(&new_index) => {
+                for palette_index in self.block_data.values_mut() {
+                    if *palette_index == index {
+                        *palette_index = new_index;
+                    }
+                }
+            }
+            None => {
+                self.block_palette.remove(old_name);
+                self.block_palette.insert(new_name.to_string(), index);
+                self.block_names[index as usize] = new_name.to_string();
+            }
+        }
+    }
+
+    /// Returns the palette index of a block, adding it to the palette if needed
+    fn palette_index(&mut self, name: &str) -> i32 {
+        if let Some(&index) = self.block_palette.get(name) {
+            return index;
+        }
+
+        let index = self.block_names.len() as i32;
+        self.block_palette.insert(name.to_string(), index);
+        self.block_names.push(name.to_string());
+        index
+    }
+
+    /// Returns the block names of the palette, indexed by their palette index
+    fn palette_names(&self) -> Vec<&str> {
+        self.block_names.iter().map(String::as_str).collect()
+    }
+
+    /// Looks up the block name stored under the given palette index
+    fn palette_name(&self, index: i32) -> &str {
+        self.block_names
+            .get(index as usize)
+            .map(String::as_str)
+            .unwrap_or(AIR)
+    }
+
+    /// Returns the width (x), height (y) and length (z) of the schematic
     /// by subtracting the lowest coords from the highest coords
-    fn get_dimensions(&self) -> (i16, i16, i16) {
-        return on_tuple(SUB_PLUS_1, self.highest_coords, self.lowest_coords);
+    pub fn get_dimensions(&self) -> Dimensions {
+        let (width_x, height_y, length_z) =
+            on_tuple(SUB_PLUS_1, self.highest_coords, self.lowest_coords);
+
+        return Dimensions {
+            width_x,
+            height_y,
+            length_z,
+        };
+    }
+}
+
+/// Moves an entity position by a block offset
+fn translate_entity(pos: EntityPos, (x, y, z): Coords) -> EntityPos {
+    (pos.0 + x as f64, pos.1 + y as f64, pos.2 + z as f64)
+}
+
+/// Turns the nbt of an entity clockwise (seen from above) by the given number of
+/// quarter turns: the yaw of its "Rotation" and the "Facing" of hanging entities
+fn rotate_entity_data(entity: &mut NbtCompound, quarter_turns: u8) {
+    if let Some(NbtTag::List(rotation)) = entity.inner_mut().get_mut("Rotation") {
+        if let Some(NbtTag::Float(yaw)) = rotation.inner_mut().first_mut() {
+            *yaw = (*yaw + 90.0 * quarter_turns as f32).rem_euclid(360.0);
+        }
+    }
+
+    // Item frames store a 3D direction (down, up, north, south, west, east),
+    // paintings a horizontal one (south, west, north, east), both in "Facing"
+    let order: &[i8] = match entity.get::<_, &str>("Id") {
+        Ok("minecraft:item_frame" | "minecraft:glow_item_frame") => &[2, 5, 3, 4],
+        Ok("minecraft:painting") => &[0, 1, 2, 3],
+        _ => return,
+    };
+    if let Some(NbtTag::Byte(facing)) = entity.inner_mut().get_mut("Facing") {
+        if let Some(index) = order.iter().position(|f| f == facing) {
+            *facing = order[(index + quarter_turns as usize) % 4];
+        }
+    }
+}
+
+/// Options controlling how MCSchematic::save_with writes the schematic
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SaveOptions {
+    /// Which block properties get written, following the version saved for, see PropertyForm
+    pub properties: PropertyForm,
+    /// Which revision of the Sponge format gets written, see SpongeVersion
+    pub format: SpongeVersion,
+}
+
+/// Options controlling which blocks get written by MCSchematic::paste
+#[derive(Default)]
+pub struct PasteOptions<'a> {
+    /// Don't paste the air blocks of the source schematic
+    pub skip_air: bool,
+    /// Only paste into positions that are currently air in the target schematic
+    pub only_replace_air: bool,
+    /// Arbitrary predicate taking the target coords and the source block,
+    /// the block is only pasted if it returns true
+    pub mask: Option<BlockMask<'a>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::versions::JE_1_20_1;
+
+    const BLOCKS: [&str; 5] = [
+        "minecraft:air",
+        "minecraft:stone",
+        "minecraft:oak_planks",
+        "minecraft:glass",
+        "minecraft:repeater[delay=2,facing=north,locked=false,powered=false]",
+    ];
+
+    /// Returns a path in the temp directory that no other test writes to
+    fn temp_path(name: &str) -> String {
+        let file = format!("nbt_{}_{}.schem", std::process::id(), name);
+        std::env::temp_dir()
+            .join(file)
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// Fills a box of the given size with pseudo random blocks (xorshift), including air.
+    /// The two corners are always solid, so the dimensions don't shrink, and every
+    /// chest gets a block entity holding its coords
+    fn random_schematic(lowest: Coords, (w, h, l): (i16, i16, i16), seed: u32) -> MCSchematic {
+        let mut schematic = MCSchematic::new();
+        let mut state = seed;
+        let highest = (lowest.0 + w - 1, lowest.1 + h - 1, lowest.2 + l - 1);
+
+        for offset in (Dimensions {
+            width_x: w,
+            height_y: h,
+            length_z: l,
+        })
+        .positions()
+        {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+
+            let coords = on_tuple(ADD, lowest, offset);
+            match state % 7 {
+                6 => {
+                    let slot = compound!("Slot": NbtTag::Byte(0), "id": "minecraft:redstone", "Count": NbtTag::Byte(offset.0 as i8 + 1));
+                    schematic.set_block_entity(
+                        coords,
+                        "minecraft:chest[facing=north,type=single,waterlogged=false]",
+                        compound!("Items": NbtList::from(vec![NbtTag::Compound(slot)])),
+                    );
+                }
+                index => schematic.set_block(coords, BLOCKS[index as usize % BLOCKS.len()]),
+            }
+        }
+        schematic.set_block(lowest, "minecraft:stone");
+        schematic.set_block(highest, "minecraft:stone");
+
+        schematic
+    }
+
+    /// Saves a schematic in the given format and loads it back
+    fn save_and_load(
+        schematic: &mut MCSchematic,
+        format: SpongeVersion,
+        name: &str,
+    ) -> MCSchematic {
+        let path = temp_path(name);
+        let options = SaveOptions {
+            format,
+            ..Default::default()
+        };
+        schematic.save_with(&path, JE_1_20_1, &options).unwrap();
+        let loaded = MCSchematic::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        loaded
+    }
+
+    /// Saves and reloads a schematic, checking every block and block entity
+    /// against the original, moved so its lowest corner is at the origin
+    fn assert_round_trip(schematic: &mut MCSchematic, format: SpongeVersion, name: &str) {
+        let loaded = save_and_load(schematic, format, name);
+
+        let dimensions = schematic.get_dimensions();
+        assert_eq!(loaded.get_dimensions(), dimensions);
+        for offset in dimensions.positions() {
+            let coords = on_tuple(ADD, schematic.lowest_coords, offset);
+            assert_eq!(
+                loaded.get_block(offset),
+                schematic.get_block(coords),
+                "{:?}",
+                offset
+            );
+
+            let items = |entity: &NbtCompound| entity.get::<_, &NbtList>("Items").unwrap().clone();
+            match (
+                schematic.get_block_entity(coords),
+                loaded.get_block_entity(offset),
+            ) {
+                (Some(original), Some(loaded)) => assert_eq!(items(loaded), items(original)),
+                (None, None) => {}
+                entities => panic!("Block entities differ at {:?}: {:?}", offset, entities),
+            }
+        }
+    }
+
+    #[test]
+    fn round_trips_asymmetric_shapes() {
+        let shapes = [
+            (2, 3, 5),
+            (5, 3, 2),
+            (7, 1, 4),
+            (1, 6, 2),
+            (3, 5, 1),
+            (9, 2, 6),
+        ];
+
+        for (seed, &shape) in shapes.iter().enumerate() {
+            for format in [SpongeVersion::V2, SpongeVersion::V3] {
+                let lowest = (-3, 10, 7 - seed as i16 * 4);
+                let mut schematic = random_schematic(lowest, shape, seed as u32 * 7919 + 1);
+                let name = format!("{:?}_{:?}", shape, format);
+                assert_round_trip(&mut schematic, format, &name);
+            }
+        }
+    }
+
+    #[test]
+    fn keeps_axes_apart() {
+        // A single line of blocks along each axis must stay on that axis
+        for (axis, end) in [("x", (4, 0, 0)), ("y", (0, 4, 0)), ("z", (0, 0, 4))] {
+            let mut schematic = MCSchematic::new();
+            schematic.set_block((0, 0, 0), "minecraft:stone");
+            schematic.set_block(end, "minecraft:oak_planks");
+            assert_round_trip(&mut schematic, SpongeVersion::V2, axis);
+        }
+    }
+
+    #[test]
+    fn rejects_negative_dimensions() {
+        let path = temp_path("negative");
+        let nbt = compound!(
+            "Version": 2,
+            "DataVersion": JE_1_20_1.data_version(),
+            "Width": NbtTag::Short(2),
+            "Height": NbtTag::Short(-1),
+            "Length": NbtTag::Short(3),
+            "PaletteMax": 1,
+            "Palette": compound!("minecraft:air": 0),
+            "BlockData": NbtTag::ByteArray(vec![])
+        );
+        let mut file = File::create(&path).unwrap();
+        quartz_nbt::io::write_nbt(&mut file, Some("Schematic"), &nbt, Flavor::GzCompressed)
+            .unwrap();
+
+        let loaded = MCSchematic::load(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.err().as_deref(), Some("Invalid dimensions 2x-1x3"));
+    }
+
+    /// A 3x1x1 line of stone, air and a chest holding its coords, at x = 10..=12
+    fn paste_source() -> MCSchematic {
+        let mut source = MCSchematic::new();
+        source.set_block((10, 0, 0), "minecraft:stone");
+        source.set_block((11, 0, 0), "minecraft:air");
+        source.set_block_entity(
+            (12, 0, 0),
+            "minecraft:chest",
+            compound!("CustomName": "source"),
+        );
+        source
+    }
+
+    fn target_with_glass() -> MCSchematic {
+        let mut target = MCSchematic::new();
+        for x in 0..3 {
+            target.set_block((x, 5, 0), "minecraft:glass");
+        }
+        target
+    }
+
+    #[test]
+    fn paste_moves_blocks_and_entities() {
+        let mut target = target_with_glass();
+        target.paste(&paste_source(), (0, 5, 0), &PasteOptions::default());
+
+        assert_eq!(target.get_block((0, 5, 0)), "minecraft:stone");
+        assert_eq!(target.get_block((1, 5, 0)), AIR);
+        assert_eq!(target.get_block((2, 5, 0)), "minecraft:chest");
+        let entity = target.get_block_entity((2, 5, 0)).unwrap();
+        assert_eq!(entity.get::<_, &str>("CustomName").unwrap(), "source");
+    }
+
+    #[test]
+    fn paste_skips_air() {
+        let mut target = target_with_glass();
+        let options = PasteOptions {
+            skip_air: true,
+            ..Default::default()
+        };
+        target.paste(&paste_source(), (0, 5, 0), &options);
+
+        assert_eq!(target.get_block((0, 5, 0)), "minecraft:stone");
+        assert_eq!(target.get_block((1, 5, 0)), "minecraft:glass");
+        assert_eq!(target.get_block((2, 5, 0)), "minecraft:chest");
+    }
+
+    #[test]
+    fn paste_only_replaces_air() {
+        let mut target = MCSchematic::new();
+        target.set_block((0, 0, 0), "minecraft:glass");
+        target.set_block((2, 0, 0), "minecraft:air");
+        let options = PasteOptions {
+            only_replace_air: true,
+            ..Default::default()
+        };
+        target.paste(&paste_source(), (0, 0, 0), &options);
+
+        assert_eq!(target.get_block((0, 0, 0)), "minecraft:glass");
+        assert_eq!(target.get_block((2, 0, 0)), "minecraft:chest");
+    }
+
+    #[test]
+    fn paste_follows_the_mask() {
+        let mut target = target_with_glass();
+        let options = PasteOptions {
+            mask: Some(Box::new(|(x, _, _), block| {
+                x != 0 && block != "minecraft:air"
+            })),
+            ..Default::default()
+        };
+        target.paste(&paste_source(), (0, 5, 0), &options);
+
+        assert_eq!(target.get_block((0, 5, 0)), "minecraft:glass");
+        assert_eq!(target.get_block((1, 5, 0)), "minecraft:glass");
+        assert_eq!(target.get_block((2, 5, 0)), "minecraft:chest");
+        assert!(target.get_block_entity((2, 5, 0)).is_some());
+    }
+
+    #[test]
+    fn copy_region_keeps_coords_and_bounds() {
+        let mut source = paste_source();
+        source.set_block((13, 0, 0), "minecraft:oak_planks");
+        let region = source.copy_region((13, 1, 0), (11, 0, 0));
+
+        assert_eq!(region.get_block((10, 0, 0)), AIR);
+        assert_eq!(region.get_block((12, 0, 0)), "minecraft:chest");
+        assert_eq!(region.get_block((13, 0, 0)), "minecraft:oak_planks");
+        assert!(region.get_block_entity((12, 0, 0)).is_some());
+        assert_eq!(region.blocks().count(), 3);
+    }
+
+    #[test]
+    fn replace_rewrites_matching_blocks() {
+        let mut schematic = MCSchematic::new();
+        schematic.set_block((0, 0, 0), "minecraft:oak_planks");
+        schematic.set_block((1, 0, 0), "minecraft:birch_planks");
+        schematic.set_block((2, 0, 0), "minecraft:stone");
+        schematic.replace(
+            &BlockMatcher::parse("*_planks"),
+            &Pattern::from("minecraft:glass"),
+        );
+
+        assert_eq!(schematic.get_block((0, 0, 0)), "minecraft:glass");
+        assert_eq!(schematic.get_block((1, 0, 0)), "minecraft:glass");
+        assert_eq!(schematic.get_block((2, 0, 0)), "minecraft:stone");
+    }
+
+    #[test]
+    fn replace_changes_properties_and_keeps_entities() {
+        let mut schematic = MCSchematic::new();
+        let chest = "minecraft:chest[facing=north]";
+        schematic.set_block_entity((0, 0, 0), chest, compound!("CustomName": "kept"));
+        schematic.set_block_entity((1, 0, 0), chest, compound!("CustomName": "dropped"));
+
+        schematic.replace(
+            &BlockMatcher::parse("chest[facing=north]"),
+            &Pattern::parse("[facing=east]").unwrap(),
+        );
+        assert_eq!(
+            schematic.get_block((0, 0, 0)),
+            "minecraft:chest[facing=east]"
+        );
+        assert!(schematic.get_block_entity((0, 0, 0)).is_some());
+
+        let only_second = Pattern::Function(Box::new(|(x, _, _)| match x {
+            1 => "minecraft:barrel".to_string(),
+            _ => "[facing=south]".to_string(),
+        }));
+        schematic.replace(&BlockMatcher::parse("chest"), &only_second);
+        assert_eq!(
+            schematic.get_block((0, 0, 0)),
+            "minecraft:chest[facing=south]"
+        );
+        assert!(schematic.get_block_entity((0, 0, 0)).is_some());
+        assert_eq!(schematic.get_block((1, 0, 0)), "minecraft:barrel");
+        assert!(schematic.get_block_entity((1, 0, 0)).is_none());
+    }
+
+    #[test]
+    fn replace_fills_air_inside_the_bounds() {
+        let mut schematic = MCSchematic::new();
+        schematic.set_block((0, 0, 0), "minecraft:stone");
+        schematic.set_block((2, 1, 0), "minecraft:stone");
+        schematic.replace(
+            &BlockMatcher::parse("air"),
+            &Pattern::from("minecraft:water"),
+        );
+
+        assert_eq!(schematic.get_block((1, 0, 0)), "minecraft:water");
+        assert_eq!(schematic.get_block((0, 1, 0)), "minecraft:water");
+        assert_eq!(schematic.get_block((0, 0, 0)), "minecraft:stone");
+        assert_eq!(schematic.get_block((3, 0, 0)), AIR);
+    }
+
+    #[test]
+    fn compaction_drops_unused_entries_and_sorts() {
+        let mut schematic = MCSchematic::new();
+        schematic.set_block((0, 0, 0), "minecraft:stone");
+        schematic.set_block((1, 0, 0), "minecraft:dirt");
+        schematic.set_block((2, 0, 0), "minecraft:andesite");
+        schematic.set_block((1, 0, 0), "minecraft:stone");
+        schematic.compact_palette();
+
+        assert_eq!(
+            schematic.palette_names(),
+            ["minecraft:air", "minecraft:andesite", "minecraft:stone"]
+        );
+        assert_eq!(schematic.get_block((0, 0, 0)), "minecraft:stone");
+        assert_eq!(schematic.get_block((1, 0, 0)), "minecraft:stone");
+        assert_eq!(schematic.get_block((2, 0, 0)), "minecraft:andesite");
+    }
+
+    #[test]
+    fn saves_the_same_blocks_to_the_same_bytes() {
+        let blocks = [
+            ((0, 0, 0), "minecraft:stone"),
+            ((1, 2, 0), "minecraft:glass"),
+            ((0, 1, 3), "minecraft:oak_planks"),
+            ((2, 0, 1), "minecraft:air"),
+        ];
+        let mut forward = MCSchematic::new();
+        let mut backward = MCSchematic::new();
+        forward.set_block((2, 2, 2), "minecraft:dirt");
+        for &(coords, block) in blocks.iter() {
+            forward.set_block(coords, block);
+        }
+        for &(coords, block) in blocks.iter().rev() {
+            backward.set_block(coords, block);
+        }
+
+        let (forward_path, backward_path) = (temp_path("forward"), temp_path("backward"));
+        forward.set_block((2, 2, 2), "minecraft:glass");
+        backward.set_block((2, 2, 2), "minecraft:glass");
+        forward.save(&forward_path, JE_1_20_1).unwrap();
+        backward.save(&backward_path, JE_1_20_1).unwrap();
+        let bytes = (
+            std::fs::read(&forward_path).unwrap(),
+            std::fs::read(&backward_path).unwrap(),
+        );
+        std::fs::remove_file(&forward_path).unwrap();
+        std::fs::remove_file(&backward_path).unwrap();
+
+        assert_eq!(bytes.0, bytes.1);
+    }
+
+    #[test]
+    fn save_fails_for_unwritable_paths() {
+        let mut schematic = MCSchematic::new();
+        schematic.set_block((0, 0, 0), "minecraft:stone");
+        let path = std::env::temp_dir()
+            .join("nbt_missing_directory")
+            .join("a.schem");
+
+        assert!(schematic.save(&path.to_string_lossy(), JE_1_20_1).is_err());
+    }
+
+    #[test]
+    fn biomes_use_their_own_palette() {
+        let mut schematic = MCSchematic::new();
+        schematic.set_block((0, 0, 0), "minecraft:stone");
+        schematic.set_biome((0, 0, 0), "minecraft:desert");
+        schematic.set_biome((1, 0, 0), "minecraft:desert");
+        schematic.set_biome((0, 1, 0), "minecraft:jungle");
+
+        assert_eq!(schematic.get_biome((1, 0, 0)), Some("minecraft:desert"));
+        assert_eq!(schematic.get_biome((0, 1, 0)), Some("minecraft:jungle"));
+        assert_eq!(schematic.get_biome((2, 0, 0)), None);
+        assert_eq!(schematic.biome_palette.len(), 2);
+        assert_eq!(schematic.get_block((1, 0, 0)), AIR);
+        // Biomes don't extend the boundaries
+        assert_eq!(schematic.get_dimensions().width_x, 1);
+    }
+
+    #[test]
+    fn version_3_keeps_a_biome_per_block() {
+        let mut schematic = MCSchematic::new();
+        schematic.set_block((3, 5, 7), "minecraft:stone");
+        schematic.set_block((4, 6, 7), "minecraft:stone");
+        schematic.set_biome((3, 5, 7), "minecraft:desert");
+        schematic.set_biome((3, 6, 7), "minecraft:jungle");
+
+        let loaded = save_and_load(&mut schematic, SpongeVersion::V3, "biomes_v3");
+        assert_eq!(loaded.get_biome((0, 0, 0)), Some("minecraft:desert"));
+        assert_eq!(loaded.get_biome((0, 1, 0)), Some("minecraft:jungle"));
+        assert_eq!(loaded.get_biome((1, 1, 0)), Some(DEFAULT_BIOME));
+    }
+
+    #[test]
+    fn version_2_keeps_the_lowest_biome_of_each_column() {
+        let mut schematic = MCSchematic::new();
+        schematic.set_block((0, 0, 0), "minecraft:stone");
+        schematic.set_block((1, 2, 0), "minecraft:stone");
+        schematic.set_biome((0, 1, 0), "minecraft:desert");
+        schematic.set_biome((0, 2, 0), "minecraft:jungle");
+
+        let loaded = save_and_load(&mut schematic, SpongeVersion::V2, "biomes_v2");
+        for y in 0..3 {
+            assert_eq!(loaded.get_biome((0, y, 0)), Some("minecraft:desert"));
+            assert_eq!(loaded.get_biome((1, y, 0)), Some(DEFAULT_BIOME));
+        }
+    }
+
+    #[test]
+    fn schematics_without_biomes_load_without_biomes() {
+        let mut schematic = MCSchematic::new();
+        schematic.set_block((0, 0, 0), "minecraft:stone");
+
+        for format in [SpongeVersion::V2, SpongeVersion::V3] {
+            let loaded = save_and_load(&mut schematic, format, "no_biomes");
+            assert_eq!(loaded.get_biome((0, 0, 0)), None);
+        }
+    }
+
+    #[test]
+    fn entities_keep_their_position_and_data() {
+        let mut schematic = MCSchematic::new();
+        schematic.set_block((2, 1, 3), "minecraft:stone");
+        schematic.set_block((4, 3, 5), "minecraft:stone");
+        schematic.add_entity(
+            (3.5, 2.0, 4.25),
+            "minecraft:armor_stand",
+            compound!("CustomName": "\"Adder\"", "Invisible": NbtTag::Byte(1)),
+        );
+
+        for format in [SpongeVersion::V2, SpongeVersion::V3] {
+            let loaded = save_and_load(&mut schematic, format, "entities");
+            let [(pos, entity)] = loaded.get_entities() else {
+                panic!("Expected one entity, got {:?}", loaded.get_entities());
+            };
+            assert_eq!(*pos, (1.5, 1.0, 1.25));
+            assert_eq!(
+                entity.get::<_, &str>("Id").unwrap(),
+                "minecraft:armor_stand"
+            );
+            assert_eq!(entity.get::<_, &str>("CustomName").unwrap(), "\"Adder\"");
+            assert_eq!(entity.get::<_, i8>("Invisible").unwrap(), 1);
+            assert!(!entity.contains_key("Pos"));
+        }
+    }
+
+    #[test]
+    fn entities_turn_with_the_blocks() {
+        let mut schematic = MCSchematic::new();
+        schematic.set_block((0, 0, 0), "minecraft:stone");
+        schematic.set_block((1, 0, 2), "minecraft:stone");
+        let rotation = NbtList::from(vec![NbtTag::Float(10.0), NbtTag::Float(0.0)]);
+        schematic.add_entity(
+            (0.5, 1.0, 0.5),
+            "minecraft:armor_stand",
+            compound!("Rotation": rotation),
+        );
+        schematic.add_entity(
+            (1.5, 0.5, 2.0),
+            "minecraft:item_frame",
+            compound!("Facing": NbtTag::Byte(2)),
+        );
+        schematic.add_entity(
+            (1.0, 0.5, 2.5),
+            "minecraft:painting",
+            compound!("Facing": NbtTag::Byte(0)),
+        );
+
+        schematic.rotate_y(1);
+
+        // The stone at (0, 0, 0), which the armor stand stood on, ends up at (2, 0, 0)
+        assert_eq!(schematic.get_block((2, 0, 0)), "minecraft:stone");
+        let entities = schematic.get_entities();
+        assert_eq!(entities[0].0, (2.5, 1.0, 0.5));
+        let rotation = entities[0].1.get::<_, &NbtList>("Rotation").unwrap();
+        assert_eq!(rotation.get::<f32>(0).unwrap(), 100.0);
+
+        // North facing item frames turn east, south facing paintings west
+        assert_eq!(entities[1].0, (1.0, 0.5, 1.5));
+        assert_eq!(entities[1].1.get::<_, i8>("Facing").unwrap(), 5);
+        assert_eq!(entities[2].0, (0.5, 0.5, 1.0));
+        assert_eq!(entities[2].1.get::<_, i8>("Facing").unwrap(), 1);
+    }
+
+    #[test]
+    fn entities_move_with_pastes_and_arrays() {
+        let mut cell = MCSchematic::new();
+        cell.set_block((0, 0, 0), "minecraft:stone");
+        cell.add_entity((0.5, 1.0, 0.5), "minecraft:pig", NbtCompound::new());
+
+        let mut schematic = MCSchematic::new();
+        schematic.paste(&cell, (10, 0, 0), &PasteOptions::default());
+        schematic
+            .array_with(
+                &cell,
+                (0, 5, 0),
+                (2, 1, 1),
+                ((3, 0, 0), (0, 0, 0), (0, 0, 0)),
+                |_, _, _| {},
+            )
+            .unwrap();
+
+        let positions: Vec<EntityPos> = schematic.get_entities().iter().map(|e| e.0).collect();
+        assert_eq!(
+            positions,
+            vec![(10.5, 1.0, 0.5), (0.5, 6.0, 0.5), (3.5, 6.0, 0.5)]
+        );
     }
 }
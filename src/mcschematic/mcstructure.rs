@@ -0,0 +1,710 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{Read, Write},
+};
+
+use quartz_nbt::{NbtCompound, NbtList, NbtTag};
+
+use super::{block_id, on_tuple, read_tag, Dimensions, EntityPos, MCSchematic, ADD, SUB};
+use crate::bedrock_blocks::{bedrock_to_java, java_to_bedrock};
+use crate::block_state::BlockState;
+use crate::versions::BedrockVersion;
+
+/// Block entity ids that don't follow the usual PascalCase conversion
+const BLOCK_ENTITY_IDS: &[(&str, &str)] = &[
+    ("minecraft:note_block", "Music"),
+    ("minecraft:spawner", "MobSpawner"),
+    ("minecraft:enchanting_table", "EnchantTable"),
+    ("minecraft:trapped_chest", "Chest"),
+];
+
+impl MCSchematic {
+    /// Saves the schematic as a Bedrock Edition structure (.mcstructure).
+    /// Blocks are converted with bedrock_blocks::java_to_bedrock, waterlogged
+    /// blocks get water in the second block layer
+    ///
+    /// file_path - location and name of the target file
+    ///
+    /// version - Bedrock version, use the BE_ constants from versions
+    pub fn save_mcstructure(
+        &mut self,
+        file_path: &str,
+        version: BedrockVersion,
+    ) -> Result<String, String> {
+        self.compact_palette();
+        let dimensions = self.get_dimensions();
+
+        // Convert the palette, remembering which entries are waterlogged
+        let names = self.palette_names();
+        let mut block_palette = NbtList::new();
+        let mut waterlogged = vec![];
+        for name in names.iter() {
+            let (bedrock_name, states) = java_to_bedrock(name);
+            block_palette.push(palette_entry(&bedrock_name, states, version));
+            waterlogged.push(BlockState::parse(name).property("waterlogged") == Some("true"));
+        }
+
+        let water_index = block_palette.len() as i32;
+        let mut water_states = NbtCompound::new();
+        water_states.insert("liquid_depth", NbtTag::Int(0));
+        block_palette.push(palette_entry("minecraft:water", water_states, version));
+
+        // Structures are stored with z changing the fastest and x the slowest
+        let mut blocks = vec![];
+        let mut liquids = vec![];
+        for x in 0..dimensions.width_x {
+            for y in 0..dimensions.height_y {
+                for z in 0..dimensions.length_z {
+                    let coords = on_tuple(ADD, (x, y, z), self.lowest_coords);
+                    let index = self.block_data.get(&coords).copied().unwrap_or(0);
+
+                    blocks.push(NbtTag::Int(index));
+                    liquids.push(NbtTag::Int(match waterlogged[index as usize] {
+                        true => water_index,
+                        false => -1,
+                    }));
+                }
+            }
+        }
+
+        let mut block_position_data = NbtCompound::new();
+        for (&coords, entity) in self.block_entities.iter() {
+            let relative = on_tuple(SUB, coords, self.lowest_coords);
+            let mut position_data = NbtCompound::new();
+            position_data.insert(
+                "block_entity_data",
+                block_entity_to_bedrock(entity, self.get_block(coords), coords),
+            );
+            block_position_data.insert(
+                structure_index(dimensions, relative).to_string(),
+                position_data,
+            );
+        }
+
+        let mut default_palette = NbtCompound::new();
+        default_palette.insert("block_palette", block_palette);
+        default_palette.insert("block_position_data", block_position_data);
+
+        let mut palette = NbtCompound::new();
+        palette.insert("default", default_palette);
+
+        let mut structure = NbtCompound::new();
+        structure.insert(
+            "block_indices",
+            NbtList::from(vec![
+                NbtTag::List(NbtList::from(blocks)),
+                NbtTag::List(NbtList::from(liquids)),
+            ]),
+        );
+        let entities: Vec<NbtTag> = self
+            .entities
+            .iter()
+            .enumerate()
+            .map(|(index, (pos, entity))| NbtTag::Compound(entity_to_bedrock(entity, *pos, index)))
+            .collect();
+        structure.insert("entities", NbtList::from(entities));
+        structure.insert("palette", palette);
+
+        let (width, height, length) =
+            (dimensions.width_x, dimensions.height_y, dimensions.length_z);
+        let (x, y, z) = self.lowest_coords;
+
+        let mut root = NbtCompound::new();
+        root.insert("format_version", NbtTag::Int(1));
+        root.insert(
+            "size",
+            int_list([width as i32, height as i32, length as i32]),
+        );
+        root.insert("structure", structure);
+        root.insert(
+            "structure_world_origin",
+            int_list([x as i32, y as i32, z as i32]),
+        );
+
+        let mut bytes = vec![];
+        write_named_tag(&mut bytes, "", &NbtTag::Compound(root))?;
+
+        // Only open the file once the structure is written, so a structure that can't
+        // be written doesn't leave the existing file empty
+        let Ok(mut file_out) = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(file_path)
+        else {
+            return Err("Failed to save structure".to_string());
+        };
+        if file_out.write_all(&bytes).is_err() {
+            return Err("Failed to save structure".to_string());
+        }
+
+        Ok(format!("Saved to {}", file_path))
+    }
+
+    /// Loads a Bedrock Edition structure (.mcstructure), converting the blocks back
+    /// with bedrock_blocks::bedrock_to_java. The lowest corner of the loaded schematic
+    /// ends up at (0, 0, 0)
+    ///
+    /// file_path - location and name of the structure file
+    pub fn load_mcstructure(file_path: &str) -> Result<MCSchematic, String> {
+        let mut bytes = vec![];
+        if File::open(file_path)
+            .and_then(|mut file| file.read_to_end(&mut bytes))
+            .is_err()
+        {
+            return Err(format!("Failed to open {}", file_path));
+        }
+
+        let mut reader = LittleEndianReader {
+            bytes: &bytes,
+            position: 0,
+        };
+        let root = match reader.read_named_tag()? {
+            NbtTag::Compound(root) => root,
+            _ => return Err(format!("{} is not a valid structure file", file_path)),
+        };
+
+        let size: &NbtList = read_tag(&root, "size")?;
+        let size: Vec<i16> = size
+            .iter_map::<i32>()
+            .flatten()
+            .filter_map(|value| i16::try_from(value).ok().filter(|&value| value >= 0))
+            .collect();
+        let [width_x, height_y, length_z] = size[..] else {
+            return Err("Structure size must hold three numbers from 0 to 32767".to_string());
+        };
+        let dimensions = Dimensions {
+            width_x,
+            height_y,
+            length_z,
+        };
+
+        let structure: &NbtCompound = read_tag(&root, "structure")?;
+        let palette: &NbtCompound = read_tag(structure, "palette")?;
+        let default_palette: &NbtCompound = read_tag(palette, "default")?;
+        let block_palette: &NbtList = read_tag(default_palette, "block_palette")?;
+
+        let mut names = vec![];
+        for entry in block_palette.iter_map::<&NbtCompound>() {
+            let Ok(entry) = entry else {
+                return Err("Invalid block palette entry".to_string());
+            };
+            let name: &str = read_tag(entry, "name")?;
+            let states = entry
+                .get::<_, &NbtCompound>("states")
+                .cloned()
+                .unwrap_or_default();
+            names.push(bedrock_to_java(name, &states));
+        }
+
+        let block_indices: &NbtList = read_tag(structure, "block_indices")?;
+        let layers: Vec<Vec<i32>> = block_indices
+            .iter_map::<&NbtList>()
+            .flatten()
+            .map(|layer| layer.iter_map::<i32>().flatten().collect())
+            .collect();
+        let Some(blocks) = layers.first() else {
+            return Err("Structure has no block layer".to_string());
+        };
+
+        let mut schematic = MCSchematic::new();
+        for (index, &palette_index) in blocks.iter().enumerate() {
+            // -1 marks structure void, which leaves the position untouched
+            let Some(name) = usize::try_from(palette_index)
+                .ok()
+                .and_then(|i| names.get(i))
+            else {
+                continue;
+            };
+
+            let liquid = layers
+                .get(1)
+                .and_then(|layer| layer.get(index))
+                .copied()
+                .unwrap_or(-1);
+            let is_water = usize::try_from(liquid)
+                .ok()
+                .and_then(|i| names.get(i))
+                .is_some_and(|name| block_id(name) == "minecraft:water");
+
+            let Some(coords) = structure_coords(dimensions, index) else {
+                return Err(format!("Block index {} is outside of the structure", index));
+            };
+            match is_water && !name.starts_with("minecraft:water") {
+                true => {
+                    let mut state = BlockState::parse(name);
+                    state.set_property("waterlogged", "true");
+                    schematic.set_block(coords, &state.to_string());
+                }
+                false => schematic.set_block(coords, name),
+            }
+        }
+
+        if let Ok(block_position_data) =
+            default_palette.get::<_, &NbtCompound>("block_position_data")
+        {
+            for (index, position_data) in block_position_data.inner() {
+                let (Ok(index), NbtTag::Compound(position_data)) =
+                    (index.parse::<usize>(), position_data)
+                else {
+                    continue;
+                };
+                let Some(coords) = structure_coords(dimensions, index) else {
+                    continue;
+                };
+                if let Ok(entity) = position_data.get::<_, &NbtCompound>("block_entity_data") {
+                    schematic
+                        .block_entities
+                        .insert(coords, block_entity_to_java(entity));
+                }
+            }
+        }
+
+        // Entity positions are absolute, relative to the world the structure was saved in
+        let origin: &NbtList = read_tag(&root, "structure_world_origin")?;
+        let origin: Vec<f64> = origin.iter_map::<i32>().flatten().map(f64::from).collect();
+        let [origin_x, origin_y, origin_z] = origin[..] else {
+            return Err("Structure origin must hold three numbers".to_string());
+        };
+        if let Ok(entities) = structure.get::<_, &NbtList>("entities") {
+            for entity in entities.iter_map::<&NbtCompound>().flatten() {
+                let pos: Vec<f64> = read_tag::<&NbtList>(entity, "Pos")?
+                    .iter_map::<f32>()
+                    .flatten()
+                    .map(f64::from)
+                    .collect();
+                let [x, y, z] = pos[..] else {
+                    return Err("Entity Pos must hold three numbers".to_string());
+                };
+                schematic.entities.push((
+                    (x - origin_x, y - origin_y, z - origin_z),
+                    entity_to_java(entity)?,
+                ));
+            }
+        }
+
+        // Air isn't stored in the blocks list, so the boundaries come from the structure size
+        schematic.lowest_coords = (0, 0, 0);
+        schematic.highest_coords = on_tuple(SUB, (width_x, height_y, length_z), (1, 1, 1));
+        schematic.dimensions = dimensions;
+
+        Ok(schematic)
+    }
+}
+
+/// Returns the index of coords relative to the lowest corner in a structure block layer
+fn structure_index(dimensions: Dimensions, (x, y, z): (i16, i16, i16)) -> usize {
+    let (height, length) = (dimensions.height_y as usize, dimensions.length_z as usize);
+
+    (x as usize * height + y as usize) * length + z as usize
+}
+
+/// Returns the coords relative to the lowest corner stored under an index of a structure block layer,
+/// or None if the index is outside of the structure
+fn structure_coords(dimensions: Dimensions, index: usize) -> Option<(i16, i16, i16)> {
+    if index >= dimensions.volume() {
+        return None;
+    }
+    let (height, length) = (dimensions.height_y as usize, dimensions.length_z as usize);
+
+    let z = index % length;
+    let y = (index / length) % height;
+    let x = index / (length * height);
+    Some((x as i16, y as i16, z as i16))
+}
+
+fn palette_entry(name: &str, states: NbtCompound, version: BedrockVersion) -> NbtCompound {
+    let mut entry = NbtCompound::new();
+    entry.insert("name", name);
+    entry.insert("states", states);
+    entry.insert("version", NbtTag::Int(version.block_version()));
+    entry
+}
+
+fn int_list<const N: usize>(values: [i32; N]) -> NbtList {
+    NbtList::from(values.map(NbtTag::Int).to_vec())
+}
+
+/// Converts Java block entity data into its Bedrock form: a PascalCase "id",
+/// absolute "x", "y" and "z" tags and items named by "Name"
+fn block_entity_to_bedrock(
+    entity: &NbtCompound,
+    block: &str,
+    (x, y, z): (i16, i16, i16),
+) -> NbtCompound {
+    let id = entity
+        .get::<_, &str>("Id")
+        .unwrap_or_else(|_| block_id(block));
+
+    let mut converted = entity.clone();
+    converted.inner_mut().shift_remove("Id");
+    converted.insert("id", bedrock_block_entity_id(id));
+    converted.insert("x", NbtTag::Int(x as i32));
+    converted.insert("y", NbtTag::Int(y as i32));
+    converted.insert("z", NbtTag::Int(z as i32));
+    converted.insert("isMovable", NbtTag::Byte(1));
+
+    if let Ok(items) = entity.get::<_, &NbtList>("Items") {
+        let items: Vec<NbtTag> = items
+            .iter_map::<&NbtCompound>()
+            .flatten()
+            .map(|item| {
+                let mut item = item.clone();
+                if let Some(id) = item.inner_mut().shift_remove("id") {
+                    item.insert("Name", id);
+                }
+                item.insert("Damage", NbtTag::Short(0));
+                item.insert("WasPickedUp", NbtTag::Byte(0));
+                NbtTag::Compound(item)
+            })
+            .collect();
+        converted.insert("Items", NbtList::from(items));
+    }
+
+    converted
+}
+
+/// Converts Bedrock block entity data back into its Java form. The id is dropped,
+/// since it's derived from the block when the schematic gets saved
+fn block_entity_to_java(entity: &NbtCompound) -> NbtCompound {
+    let mut converted = entity.clone();
+    for key in ["id", "x", "y", "z", "isMovable"] {
+        converted.inner_mut().shift_remove(key);
+    }
+
+    if let Ok(items) = entity.get::<_, &NbtList>("Items") {
+        let items: Vec<NbtTag> = items
+            .iter_map::<&NbtCompound>()
+            .flatten()
+            .map(|item| {
+                let mut item = item.clone();
+                if let Some(name) = item.inner_mut().shift_remove("Name") {
+                    item.insert("id", name);
+                }
+                item.inner_mut().shift_remove("Damage");
+                item.inner_mut().shift_remove("WasPickedUp");
+                NbtTag::Compound(item)
+            })
+            .collect();
+        converted.insert("Items", NbtList::from(items));
+    }
+
+    converted
+}
+
+/// Converts Java entity data into its Bedrock form: the id in "identifier", the absolute
+/// position in "Pos" and a "UniqueID" that's unique within the structure
+fn entity_to_bedrock(entity: &NbtCompound, (x, y, z): EntityPos, index: usize) -> NbtCompound {
+    let mut converted = entity.clone();
+    if let Some(id) = converted.inner_mut().shift_remove("Id") {
+        converted.insert("identifier", id);
+    }
+    converted.insert(
+        "Pos",
+        NbtList::from(vec![
+            NbtTag::Float(x as f32),
+            NbtTag::Float(y as f32),
+            NbtTag::Float(z as f32),
+        ]),
+    );
+    converted.insert("UniqueID", NbtTag::Long(-(index as i64) - 1));
+
+    converted
+}
+
+/// Converts Bedrock entity data back into its Java form, without its position
+fn entity_to_java(entity: &NbtCompound) -> Result<NbtCompound, String> {
+    let id: &str = read_tag(entity, "identifier")?;
+
+    let mut converted = entity.clone();
+    for key in ["identifier", "Pos", "UniqueID"] {
+        converted.inner_mut().shift_remove(key);
+    }
+    converted.insert("Id", id);
+
+    Ok(converted)
+}
+
+/// Turns a Java block entity id such as "minecraft:shulker_box" into "ShulkerBox"
+fn bedrock_block_entity_id(id: &str) -> String {
+    if let Some(&(_, bedrock)) = BLOCK_ENTITY_IDS.iter().find(|&&(java, _)| java == id) {
+        return bedrock.to_string();
+    }
+
+    id.trim_start_matches("minecraft:")
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// Returns the NBT type id of a tag
+fn tag_id(tag: &NbtTag) -> u8 {
+    match tag {
+        NbtTag::Byte(_) => 1,
+        NbtTag::Short(_) => 2,
+        NbtTag::Int(_) => 3,
+        NbtTag::Long(_) => 4,
+        NbtTag::Float(_) => 5,
+        NbtTag::Double(_) => 6,
+        NbtTag::ByteArray(_) => 7,
+        NbtTag::String(_) => 8,
+        NbtTag::List(_) => 9,
+        NbtTag::Compound(_) => 10,
+        NbtTag::IntArray(_) => 11,
+        NbtTag::LongArray(_) => 12,
+    }
+}
+
+/// Writes a tag with its type and name, the way Bedrock stores the root tag of a file
+fn write_named_tag(out: &mut Vec<u8>, name: &str, tag: &NbtTag) -> Result<(), String> {
+    out.push(tag_id(tag));
+    write_string(out, name)?;
+    write_tag(out, tag)
+}
+
+/// Writes a string prefixed with its length, which has to fit in 16 bits
+fn write_string(out: &mut Vec<u8>, string: &str) -> Result<(), String> {
+    let Ok(length) = u16::try_from(string.len()) else {
+        return Err(format!(
+            "Strings can't be longer than {} bytes, found one with {}",
+            u16::MAX,
+            string.len()
+        ));
+    };
+    out.extend(length.to_le_bytes());
+    out.extend(string.as_bytes());
+
+    Ok(())
+}
+
+/// Writes the payload of a tag in Bedrock's little endian NBT flavor
+fn write_tag(out: &mut Vec<u8>, tag: &NbtTag) -> Result<(), String> {
+    match tag {
+        NbtTag::Byte(value) => out.extend(value.to_le_bytes()),
+        NbtTag::Short(value) => out.extend(value.to_le_bytes()),
+        NbtTag::Int(value) => out.extend(value.to_le_bytes()),
+        NbtTag::Long(value) => out.extend(value.to_le_bytes()),
+        NbtTag::Float(value) => out.extend(value.to_le_bytes()),
+        NbtTag::Double(value) => out.extend(value.to_le_bytes()),
+        NbtTag::ByteArray(values) => {
+            out.extend((values.len() as i32).to_le_bytes());
+            out.extend(values.iter().map(|&value| value as u8));
+        }
+        NbtTag::String(value) => write_string(out, value)?,
+        NbtTag::List(list) => {
+            out.push(list.iter().next().map_or(0, tag_id));
+            out.extend((list.len() as i32).to_le_bytes());
+            for element in list.iter() {
+                write_tag(out, element)?;
+            }
+        }
+        NbtTag::Compound(compound) => {
+            for (name, element) in compound.inner() {
+                write_named_tag(out, name, element)?;
+            }
+            out.push(0);
+        }
+        NbtTag::IntArray(values) => {
+            out.extend((values.len() as i32).to_le_bytes());
+            for value in values {
+                out.extend(value.to_le_bytes());
+            }
+        }
+        NbtTag::LongArray(values) => {
+            out.extend((values.len() as i32).to_le_bytes());
+            for value in values {
+                out.extend(value.to_le_bytes());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads Bedrock's little endian NBT flavor
+struct LittleEndianReader<'b> {
+    bytes: &'b [u8],
+    position: usize,
+}
+
+impl LittleEndianReader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        let Some(bytes) = self.bytes.get(self.position..self.position + N) else {
+            return Err("Unexpected end of the structure file".to_string());
+        };
+        self.position += N;
+
+        Ok(bytes.try_into().unwrap())
+    }
+
+    fn read_length(&mut self) -> Result<usize, String> {
+        match i32::from_le_bytes(self.take()?) {
+            length if length >= 0 => Ok(length as usize),
+            length => Err(format!("Invalid length {} in the structure file", length)),
+        }
+    }
+
+    fn read_string(&mut self) -> Result<String, String> {
+        let length = u16::from_le_bytes(self.take()?) as usize;
+        let Some(bytes) = self.bytes.get(self.position..self.position + length) else {
+            return Err("Unexpected end of the structure file".to_string());
+        };
+        self.position += length;
+
+        Ok(String::from_utf8_lossy(bytes).into_owned())
+    }
+
+    fn read_named_tag(&mut self) -> Result<NbtTag, String> {
+        let [id] = self.take()?;
+        self.read_string()?;
+        self.read_tag(id)
+    }
+
+    fn read_tag(&mut self, id: u8) -> Result<NbtTag, String> {
+        Ok(match id {
+            1 => NbtTag::Byte(i8::from_le_bytes(self.take()?)),
+            2 => NbtTag::Short(i16::from_le_bytes(self.take()?)),
+            3 => NbtTag::Int(i32::from_le_bytes(self.take()?)),
+            4 => NbtTag::Long(i64::from_le_bytes(self.take()?)),
+            5 => NbtTag::Float(f32::from_le_bytes(self.take()?)),
+            6 => NbtTag::Double(f64::from_le_bytes(self.take()?)),
+            7 => {
+                let length = self.read_length()?;
+                let mut values = Vec::with_capacity(length.min(self.bytes.len()));
+                for _ in 0..length {
+                    values.push(i8::from_le_bytes(self.take()?));
+                }
+                NbtTag::ByteArray(values)
+            }
+            8 => NbtTag::String(self.read_string()?),
+            9 => {
+                let [element_id] = self.take()?;
+                let length = self.read_length()?;
+                let mut list = NbtList::new();
+                for _ in 0..length {
+                    list.push(self.read_tag(element_id)?);
+                }
+                NbtTag::List(list)
+            }
+            10 => {
+                let mut compound = NbtCompound::new();
+                loop {
+                    let [element_id] = self.take()?;
+                    if element_id == 0 {
+                        break;
+                    }
+                    let name = self.read_string()?;
+                    compound.insert(name, self.read_tag(element_id)?);
+                }
+                NbtTag::Compound(compound)
+            }
+            11 => {
+                let length = self.read_length()?;
+                let mut values = Vec::with_capacity(length.min(self.bytes.len()));
+                for _ in 0..length {
+                    values.push(i32::from_le_bytes(self.take()?));
+                }
+                NbtTag::IntArray(values)
+            }
+            12 => {
+                let length = self.read_length()?;
+                let mut values = Vec::with_capacity(length.min(self.bytes.len()));
+                for _ in 0..length {
+                    values.push(i64::from_le_bytes(self.take()?));
+                }
+                NbtTag::LongArray(values)
+            }
+            _ => return Err(format!("Unknown tag type {} in the structure file", id)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::versions::BE_1_20_0;
+    use quartz_nbt::compound;
+
+    fn temp_path(name: &str) -> String {
+        let file = format!("nbt_{}_{}.mcstructure", std::process::id(), name);
+        std::env::temp_dir()
+            .join(file)
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn round_trips_blocks_entities_and_water() {
+        let mut schematic = MCSchematic::new();
+        schematic.set_block((5, 2, -1), "minecraft:stone");
+        schematic.set_block((6, 2, -1), "minecraft:oak_planks");
+        schematic.set_block((5, 4, 1), "minecraft:glass");
+        schematic.set_block((6, 3, 0), "minecraft:oak_stairs[waterlogged=true]");
+        schematic.set_block(
+            (6, 4, 1),
+            "minecraft:repeater[powered=false,delay=3,facing=west]",
+        );
+        let item = compound!("Slot": NbtTag::Byte(0), "id": "minecraft:redstone", "Count": NbtTag::Byte(5));
+        schematic.set_block_entity(
+            (5, 3, 0),
+            "minecraft:chest",
+            compound!("Items": NbtList::from(vec![NbtTag::Compound(item)])),
+        );
+        schematic.add_entity((6.5, 2.0, 0.5), "minecraft:pig", NbtCompound::new());
+
+        let path = temp_path("round_trip");
+        schematic.save_mcstructure(&path, BE_1_20_0).unwrap();
+        let loaded = MCSchematic::load_mcstructure(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.get_dimensions(), schematic.get_dimensions());
+        for offset in schematic.get_dimensions().positions() {
+            let coords = on_tuple(ADD, offset, (5, 2, -1));
+            assert_eq!(
+                loaded.get_block(offset),
+                schematic.get_block(coords),
+                "{:?}",
+                coords
+            );
+        }
+
+        let chest = loaded.get_block_entity((0, 1, 1)).unwrap();
+        let items = chest.get::<_, &NbtList>("Items").unwrap();
+        let NbtTag::Compound(item) = &items[0] else {
+            panic!("Items holds {:?}", items);
+        };
+        assert_eq!(item.get::<_, &str>("id").unwrap(), "minecraft:redstone");
+        assert_eq!(item.get::<_, i8>("Count").unwrap(), 5);
+
+        let entities = loaded.get_entities();
+        assert_eq!(entities.len(), 1);
+        assert_eq!(entities[0].0, (1.5, 0.0, 1.5));
+        assert_eq!(entities[0].1.get::<_, &str>("Id").unwrap(), "minecraft:pig");
+    }
+
+    #[test]
+    fn failed_saves_keep_the_existing_file() {
+        let path = temp_path("unwritable");
+        std::fs::write(&path, b"existing").unwrap();
+
+        let mut schematic = MCSchematic::new();
+        let name = "x".repeat(70_000);
+        schematic.set_block_entity(
+            (0, 0, 0),
+            "minecraft:chest",
+            compound!("CustomName": name.as_str()),
+        );
+        let saved = schematic.save_mcstructure(&path, BE_1_20_0);
+        let contents = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(saved.is_err());
+        assert_eq!(contents, b"existing");
+    }
+}
@@ -0,0 +1,355 @@
+use std::fmt::Write;
+
+use quartz_nbt::{compound, NbtCompound, NbtList, NbtTag};
+
+use super::{Coords, MCSchematic};
+use crate::versions::{McVersion, JE_1_14, JE_1_17, JE_1_20_1, JE_23W12A};
+
+/// A line of text in the JSON text component format, such as {"text":"REG A","color":"red"}
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TextComponent {
+    pub text: String,
+    /// A named color such as "red" or a hex color such as "#FF0000"
+    pub color: Option<String>,
+    pub bold: bool,
+    pub italic: bool,
+    pub underlined: bool,
+    pub strikethrough: bool,
+    pub obfuscated: bool,
+}
+
+impl TextComponent {
+    /// Returns a plain, unstyled piece of text
+    pub fn new(text: &str) -> TextComponent {
+        TextComponent {
+            text: text.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the text with the given color, see TextComponent::color
+    pub fn colored(mut self, color: &str) -> TextComponent {
+        self.color = Some(color.to_string());
+        self
+    }
+
+    /// Returns the text in bold
+    pub fn bold(mut self) -> TextComponent {
+        self.bold = true;
+        self
+    }
+
+    /// Returns the text in italics
+    pub fn italic(mut self) -> TextComponent {
+        self.italic = true;
+        self
+    }
+
+    /// Serializes the component into its JSON form, leaving out the styles that aren't set
+    pub fn to_json(&self) -> String {
+        let mut json = format!("{{\"text\":{}", json_string(&self.text));
+
+        if let Some(color) = &self.color {
+            let _ = write!(json, ",\"color\":{}", json_string(color));
+        }
+        for (style, set) in [
+            ("bold", self.bold),
+            ("italic", self.italic),
+            ("underlined", self.underlined),
+            ("strikethrough", self.strikethrough),
+            ("obfuscated", self.obfuscated),
+        ] {
+            if set {
+                let _ = write!(json, ",\"{}\":true", style);
+            }
+        }
+
+        json.push('}');
+        json
+    }
+}
+
+impl From<&str> for TextComponent {
+    fn from(text: &str) -> Self {
+        TextComponent::new(text)
+    }
+}
+
+/// Options for MCSchematic::place_sign
+#[derive(Clone, Debug)]
+pub struct SignOptions {
+    /// The version the sign gets saved for, which decides the layout of its nbt
+    pub version: McVersion,
+    /// Wood type of the sign, such as "oak" or "dark_oak"
+    pub wood: String,
+    /// Attach the sign to the block behind it instead of standing on the block below
+    pub wall: bool,
+    /// Dye color of the text, such as "black" or "light_blue" (other than black 1.14 and later)
+    pub color: String,
+    /// Whether the text was given the glow ink effect (1.17 and later)
+    pub glowing: bool,
+    /// Whether the sign is waxed, so players can't edit it (1.20 and later)
+    pub waxed: bool,
+    /// Lines on the back of the sign (1.20 and later)
+    pub back_lines: Vec<TextComponent>,
+}
+
+impl Default for SignOptions {
+    fn default() -> Self {
+        SignOptions {
+            version: JE_1_20_1,
+            wood: "oak".to_string(),
+            wall: false,
+            color: "black".to_string(),
+            glowing: false,
+            waxed: false,
+            back_lines: vec![],
+        }
+    }
+}
+
+impl MCSchematic {
+    /// Places a sign with the given text, writing the block entity in the layout the
+    /// target version expects: "front_text" and "back_text" since 1.20, "Text1" to "Text4" before
+    ///
+    /// coords - desired sign coordinates: (x, y, z)
+    ///
+    /// facing - the direction the text faces: "north", "east", "south" or "west"
+    ///
+    /// lines - up to four lines of text, the missing ones are left empty
+    ///
+    /// options - wood type, glowing text and the rest, see SignOptions. Fails for options
+    /// the target version doesn't have, like a waxed sign before 1.20
+    pub fn place_sign(
+        &mut self,
+        coords: Coords,
+        facing: &str,
+        lines: &[TextComponent],
+        options: &SignOptions,
+    ) -> Result<(), String> {
+        let rotation = match facing {
+            "south" => 0,
+            "west" => 4,
+            "north" => 8,
+            "east" => 12,
+            _ => return Err(format!("Signs can't face {}", facing)),
+        };
+        let block = match options.wall {
+            true => format!("minecraft:{}_wall_sign[facing={}]", options.wood, facing),
+            false => format!("minecraft:{}_sign[rotation={}]", options.wood, rotation),
+        };
+        if lines.len() > 4 || options.back_lines.len() > 4 {
+            return Err("Signs hold at most four lines of text".to_string());
+        }
+        let unsupported = [
+            ("dyed text", options.color != "black", JE_1_14),
+            ("glowing text", options.glowing, JE_1_17),
+            ("waxing", options.waxed, JE_23W12A),
+            (
+                "text on the back",
+                !options.back_lines.is_empty(),
+                JE_23W12A,
+            ),
+        ];
+        if let Some((feature, _, since)) = unsupported
+            .into_iter()
+            .find(|&(_, used, since)| used && options.version < since)
+        {
+            return Err(format!(
+                "Signs only have {} since {}, not in {}",
+                feature, since, options.version
+            ));
+        }
+
+        let mut entity = compound!("Id": "minecraft:sign");
+        if options.version >= JE_23W12A {
+            entity.insert("front_text", sign_text(lines, options));
+            entity.insert("back_text", sign_text(&options.back_lines, options));
+            entity.insert("is_waxed", NbtTag::Byte(options.waxed as i8));
+        } else {
+            for (index, line) in padded_lines(lines).into_iter().enumerate() {
+                entity.insert(format!("Text{}", index + 1), line);
+            }
+            if options.version >= JE_1_14 {
+                entity.insert("Color", options.color.as_str());
+            }
+            if options.version >= JE_1_17 {
+                entity.insert("GlowingText", NbtTag::Byte(options.glowing as i8));
+            }
+        }
+
+        self.set_block_entity(coords, &block, entity);
+        Ok(())
+    }
+}
+
+/// Generates one side of a sign in the 1.20 layout
+fn sign_text(lines: &[TextComponent], options: &SignOptions) -> NbtCompound {
+    let messages: Vec<NbtTag> = padded_lines(lines)
+        .into_iter()
+        .map(NbtTag::String)
+        .collect();
+
+    compound!(
+        "messages": NbtList::from(messages),
+        "color": options.color.as_str(),
+        "has_glowing_text": NbtTag::Byte(options.glowing as i8)
+    )
+}
+
+/// Returns the JSON of exactly four lines, filling the missing ones with empty text
+fn padded_lines(lines: &[TextComponent]) -> Vec<String> {
+    (0..4)
+        .map(|index| match lines.get(index) {
+            Some(line) => line.to_json(),
+            None => TextComponent::default().to_json(),
+        })
+        .collect()
+}
+
+/// Quotes text as a JSON string, escaping the characters JSON doesn't allow
+fn json_string(text: &str) -> String {
+    let mut json = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::versions::{JE_1_13_2, JE_1_16_5};
+
+    /// Places a sign with the given options and returns its block and block entity
+    fn sign(lines: &[TextComponent], options: &SignOptions) -> (String, NbtCompound) {
+        let mut schematic = MCSchematic::new();
+        schematic
+            .place_sign((0, 0, 0), "east", lines, options)
+            .unwrap();
+
+        let entity = schematic.get_block_entity((0, 0, 0)).unwrap().clone();
+        (schematic.get_block((0, 0, 0)).to_string(), entity)
+    }
+
+    #[test]
+    fn serializes_text_components() {
+        assert_eq!(TextComponent::new("A").to_json(), r#"{"text":"A"}"#);
+        assert_eq!(
+            TextComponent::new("say \"hi\"\n")
+                .colored("#FF0000")
+                .bold()
+                .to_json(),
+            r##"{"text":"say \"hi\"\n","color":"#FF0000","bold":true}"##
+        );
+        assert_eq!(
+            TextComponent::new("\u{1}").italic().to_json(),
+            r#"{"text":"\u0001","italic":true}"#
+        );
+    }
+
+    #[test]
+    fn writes_both_sides_since_1_20() {
+        let options = SignOptions {
+            waxed: true,
+            glowing: true,
+            back_lines: vec!["back".into()],
+            ..Default::default()
+        };
+        let (block, entity) = sign(&["REG A".into()], &options);
+        assert_eq!(block, "minecraft:oak_sign[rotation=12]");
+
+        let front: &NbtCompound = entity.get("front_text").unwrap();
+        let messages: &NbtList = front.get("messages").unwrap();
+        assert_eq!(messages.len(), 4);
+        assert_eq!(messages.get::<&str>(0).unwrap(), r#"{"text":"REG A"}"#);
+        assert_eq!(messages.get::<&str>(3).unwrap(), r#"{"text":""}"#);
+        assert_eq!(front.get::<_, i8>("has_glowing_text").unwrap(), 1);
+
+        let back: &NbtCompound = entity.get("back_text").unwrap();
+        let messages: &NbtList = back.get("messages").unwrap();
+        assert_eq!(messages.get::<&str>(0).unwrap(), r#"{"text":"back"}"#);
+        assert_eq!(entity.get::<_, i8>("is_waxed").unwrap(), 1);
+    }
+
+    #[test]
+    fn writes_four_text_tags_before_1_20() {
+        let options = SignOptions {
+            version: JE_1_16_5,
+            wall: true,
+            wood: "spruce".to_string(),
+            color: "red".to_string(),
+            ..Default::default()
+        };
+        let (block, entity) = sign(&["A".into(), "B".into()], &options);
+        assert_eq!(block, "minecraft:spruce_wall_sign[facing=east]");
+        assert_eq!(entity.get::<_, &str>("Text2").unwrap(), r#"{"text":"B"}"#);
+        assert_eq!(entity.get::<_, &str>("Text4").unwrap(), r#"{"text":""}"#);
+        assert_eq!(entity.get::<_, &str>("Color").unwrap(), "red");
+        assert!(!entity.contains_key("GlowingText"));
+        assert!(!entity.contains_key("front_text"));
+
+        let options = SignOptions {
+            version: JE_1_13_2,
+            ..Default::default()
+        };
+        let (_, entity) = sign(&["A".into()], &options);
+        assert!(!entity.contains_key("Color"));
+    }
+
+    #[test]
+    fn rejects_what_the_version_doesnt_have() {
+        let mut schematic = MCSchematic::new();
+        let rejected = [
+            SignOptions {
+                version: JE_1_13_2,
+                color: "red".to_string(),
+                ..Default::default()
+            },
+            SignOptions {
+                version: JE_1_16_5,
+                glowing: true,
+                ..Default::default()
+            },
+            SignOptions {
+                version: JE_1_16_5,
+                waxed: true,
+                ..Default::default()
+            },
+            SignOptions {
+                version: JE_1_16_5,
+                back_lines: vec!["back".into()],
+                ..Default::default()
+            },
+        ];
+        for options in rejected {
+            assert!(
+                schematic
+                    .place_sign((0, 0, 0), "east", &[], &options)
+                    .is_err(),
+                "{:?}",
+                options
+            );
+        }
+
+        let default = SignOptions::default();
+        assert!(schematic
+            .place_sign((0, 0, 0), "up", &[], &default)
+            .is_err());
+        let lines = vec![TextComponent::new("A"); 5];
+        assert!(schematic
+            .place_sign((0, 0, 0), "east", &lines, &default)
+            .is_err());
+        assert_eq!(schematic.get_block((0, 0, 0)), "minecraft:air");
+    }
+}
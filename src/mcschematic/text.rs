@@ -0,0 +1,334 @@
+use super::{step_coords, Coords, MCSchematic};
+use crate::block_state::apply_block;
+use crate::pattern::Pattern;
+
+const GLYPH_WIDTH: u16 = 5;
+const GLYPH_HEIGHT: u16 = 7;
+
+/// 5x7 bitmap font, one byte per row from top to bottom, with the
+/// leftmost column in the highest of the five bits
+#[rustfmt::skip]
+const FONT: &[(char, [u8; 7])] = &[
+    (' ', [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000]),
+    ('A', [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001]),
+    ('B', [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110]),
+    ('C', [0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110]),
+    ('D', [0b11110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b11110]),
+    ('E', [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111]),
+    ('F', [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000]),
+    ('G', [0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111]),
+    ('H', [0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001]),
+    ('I', [0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110]),
+    ('J', [0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100]),
+    ('K', [0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001]),
+    ('L', [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111]),
+    ('M', [0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001]),
+    ('N', [0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001]),
+    ('O', [0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110]),
+    ('P', [0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000]),
+    ('Q', [0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101]),
+    ('R', [0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001]),
+    ('S', [0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110]),
+    ('T', [0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100]),
+    ('U', [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110]),
+    ('V', [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100]),
+    ('W', [0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010]),
+    ('X', [0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001]),
+    ('Y', [0b10001, 0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100]),
+    ('Z', [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111]),
+    ('0', [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110]),
+    ('1', [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110]),
+    ('2', [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111]),
+    ('3', [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110]),
+    ('4', [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010]),
+    ('5', [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110]),
+    ('6', [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110]),
+    ('7', [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000]),
+    ('8', [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110]),
+    ('9', [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100]),
+    ('.', [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b01100]),
+    (',', [0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b00100, 0b01000]),
+    (':', [0b00000, 0b01100, 0b01100, 0b00000, 0b01100, 0b01100, 0b00000]),
+    (';', [0b00000, 0b01100, 0b01100, 0b00000, 0b01100, 0b00100, 0b01000]),
+    ('!', [0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00000, 0b00100]),
+    ('?', [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b00000, 0b00100]),
+    ('-', [0b00000, 0b00000, 0b00000, 0b11111, 0b00000, 0b00000, 0b00000]),
+    ('+', [0b00000, 0b00100, 0b00100, 0b11111, 0b00100, 0b00100, 0b00000]),
+    ('=', [0b00000, 0b00000, 0b11111, 0b00000, 0b11111, 0b00000, 0b00000]),
+    ('_', [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b11111]),
+    ('/', [0b00000, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b00000]),
+    ('(', [0b00010, 0b00100, 0b01000, 0b01000, 0b01000, 0b00100, 0b00010]),
+    (')', [0b01000, 0b00100, 0b00010, 0b00010, 0b00010, 0b00100, 0b01000]),
+    ('[', [0b01110, 0b01000, 0b01000, 0b01000, 0b01000, 0b01000, 0b01110]),
+    (']', [0b01110, 0b00010, 0b00010, 0b00010, 0b00010, 0b00010, 0b01110]),
+    ('<', [0b00010, 0b00100, 0b01000, 0b10000, 0b01000, 0b00100, 0b00010]),
+    ('>', [0b01000, 0b00100, 0b00010, 0b00001, 0b00010, 0b00100, 0b01000]),
+    ('\'', [0b01100, 0b00100, 0b01000, 0b00000, 0b00000, 0b00000, 0b00000]),
+    ('"', [0b01010, 0b01010, 0b01010, 0b00000, 0b00000, 0b00000, 0b00000]),
+    ('#', [0b01010, 0b01010, 0b11111, 0b01010, 0b11111, 0b01010, 0b01010]),
+    ('%', [0b11000, 0b11001, 0b00010, 0b00100, 0b01000, 0b10011, 0b00011]),
+    ('*', [0b00000, 0b00100, 0b10101, 0b01110, 0b10101, 0b00100, 0b00000]),
+];
+
+/// The plane text is drawn in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TextPlane {
+    /// Upright, like a sign on a wall. The origin is the top left corner
+    /// and `facing` is the side the text is read from
+    #[default]
+    Wall,
+    /// Lying flat on the ground. The origin is the top left corner
+    /// and `facing` is the direction the top of the letters points to
+    Floor,
+}
+
+/// Options for MCSchematic::draw_text
+pub struct TextOptions<'a> {
+    /// Size of a font pixel in blocks
+    pub scale: u16,
+    /// Whether the text stands upright or lies on the ground, see TextPlane
+    pub plane: TextPlane,
+    /// "north", "east", "south" or "west", see TextPlane
+    pub facing: &'a str,
+    /// Blocks of the letters
+    pub foreground: Pattern<'a>,
+    /// Blocks of the rectangle behind the letters, including the gaps
+    /// between them, or None to leave those positions untouched
+    pub background: Option<Pattern<'a>>,
+    /// Empty font pixels between two letters and between two lines
+    pub spacing: u16,
+}
+
+impl Default for TextOptions<'_> {
+    fn default() -> Self {
+        TextOptions {
+            scale: 1,
+            plane: TextPlane::Wall,
+            facing: "south",
+            foreground: Pattern::from("minecraft:white_concrete"),
+            background: None,
+            spacing: 1,
+        }
+    }
+}
+
+impl MCSchematic {
+    /// Writes text into the schematic using a built-in 5x7 pixel font. Lowercase letters
+    /// are drawn as uppercase, characters the font doesn't have as "?" and "\n" starts a new line.
+    /// Returns the width and height of the text in blocks
+    ///
+    /// origin - coords of the top left corner of the text, as seen by the reader
+    ///
+    /// text - the text to draw, such as "REG A"
+    ///
+    /// options - scale, orientation and blocks, see TextOptions
+    pub fn draw_text(
+        &mut self,
+        origin: Coords,
+        text: &str,
+        options: &TextOptions,
+    ) -> Result<(u16, u16), String> {
+        let (right, down) = text_axes(options.plane, options.facing)?;
+        let scale = options.scale.max(1);
+        let too_large = || "The text is too large to draw".to_string();
+
+        // Size of the text in font pixels, and in blocks once scaled
+        let lines: Vec<Vec<&[u8; 7]>> = text
+            .lines()
+            .map(|line| line.chars().map(glyph).collect())
+            .collect();
+        let pixels = |count: usize, glyph_size: u16| {
+            glyph_size
+                .checked_add(options.spacing)
+                .zip(u16::try_from(count).ok())
+                .and_then(|(size, count)| size.checked_mul(count))
+                .map(|pixels| pixels.saturating_sub(options.spacing))
+        };
+        let mut columns = 0;
+        for line in lines.iter() {
+            columns = columns.max(pixels(line.len(), GLYPH_WIDTH).ok_or_else(too_large)?);
+        }
+        let rows = pixels(lines.len(), GLYPH_HEIGHT).ok_or_else(too_large)?;
+        let width = columns.checked_mul(scale).ok_or_else(too_large)?;
+        let height = rows.checked_mul(scale).ok_or_else(too_large)?;
+
+        // The blocks furthest from the origin are at the opposite corner of the text
+        let position = |column: u16, row: u16| {
+            step_coords(origin, right, column as u32)
+                .and_then(|coords| step_coords(coords, down, row as u32))
+                .ok_or_else(|| "The text doesn't fit in the range of coords".to_string())
+        };
+        if width > 0 && height > 0 {
+            position(width - 1, height - 1)?;
+        }
+
+        for row in 0..height {
+            for column in 0..width {
+                let (font_row, font_column) = (row / scale, column / scale);
+                let line = font_row / (GLYPH_HEIGHT + options.spacing);
+                let glyph_row = font_row % (GLYPH_HEIGHT + options.spacing);
+                let character = font_column / (GLYPH_WIDTH + options.spacing);
+                let glyph_column = font_column % (GLYPH_WIDTH + options.spacing);
+
+                let lit = glyph_row < GLYPH_HEIGHT
+                    && glyph_column < GLYPH_WIDTH
+                    && lines[line as usize]
+                        .get(character as usize)
+                        .is_some_and(|glyph| {
+                            glyph[glyph_row as usize] >> (GLYPH_WIDTH - 1 - glyph_column) & 1 == 1
+                        });
+
+                let pattern = match (lit, &options.background) {
+                    (true, _) => &options.foreground,
+                    (false, Some(background)) => background,
+                    (false, None) => continue,
+                };
+
+                let coords = position(column, row)?;
+                let block = apply_block(self.get_block(coords), &pattern.block_at(coords));
+                self.set_block(coords, &block);
+            }
+        }
+
+        Ok((width, height))
+    }
+}
+
+/// Returns the bitmap of a character, "?" if the font doesn't have it
+fn glyph(character: char) -> &'static [u8; 7] {
+    let character = character.to_ascii_uppercase();
+
+    FONT.iter()
+        .find(|(c, _)| *c == character)
+        .or_else(|| FONT.iter().find(|(c, _)| *c == '?'))
+        .map(|(_, rows)| rows)
+        .unwrap_or(&FONT[0].1)
+}
+
+/// Returns the directions along which the text goes right and down
+fn text_axes(plane: TextPlane, facing: &str) -> Result<(Coords, Coords), String> {
+    let direction = match facing {
+        "north" => (0, 0, -1),
+        "east" => (1, 0, 0),
+        "south" => (0, 0, 1),
+        "west" => (-1, 0, 0),
+        _ => return Err(format!("Text can't face {}", facing)),
+    };
+    // The direction a quarter turn clockwise, seen from above
+    let clockwise = (-direction.2, 0, direction.0);
+
+    Ok(match plane {
+        // The reader looks against the facing direction, so their right is a quarter turn counterclockwise
+        TextPlane::Wall => ((-clockwise.0, 0, -clockwise.2), (0, -1, 0)),
+        TextPlane::Floor => (clockwise, (-direction.0, 0, -direction.2)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WHITE: &str = "minecraft:white_concrete";
+
+    #[test]
+    fn measures_lines_with_spacing_and_scale() {
+        let mut schematic = MCSchematic::new();
+        let options = TextOptions::default();
+        assert_eq!(schematic.draw_text((0, 0, 0), "AB", &options), Ok((11, 7)));
+        assert_eq!(
+            schematic.draw_text((0, 0, 0), "AB\nC", &options),
+            Ok((11, 15))
+        );
+
+        let options = TextOptions {
+            scale: 2,
+            spacing: 0,
+            ..Default::default()
+        };
+        assert_eq!(schematic.draw_text((0, 0, 0), "AB", &options), Ok((20, 14)));
+        assert_eq!(schematic.draw_text((0, 0, 0), "", &options), Ok((0, 0)));
+    }
+
+    #[test]
+    fn draws_upright_letters_towards_the_reader() {
+        let mut schematic = MCSchematic::new();
+        schematic
+            .draw_text((0, 10, 0), "I", &TextOptions::default())
+            .unwrap();
+
+        // Read from the south, so the text goes east and down
+        let top: Vec<&str> = (0..5).map(|x| schematic.get_block((x, 10, 0))).collect();
+        assert_eq!(top, ["minecraft:air", WHITE, WHITE, WHITE, "minecraft:air"]);
+        assert_eq!(schematic.get_block((2, 5, 0)), WHITE);
+        assert_eq!(schematic.get_block((0, 5, 0)), "minecraft:air");
+
+        let mut schematic = MCSchematic::new();
+        let options = TextOptions {
+            facing: "north",
+            ..Default::default()
+        };
+        schematic.draw_text((0, 10, 0), "L", &options).unwrap();
+        assert_eq!(schematic.get_block((0, 10, 0)), WHITE);
+        assert_eq!(schematic.get_block((-4, 4, 0)), WHITE);
+        assert_eq!(schematic.get_block((-4, 10, 0)), "minecraft:air");
+    }
+
+    #[test]
+    fn draws_flat_letters_with_a_background() {
+        let mut schematic = MCSchematic::new();
+        let options = TextOptions {
+            plane: TextPlane::Floor,
+            facing: "north",
+            background: Some(Pattern::from("minecraft:black_concrete")),
+            ..Default::default()
+        };
+        schematic.draw_text((0, 0, 0), "LL", &options).unwrap();
+
+        // The top of the letters points north, so the lines go east and south
+        assert_eq!(schematic.get_block((0, 0, 0)), WHITE);
+        assert_eq!(schematic.get_block((4, 0, 6)), WHITE);
+        assert_eq!(schematic.get_block((4, 0, 0)), "minecraft:black_concrete");
+        // The gap between the letters is background too
+        assert_eq!(schematic.get_block((5, 0, 3)), "minecraft:black_concrete");
+        assert_eq!(schematic.get_block((6, 0, 0)), WHITE);
+    }
+
+    #[test]
+    fn draws_unknown_characters_as_question_marks() {
+        let mut unknown = MCSchematic::new();
+        let mut question_mark = MCSchematic::new();
+        unknown
+            .draw_text((0, 0, 0), "é", &TextOptions::default())
+            .unwrap();
+        question_mark
+            .draw_text((0, 0, 0), "?", &TextOptions::default())
+            .unwrap();
+        let mut lowercase = MCSchematic::new();
+        let mut uppercase = MCSchematic::new();
+        lowercase
+            .draw_text((0, 0, 0), "a", &TextOptions::default())
+            .unwrap();
+        uppercase
+            .draw_text((0, 0, 0), "A", &TextOptions::default())
+            .unwrap();
+
+        for coords in [(1, 0, 0), (2, -6, 0), (0, -3, 0)] {
+            assert_eq!(unknown.get_block(coords), question_mark.get_block(coords));
+            assert_eq!(lowercase.get_block(coords), uppercase.get_block(coords));
+        }
+    }
+
+    #[test]
+    fn rejects_bad_facings_and_text_out_of_range() {
+        let mut schematic = MCSchematic::new();
+        let options = TextOptions {
+            facing: "up",
+            ..Default::default()
+        };
+        assert!(schematic.draw_text((0, 0, 0), "A", &options).is_err());
+        assert!(schematic
+            .draw_text((i16::MAX - 2, 0, 0), "A", &TextOptions::default())
+            .is_err());
+        assert_eq!(schematic.get_block((i16::MAX - 2, 0, 0)), "minecraft:air");
+    }
+}
@@ -0,0 +1,220 @@
+use std::fmt;
+
+use super::{Coords, MCSchematic};
+use crate::block_registry::block_info;
+use crate::block_state::BlockState;
+use crate::renames::upgrade;
+use crate::versions::McVersion;
+
+/// What's wrong with a block found by MCSchematic::validate
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Problem {
+    /// The block id isn't in the block registry
+    UnknownBlock,
+    /// The block was only added by a later version, with the given DataVersion
+    NotInVersion(i32),
+    /// The block doesn't have a property with this name in the version
+    UnknownProperty(String),
+    /// The property doesn't allow this value in the version
+    InvalidValue { property: String, value: String },
+}
+
+/// A problem with one of the blocks in the palette, along with every position using that block
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidationIssue {
+    pub block: String,
+    pub problem: Problem,
+    pub coords: Vec<Coords>,
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.problem {
+            Problem::UnknownBlock => write!(f, "unknown block {}", self.block)?,
+            Problem::NotInVersion(since) => match McVersion::from_data_version(*since) {
+                Some(version) => write!(f, "{} was only added in {}", self.block, version)?,
+                None => write!(f, "{} was only added in DataVersion {}", self.block, since)?,
+            },
+            Problem::UnknownProperty(property) => {
+                write!(f, "{} has no property \"{}\"", self.block, property)?
+            }
+            Problem::InvalidValue { property, value } => {
+                write!(f, "{} doesn't allow {}={}", self.block, property, value)?
+            }
+        }
+
+        write!(
+            f,
+            " at {} position(s): {:?}",
+            self.coords.len(),
+            self.coords
+        )
+    }
+}
+
+impl MCSchematic {
+    /// Checks every block against the block registry of the given version, reporting
+    /// unknown blocks, blocks added by later versions, unknown properties and invalid
+    /// property values, together with the coords of every block affected.
+    /// Blocks are expected to use their current names, which get renamed on save.
+    /// The registry only knows the current property values, so values a version named
+    /// differently, like the "true" and "false" sides of walls before 1.16, are accepted
+    /// when the renames module knows what they became
+    pub fn validate(&self, version: McVersion) -> Vec<ValidationIssue> {
+        let names = self.palette_names();
+
+        let mut problems: Vec<Vec<Problem>> = names
+            .iter()
+            .map(|name| block_problems(name, version))
+            .collect();
+        let mut positions: Vec<Vec<Coords>> = vec![vec![]; names.len()];
+        for (&coords, &index) in self.block_data.iter() {
+            if !problems[index as usize].is_empty() {
+                positions[index as usize].push(coords);
+            }
+        }
+
+        let mut issues = vec![];
+        for (index, coords) in positions.iter_mut().enumerate() {
+            if coords.is_empty() {
+                continue;
+            }
+            coords.sort_by_key(|&(x, y, z)| (y, z, x));
+
+            for problem in problems[index].drain(..) {
+                issues.push(ValidationIssue {
+                    block: names[index].to_string(),
+                    problem,
+                    coords: coords.clone(),
+                });
+            }
+        }
+
+        issues
+    }
+}
+
+/// Lists everything wrong with a single block
+fn block_problems(block: &str, version: McVersion) -> Vec<Problem> {
+    let state = BlockState::parse(block);
+    let Some(info) = block_info(&state.id) else {
+        return vec![Problem::UnknownBlock];
+    };
+
+    let mut problems = vec![];
+    if !info.exists_in(version) {
+        problems.push(Problem::NotInVersion(info.since));
+    }
+
+    // The same block with the values the version used renamed to their current names
+    let upgraded = upgrade(block, version.data_version())
+        .map(|block| BlockState::parse(&block))
+        .unwrap_or_else(|_| state.clone());
+
+    for (property, value) in state.properties {
+        let Some(values) = info.allowed_values(&property, version) else {
+            problems.push(Problem::UnknownProperty(property));
+            continue;
+        };
+        let current = upgraded.property(&property).unwrap_or(&value);
+        if !values.contains(&value.as_str()) && !values.contains(&current) {
+            problems.push(Problem::InvalidValue { property, value });
+        }
+    }
+
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::versions::{JE_1_13_2, JE_1_15_2, JE_1_20_1};
+
+    #[test]
+    fn accepts_valid_blocks() {
+        let mut schematic = MCSchematic::new();
+        schematic.set_block((0, 0, 0), "minecraft:stone");
+        schematic.set_block((1, 0, 0), "minecraft:repeater[delay=2,facing=east]");
+        schematic.set_block((2, 0, 0), "minecraft:cobblestone_wall[east=low,up=true]");
+
+        assert_eq!(schematic.validate(JE_1_20_1), vec![]);
+    }
+
+    #[test]
+    fn reports_every_problem_with_its_coords() {
+        let mut schematic = MCSchematic::new();
+        schematic.set_block((0, 1, 0), "minecraft:stonee");
+        schematic.set_block((3, 0, 0), "minecraft:stonee");
+        schematic.set_block((1, 0, 0), "minecraft:repeater[facing=up,color=red]");
+
+        assert_eq!(
+            schematic.validate(JE_1_20_1),
+            vec![
+                ValidationIssue {
+                    block: "minecraft:stonee".to_string(),
+                    problem: Problem::UnknownBlock,
+                    coords: vec![(3, 0, 0), (0, 1, 0)],
+                },
+                ValidationIssue {
+                    block: "minecraft:repeater[facing=up,color=red]".to_string(),
+                    problem: Problem::InvalidValue {
+                        property: "facing".to_string(),
+                        value: "up".to_string(),
+                    },
+                    coords: vec![(1, 0, 0)],
+                },
+                ValidationIssue {
+                    block: "minecraft:repeater[facing=up,color=red]".to_string(),
+                    problem: Problem::UnknownProperty("color".to_string()),
+                    coords: vec![(1, 0, 0)],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_blocks_and_properties_added_later() {
+        let mut schematic = MCSchematic::new();
+        schematic.set_block((0, 0, 0), "minecraft:spruce_sign");
+        schematic.set_block((1, 0, 0), "minecraft:lantern[waterlogged=true]");
+        schematic.set_block((2, 0, 0), "minecraft:note_block[instrument=bit]");
+
+        let issues = schematic.validate(JE_1_13_2);
+        let problems: Vec<&Problem> = issues.iter().map(|issue| &issue.problem).collect();
+        assert_eq!(
+            problems,
+            vec![
+                &Problem::NotInVersion(1952),
+                &Problem::NotInVersion(1952),
+                &Problem::UnknownProperty("waterlogged".to_string()),
+                &Problem::InvalidValue {
+                    property: "instrument".to_string(),
+                    value: "bit".to_string()
+                },
+            ]
+        );
+        assert_eq!(
+            issues[0].to_string(),
+            "minecraft:spruce_sign was only added in 1.14 at 1 position(s): [(0, 0, 0)]"
+        );
+
+        let issues = schematic.validate(JE_1_15_2);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(
+            issues[0].problem,
+            Problem::UnknownProperty("waterlogged".to_string())
+        );
+    }
+
+    #[test]
+    fn accepts_the_values_older_versions_used() {
+        let mut schematic = MCSchematic::new();
+        schematic.set_block(
+            (0, 0, 0),
+            "minecraft:cobblestone_wall[east=true,west=false]",
+        );
+
+        assert_eq!(schematic.validate(JE_1_15_2), vec![]);
+        assert_eq!(schematic.validate(JE_1_20_1).len(), 2);
+    }
+}
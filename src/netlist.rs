@@ -0,0 +1,440 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+
+use crate::gates::{GateKind, GateOptions};
+use crate::mcschematic::{Coords, MCSchematic};
+use crate::wiring::lay_paths;
+
+/// Gate names of the netlist format, with the gate they stand for
+const GATE_NAMES: &[(&str, GateKind)] = &[
+    ("not", GateKind::Not),
+    ("and", GateKind::And),
+    ("or", GateKind::Or),
+    ("xor", GateKind::Xor),
+    ("latch", GateKind::Latch),
+    ("tff", GateKind::TFlipFlop),
+];
+
+/// A gate of a netlist, driving its output net from its input nets
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NetGate {
+    pub kind: GateKind,
+    pub output: String,
+    /// Input nets in the order listed by GateKind
+    pub inputs: Vec<String>,
+}
+
+/// A circuit made of gates connected by named nets
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Netlist {
+    /// Nets driven from outside of the circuit, becoming levers
+    pub inputs: Vec<String>,
+    /// Nets read from outside of the circuit, becoming lamps
+    pub outputs: Vec<String>,
+    pub gates: Vec<NetGate>,
+}
+
+/// Options for Netlist::compile
+#[derive(Clone, Debug)]
+pub struct NetlistOptions {
+    /// Solid block carrying the signals inside the gates
+    pub gate_block: String,
+    /// Block below the gates and the wire
+    pub support_block: String,
+    /// Block driving every input net, such as a lever
+    pub input_block: String,
+    /// Block powered by every output net, such as a lamp
+    pub output_block: String,
+}
+
+impl Default for NetlistOptions {
+    fn default() -> Self {
+        NetlistOptions {
+            gate_block: "minecraft:stone".to_string(),
+            support_block: "minecraft:smooth_stone".to_string(),
+            input_block: "minecraft:lever[face=floor,facing=east,powered=false]".to_string(),
+            output_block: "minecraft:redstone_lamp[lit=false]".to_string(),
+        }
+    }
+}
+
+/// A netlist placed and routed into a schematic
+pub struct CompiledNetlist {
+    pub schematic: MCSchematic,
+    /// Position of the input block of every input net
+    pub inputs: Vec<(String, Coords)>,
+    /// Position of the output block of every output net
+    pub outputs: Vec<(String, Coords)>,
+}
+
+/// What drives a net
+#[derive(Clone, Copy)]
+enum Driver {
+    Input,
+    Gate(usize),
+}
+
+impl Netlist {
+    /// Parses a netlist with one statement per line. Everything after a # is a comment
+    ///
+    /// "inputs a b c" - declares input nets
+    ///
+    /// "outputs x y" - declares output nets
+    ///
+    /// "and x a b" - a gate driving the net x from the nets a and b. The gates are
+    /// not, and, or, xor, latch (inputs D and E) and tff, see GateKind
+    pub fn parse(text: &str) -> Result<Netlist, String> {
+        let mut netlist = Netlist::default();
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default();
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let Some((&statement, nets)) = words.split_first() else {
+                continue;
+            };
+            let nets: Vec<String> = nets.iter().map(|net| net.to_string()).collect();
+
+            match statement {
+                "input" | "inputs" => netlist.inputs.extend(nets),
+                "output" | "outputs" => netlist.outputs.extend(nets),
+                name => {
+                    let Some(&(_, kind)) = GATE_NAMES.iter().find(|(gate, _)| *gate == name) else {
+                        return Err(format!("Line {}: unknown gate \"{}\"", number + 1, name));
+                    };
+                    if nets.len() != kind.input_count() + 1 {
+                        return Err(format!(
+                            "Line {}: {} takes an output and {} inputs",
+                            number + 1,
+                            name,
+                            kind.input_count()
+                        ));
+                    }
+                    netlist.gates.push(NetGate {
+                        kind,
+                        output: nets[0].clone(),
+                        inputs: nets[1..].to_vec(),
+                    });
+                }
+            }
+        }
+
+        Ok(netlist)
+    }
+
+    /// Reads a netlist from a text file, see Netlist::parse
+    ///
+    /// file_path - location and name of the file
+    pub fn from_file(file_path: &str) -> Result<Netlist, String> {
+        let Ok(text) = fs::read_to_string(file_path) else {
+            return Err(format!("Failed to open {}", file_path));
+        };
+
+        Netlist::parse(&text)
+    }
+
+    /// Places and routes the netlist into a schematic. The inputs are at x = 0 and the
+    /// signals flow towards +x through columns of gates, every gate being one column
+    /// further than the gates driving it. Every input, gate and output gets its own
+    /// rows along z, so wire runs along x at the height of the gates and crosses over
+    /// the others two blocks higher when going along z. Repeaters keep the signals up
+    /// on long wires
+    ///
+    /// options - blocks of the circuit, see NetlistOptions
+    pub fn compile(&self, options: &NetlistOptions) -> Result<CompiledNetlist, String> {
+        let drivers = self.drivers()?;
+        let levels = self.gate_levels(&drivers)?;
+        let output_column = levels.iter().max().map_or(1, |level| level + 1);
+
+        // Inputs and outputs of every net that has to be routed
+        let mut sinks: HashMap<&str, Vec<Sink>> = HashMap::new();
+        for (index, gate) in self.gates.iter().enumerate() {
+            for (port, net) in gate.inputs.iter().enumerate() {
+                sinks.entry(net).or_default().push(Sink::Gate(index, port));
+            }
+        }
+        for (index, net) in self.outputs.iter().enumerate() {
+            sinks.entry(net).or_default().push(Sink::Output(index));
+        }
+        let mut nets: Vec<&str> = sinks.keys().copied().collect();
+        nets.sort_by_key(|net| self.net_order(net));
+        for net in nets.iter() {
+            if !drivers.contains_key(net) {
+                return Err(format!("Net {} isn't driven by an input or a gate", net));
+            }
+        }
+
+        let column_of = |net: &str| match drivers[net] {
+            Driver::Input => 0,
+            Driver::Gate(index) => levels[index],
+        };
+
+        // Every net crossing over along z gets its own lane in the channel after its driver
+        let mut lanes: HashMap<&str, usize> = HashMap::new();
+        let mut channel_sizes = vec![0; output_column];
+        for &net in nets.iter() {
+            let column = column_of(net);
+            lanes.insert(net, channel_sizes[column]);
+            channel_sizes[column] += 1;
+        }
+
+        let mut column_starts = vec![0i16];
+        let mut channel_starts = vec![];
+        let mut column_end = 0;
+        for column in 1..=output_column {
+            let channel_start = column_end + 1;
+            channel_starts.push(channel_start);
+            let start = match channel_sizes[column - 1] {
+                0 => channel_start,
+                lanes => channel_start + 3 * lanes as i16 + 2,
+            };
+            column_starts.push(start);
+
+            let length = self
+                .gates
+                .iter()
+                .zip(levels.iter())
+                .filter(|(_, &level)| level == column)
+                .map(|(gate, _)| gate.kind.size().0)
+                .max()
+                .unwrap_or(1);
+            column_end = start + length - 1;
+        }
+
+        // Rows of the inputs, then of the gates by column, then of the outputs
+        let mut schematic = MCSchematic::new();
+        let mut z = 0;
+        let mut sources: HashMap<&str, Coords> = HashMap::new();
+        let mut inputs = vec![];
+        for net in self.inputs.iter() {
+            schematic.set_block((0, 0, z), &options.support_block);
+            schematic.set_block((0, 1, z), &options.input_block);
+            sources.insert(net, (1, 1, z));
+            inputs.push((net.clone(), (0, 1, z)));
+            z += 2;
+        }
+
+        let gate_options = GateOptions {
+            block: options.gate_block.clone(),
+            support_block: options.support_block.clone(),
+            ..Default::default()
+        };
+        let mut order: Vec<usize> = (0..self.gates.len()).collect();
+        order.sort_by_key(|&index| levels[index]);
+        let mut feeds: HashMap<Sink, Coords> = HashMap::new();
+        for index in order {
+            let gate = &self.gates[index];
+            let origin = (column_starts[levels[index]], 0, z);
+            let ports = schematic.place_gate(origin, gate.kind, &gate_options)?;
+
+            let (x, y, output_z) = ports.output;
+            sources.insert(&gate.output, (x + 1, y, output_z));
+            for (port, &(x, y, input_z)) in ports.inputs.iter().enumerate() {
+                feeds.insert(Sink::Gate(index, port), (x - 1, y, input_z));
+            }
+            z += gate.kind.size().1 + 1;
+        }
+
+        let mut outputs = vec![];
+        for (index, net) in self.outputs.iter().enumerate() {
+            let x = column_starts[output_column];
+            schematic.set_block((x, 0, z), &options.support_block);
+            schematic.set_block((x, 1, z), &options.output_block);
+            feeds.insert(Sink::Output(index), (x - 1, 1, z));
+            outputs.push((net.clone(), (x, 1, z)));
+            z += 2;
+        }
+
+        for &net in nets.iter() {
+            let source = sources[net];
+            let lane = channel_starts[column_of(net)] + 2 + 3 * lanes[net] as i16;
+            let paths: Vec<Vec<Coords>> = sinks[net]
+                .iter()
+                .map(|sink| channel_path(source, feeds[sink], lane))
+                .collect();
+            lay_paths(&mut schematic, &paths, 15, &options.support_block);
+        }
+
+        Ok(CompiledNetlist {
+            schematic,
+            inputs,
+            outputs,
+        })
+    }
+
+    /// Returns what drives every net, checking that no net is driven twice
+    fn drivers(&self) -> Result<HashMap<&str, Driver>, String> {
+        let mut drivers = HashMap::new();
+        let inputs = self.inputs.iter().map(|net| (net, Driver::Input));
+        let gates = self
+            .gates
+            .iter()
+            .enumerate()
+            .map(|(i, gate)| (&gate.output, Driver::Gate(i)));
+
+        for (net, driver) in inputs.chain(gates) {
+            if drivers.insert(net.as_str(), driver).is_some() {
+                return Err(format!("Net {} is driven more than once", net));
+            }
+        }
+
+        Ok(drivers)
+    }
+
+    /// Returns the column of every gate, one more than the furthest column driving it.
+    /// Inputs are in column 0
+    fn gate_levels(&self, drivers: &HashMap<&str, Driver>) -> Result<Vec<usize>, String> {
+        let mut levels: Vec<Option<usize>> = vec![None; self.gates.len()];
+        let mut visiting = HashSet::new();
+
+        for index in 0..self.gates.len() {
+            self.gate_level(index, drivers, &mut levels, &mut visiting)?;
+        }
+
+        Ok(levels.into_iter().map(|level| level.unwrap_or(1)).collect())
+    }
+
+    fn gate_level(
+        &self,
+        index: usize,
+        drivers: &HashMap<&str, Driver>,
+        levels: &mut Vec<Option<usize>>,
+        visiting: &mut HashSet<usize>,
+    ) -> Result<usize, String> {
+        if let Some(level) = levels[index] {
+            return Ok(level);
+        }
+        let gate = &self.gates[index];
+        if !visiting.insert(index) {
+            return Err(format!(
+                "Net {} depends on itself, loops aren't supported",
+                gate.output
+            ));
+        }
+
+        let mut level = 1;
+        for net in gate.inputs.iter() {
+            match drivers.get(net.as_str()) {
+                Some(&Driver::Gate(driver)) => {
+                    level = level.max(self.gate_level(driver, drivers, levels, visiting)? + 1);
+                }
+                Some(Driver::Input) => {}
+                None => return Err(format!("Net {} isn't driven by an input or a gate", net)),
+            }
+        }
+
+        visiting.remove(&index);
+        levels[index] = Some(level);
+        Ok(level)
+    }
+
+    /// Sorts nets in the order they're declared in, so the layout doesn't change between runs
+    fn net_order(&self, net: &str) -> usize {
+        let inputs = self.inputs.iter();
+        let gates = self.gates.iter().map(|gate| &gate.output);
+        inputs
+            .chain(gates)
+            .position(|name| name == net)
+            .unwrap_or(usize::MAX)
+    }
+}
+
+/// Something reading a net: an input of a gate or an output of the netlist
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum Sink {
+    Gate(usize, usize),
+    Output(usize),
+}
+
+/// Returns the cells of the wire from a source to a sink in another row: along x on
+/// the source row, up two blocks, along z on the given lane, down two blocks and along x
+/// on the sink row
+fn channel_path(source: Coords, sink: Coords, lane: i16) -> Vec<Coords> {
+    let (source_x, y, source_z) = source;
+    let (sink_x, _, sink_z) = sink;
+
+    let mut cells: Vec<Coords> = (source_x..=lane - 2).map(|x| (x, y, source_z)).collect();
+    cells.push((lane - 1, y + 1, source_z));
+    let step = if sink_z > source_z { 1 } else { -1 };
+    let mut z = source_z;
+    loop {
+        cells.push((lane, y + 2, z));
+        if z == sink_z {
+            break;
+        }
+        z += step;
+    }
+    cells.push((lane + 1, y + 1, sink_z));
+    cells.extend((lane + 2..=sink_x).map(|x| (x, y, sink_z)));
+
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::Simulation;
+
+    const FULL_ADDER: &str = "
+        inputs a b cin
+        outputs sum cout
+        xor half a b  # the sum of a and b
+        xor sum half cin
+        and carry_ab a b
+        and carry_half half cin
+        or cout carry_ab carry_half
+    ";
+
+    #[test]
+    fn parses_statements_and_comments() {
+        let netlist = Netlist::parse(FULL_ADDER).unwrap();
+        assert_eq!(netlist.inputs, ["a", "b", "cin"]);
+        assert_eq!(netlist.outputs, ["sum", "cout"]);
+        assert_eq!(netlist.gates.len(), 5);
+        assert_eq!(
+            netlist.gates[0],
+            NetGate {
+                kind: GateKind::Xor,
+                output: "half".to_string(),
+                inputs: vec!["a".to_string(), "b".to_string()],
+            }
+        );
+
+        assert!(Netlist::parse("nand x a b").is_err());
+        assert!(Netlist::parse("and x a").is_err());
+    }
+
+    #[test]
+    fn rejects_undriven_nets_loops_and_multiple_drivers() {
+        let compile = |text: &str| Netlist::parse(text)?.compile(&NetlistOptions::default());
+        assert!(compile("inputs a\noutputs x\nand x a b").is_err());
+        assert!(compile("inputs a\noutputs x\nand x a y\nnot y x").is_err());
+        assert!(compile("inputs a\noutputs x\nnot x a\nnot x a").is_err());
+        assert!(compile("inputs a\noutputs x").is_err());
+    }
+
+    #[test]
+    fn compiles_a_working_full_adder() {
+        let compiled = Netlist::parse(FULL_ADDER)
+            .unwrap()
+            .compile(&NetlistOptions::default())
+            .unwrap();
+        let mut simulation = Simulation::new(&compiled.schematic);
+        simulation.run_until_stable(200).unwrap();
+
+        for inputs in 0..8 {
+            let bits = [inputs & 1 != 0, inputs & 2 != 0, inputs & 4 != 0];
+            for ((_, lever), &on) in compiled.inputs.iter().zip(bits.iter()) {
+                simulation.set_lever(*lever, on).unwrap();
+            }
+            simulation.run_until_stable(200).unwrap();
+
+            let total = bits.iter().filter(|&&bit| bit).count();
+            let lit: Vec<bool> = compiled
+                .outputs
+                .iter()
+                .map(|&(_, lamp)| simulation.is_powered(lamp))
+                .collect();
+            assert_eq!(lit, [total % 2 == 1, total >= 2], "{:?}", bits);
+        }
+    }
+}
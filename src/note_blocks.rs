@@ -0,0 +1,476 @@
+use std::fs;
+
+use midly::{MetaMessage, MidiMessage, Smf, Timing, TrackEventKind};
+
+use crate::mcschematic::{Coords, MCSchematic};
+
+/// Sound of a note block, picked by the block below it
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Instrument {
+    Harp,
+    Basedrum,
+    Snare,
+    Hat,
+    Bass,
+    Flute,
+    Bell,
+    Guitar,
+    Chime,
+    Xylophone,
+    IronXylophone,
+    CowBell,
+    Didgeridoo,
+    Bit,
+    Banjo,
+    Pling,
+}
+
+/// Every instrument with its block state value, the block below the note block
+/// that selects it and the MIDI key of its lowest note (note=0).
+/// The pitched instruments are listed from the most to the least common choice
+const INSTRUMENTS: &[(Instrument, &str, &str, u8)] = &[
+    (Instrument::Harp, "harp", "minecraft:dirt", 54),
+    (Instrument::Bass, "bass", "minecraft:oak_planks", 30),
+    (Instrument::Bell, "bell", "minecraft:gold_block", 78),
+    (Instrument::Flute, "flute", "minecraft:clay", 66),
+    (Instrument::Guitar, "guitar", "minecraft:white_wool", 42),
+    (Instrument::Chime, "chime", "minecraft:packed_ice", 78),
+    (
+        Instrument::Xylophone,
+        "xylophone",
+        "minecraft:bone_block",
+        78,
+    ),
+    (
+        Instrument::IronXylophone,
+        "iron_xylophone",
+        "minecraft:iron_block",
+        54,
+    ),
+    (Instrument::CowBell, "cow_bell", "minecraft:soul_sand", 66),
+    (
+        Instrument::Didgeridoo,
+        "didgeridoo",
+        "minecraft:pumpkin",
+        30,
+    ),
+    (Instrument::Bit, "bit", "minecraft:emerald_block", 54),
+    (Instrument::Banjo, "banjo", "minecraft:hay_block", 54),
+    (Instrument::Pling, "pling", "minecraft:glowstone", 54),
+    (Instrument::Basedrum, "basedrum", "minecraft:stone", 54),
+    (Instrument::Snare, "snare", "minecraft:sand", 54),
+    (Instrument::Hat, "hat", "minecraft:glass", 54),
+];
+
+/// Number of different notes a note block can play
+const NOTE_RANGE: u8 = 25;
+
+/// Note names of an octave, starting at C
+const KEY_NAMES: [&str; 12] = [
+    "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B",
+];
+
+impl Instrument {
+    fn entry(self) -> &'static (Instrument, &'static str, &'static str, u8) {
+        INSTRUMENTS
+            .iter()
+            .find(|(instrument, ..)| *instrument == self)
+            .expect("every instrument is in the table")
+    }
+
+    /// Returns the value of the "instrument" block state
+    pub fn name(self) -> &'static str {
+        self.entry().1
+    }
+
+    /// Returns the block that has to be below the note block to play this instrument
+    pub fn base_block(self) -> &'static str {
+        self.entry().2
+    }
+
+    /// Returns the MIDI key of the lowest note the instrument plays
+    pub fn lowest_key(self) -> u8 {
+        self.entry().3
+    }
+
+    /// Returns the most common pitched instrument able to play the given MIDI key, if any
+    pub fn for_key(key: u8) -> Option<Instrument> {
+        INSTRUMENTS
+            .iter()
+            .filter(|(instrument, ..)| instrument.is_pitched())
+            .find(|&&(_, _, _, lowest)| (lowest..lowest + NOTE_RANGE).contains(&key))
+            .map(|&(instrument, ..)| instrument)
+    }
+
+    /// Returns false for the drums, whose pitch doesn't change the sound much
+    pub fn is_pitched(self) -> bool {
+        !matches!(
+            self,
+            Instrument::Basedrum | Instrument::Snare | Instrument::Hat
+        )
+    }
+
+    /// Returns the note block state value playing the given MIDI key, moving keys
+    /// outside of the instrument's two octaves by whole octaves until they fit
+    pub fn note(self, key: u8) -> u8 {
+        let lowest = self.lowest_key();
+        let mut key = key;
+        while key < lowest {
+            key += 12;
+        }
+        while key >= lowest + NOTE_RANGE {
+            key -= 12;
+        }
+
+        key - lowest
+    }
+}
+
+/// A single note of a song
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Note {
+    /// When the note plays, in redstone ticks (0.1 seconds) from the start of the song
+    pub tick: u32,
+    /// MIDI key of the note, 60 being the middle C (C4)
+    pub key: u8,
+    pub instrument: Instrument,
+}
+
+impl Note {
+    /// Returns a note played by the most common instrument able to play the key,
+    /// or by the harp if none of them can
+    pub fn new(tick: u32, key: u8) -> Note {
+        Note {
+            tick,
+            key,
+            instrument: Instrument::for_key(key).unwrap_or(Instrument::Harp),
+        }
+    }
+}
+
+/// Parses a note name such as "C4", "F#3" or "Bb5" into its MIDI key, C4 being 60
+pub fn parse_key(name: &str) -> Result<u8, String> {
+    let name = name.trim();
+    let split = name
+        .find(|c: char| c.is_ascii_digit() || c == '-')
+        .ok_or_else(|| format!("Note \"{}\" has no octave", name))?;
+    let (pitch, octave) = name.split_at(split);
+
+    let (letter, accidental) = pitch.split_at(pitch.chars().next().map_or(0, char::len_utf8));
+    let Some(index) = KEY_NAMES
+        .iter()
+        .position(|key| key.eq_ignore_ascii_case(letter))
+    else {
+        return Err(format!("Unknown note \"{}\"", name));
+    };
+    let shift = match accidental {
+        "" => 0,
+        "#" => 1,
+        "b" => -1,
+        _ => return Err(format!("Unknown note \"{}\"", name)),
+    };
+    let Ok(octave) = octave.parse::<i32>() else {
+        return Err(format!("Invalid octave in note \"{}\"", name));
+    };
+
+    u8::try_from((octave + 1) * 12 + index as i32 + shift)
+        .ok()
+        .filter(|&key| key < 128)
+        .ok_or_else(|| format!("Note \"{}\" is out of the MIDI range", name))
+}
+
+/// Reads the notes of a MIDI file. Tempo changes are followed and every note gets
+/// the closest redstone tick. Notes on the percussion channel (10) become drums,
+/// the others get the most common instrument able to play them, see Note::new.
+/// Every note is returned, but chords often end up with more notes on the same tick
+/// than MCSchematic::build_song can play, see there
+///
+/// file_path - location and name of the .mid file
+pub fn notes_from_midi(file_path: &str) -> Result<Vec<Note>, String> {
+    let Ok(bytes) = fs::read(file_path) else {
+        return Err(format!("Failed to open {}", file_path));
+    };
+    let Ok(smf) = Smf::parse(&bytes) else {
+        return Err(format!("{} is not a valid MIDI file", file_path));
+    };
+
+    // Gather the events of all tracks by their time in MIDI ticks
+    let mut events = vec![];
+    for track in smf.tracks.iter() {
+        let mut time = 0u64;
+        for event in track.iter() {
+            time += event.delta.as_int() as u64;
+            events.push((time, event.kind));
+        }
+    }
+    events.sort_by_key(|&(time, _)| time);
+
+    let mut notes = vec![];
+    let mut microseconds_per_beat = 500_000.0;
+    let (mut last_time, mut seconds) = (0, 0.0);
+
+    for (time, kind) in events {
+        seconds += (time - last_time) as f64
+            * match smf.header.timing {
+                Timing::Metrical(ticks_per_beat) => {
+                    microseconds_per_beat / 1_000_000.0 / ticks_per_beat.as_int() as f64
+                }
+                Timing::Timecode(fps, subframes) => 1.0 / fps.as_f32() as f64 / subframes as f64,
+            };
+        last_time = time;
+
+        match kind {
+            TrackEventKind::Meta(MetaMessage::Tempo(tempo)) => {
+                microseconds_per_beat = tempo.as_int() as f64;
+            }
+            TrackEventKind::Midi {
+                channel,
+                message: MidiMessage::NoteOn { key, vel },
+            } if vel.as_int() > 0 => {
+                let tick = (seconds * 10.0).round() as u32;
+                let key = key.as_int();
+                notes.push(match channel.as_int() {
+                    9 => Note {
+                        tick,
+                        key,
+                        instrument: drum_for_key(key),
+                    },
+                    _ => Note::new(tick, key),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    Ok(notes)
+}
+
+/// Picks the drum closest to a key of the General MIDI percussion channel
+fn drum_for_key(key: u8) -> Instrument {
+    match key {
+        35 | 36 | 41 | 43 | 45 | 47 => Instrument::Basedrum,
+        37..=40 => Instrument::Snare,
+        _ => Instrument::Hat,
+    }
+}
+
+/// Options for MCSchematic::build_song
+#[derive(Clone, Debug)]
+pub struct SongOptions {
+    /// Block the repeaters stand on
+    pub support_block: String,
+    /// Block powered by the repeater line at every step, activating the note blocks next to it
+    pub step_block: String,
+}
+
+impl Default for SongOptions {
+    fn default() -> Self {
+        SongOptions {
+            support_block: "minecraft:smooth_stone".to_string(),
+            step_block: "minecraft:iron_block".to_string(),
+        }
+    }
+}
+
+impl MCSchematic {
+    /// Builds a note block song along the x axis. A line of repeaters, waiting as many
+    /// redstone ticks as there are between two groups of notes, powers a block at every
+    /// step, which plays the note blocks on both sides of it. Powering the block at the
+    /// origin, for example with a button, starts the song one redstone tick later.
+    ///
+    /// Only two note blocks fit next to each step, so at most two notes play on the same tick:
+    /// the first two of that tick, in the order they're given. The others are left out of the
+    /// song and counted in the returned number, which is 0 when every note got placed
+    ///
+    /// origin - coords of the block that starts the song, the line goes towards +x
+    ///
+    /// notes - the notes to play, in any order
+    ///
+    /// options - blocks of the delay line, see SongOptions
+    pub fn build_song(
+        &mut self,
+        origin: Coords,
+        notes: &[Note],
+        options: &SongOptions,
+    ) -> Result<usize, String> {
+        let mut notes = notes.to_vec();
+        notes.sort_by_key(|note| note.tick);
+
+        let (mut x, y, z) = origin;
+        let too_long = || "The song is too long for a schematic".to_string();
+        if y == i16::MIN || z == i16::MIN || z == i16::MAX {
+            return Err("The note blocks of the song don't fit next to the origin".to_string());
+        }
+        let mut left_out = 0;
+        let mut last_tick = notes.first().map_or(0, |note| note.tick as i64 - 1);
+
+        self.set_block(origin, &options.step_block);
+
+        for chord in notes.chunk_by(|a, b| a.tick == b.tick) {
+            // Repeaters adding up to the delay since the previous step, 4 ticks at most each
+            let mut delay = chord[0].tick as i64 - last_tick;
+            while delay > 0 {
+                let repeater_delay = delay.min(4);
+                x = x.checked_add(1).ok_or_else(too_long)?;
+                self.set_block((x, y - 1, z), &options.support_block);
+                self.set_block(
+                    (x, y, z),
+                    &format!("minecraft:repeater[delay={},facing=west]", repeater_delay),
+                );
+                delay -= repeater_delay;
+            }
+            last_tick = chord[0].tick as i64;
+
+            x = x.checked_add(1).ok_or_else(too_long)?;
+            self.set_block((x, y, z), &options.step_block);
+            for (note, side) in chord.iter().zip([-1, 1]) {
+                self.set_block((x, y - 1, z + side), note.instrument.base_block());
+                self.set_block(
+                    (x, y, z + side),
+                    &format!(
+                        "minecraft:note_block[instrument={},note={}]",
+                        note.instrument.name(),
+                        note.instrument.note(note.key)
+                    ),
+                );
+            }
+            left_out += chord.len().saturating_sub(2);
+        }
+
+        Ok(left_out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use midly::num::{u15, u24, u28, u4, u7};
+    use midly::{Format, Header, TrackEvent};
+
+    #[test]
+    fn parses_note_names() {
+        assert_eq!(parse_key("C4"), Ok(60));
+        assert_eq!(parse_key(" a4 "), Ok(69));
+        assert_eq!(parse_key("F#3"), Ok(54));
+        assert_eq!(parse_key("Bb5"), Ok(82));
+        assert_eq!(parse_key("Cb4"), Ok(59));
+        assert_eq!(parse_key("C-1"), Ok(0));
+        assert_eq!(parse_key("G9"), Ok(127));
+    }
+
+    #[test]
+    fn rejects_invalid_note_names() {
+        for name in [
+            "", "C", "H4", "C##4", "Cx4", "é4", "#4", "C4.5", "G#9", "C-2",
+        ] {
+            assert!(parse_key(name).is_err(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn picks_instruments_and_notes_for_keys() {
+        assert_eq!(Instrument::for_key(54), Some(Instrument::Harp));
+        assert_eq!(Instrument::for_key(30), Some(Instrument::Bass));
+        assert_eq!(Instrument::for_key(102), Some(Instrument::Bell));
+        assert_eq!(Instrument::for_key(29), None);
+        assert_eq!(Note::new(0, 120).instrument, Instrument::Harp);
+
+        assert_eq!(Instrument::Harp.note(54), 0);
+        assert_eq!(Instrument::Harp.note(78), 24);
+        // Keys outside of the two octaves move by whole octaves
+        assert_eq!(Instrument::Harp.note(42), 0);
+        assert_eq!(Instrument::Harp.note(91), 13);
+        assert!(!Instrument::Snare.is_pitched());
+    }
+
+    #[test]
+    fn builds_the_delay_line_and_note_blocks() {
+        let mut schematic = MCSchematic::new();
+        let notes = [
+            Note::new(6, 60),
+            Note::new(0, 54),
+            Note::new(0, 55),
+            Note::new(0, 56),
+        ];
+        let left_out = schematic.build_song((0, 1, 0), &notes, &SongOptions::default());
+        assert_eq!(left_out, Ok(1));
+
+        // The first chord plays one tick after the start, the second 6 ticks later
+        assert_eq!(
+            schematic.get_block((1, 1, 0)),
+            "minecraft:repeater[delay=1,facing=west]"
+        );
+        assert_eq!(schematic.get_block((2, 1, 0)), "minecraft:iron_block");
+        assert_eq!(
+            schematic.get_block((2, 1, -1)),
+            "minecraft:note_block[instrument=harp,note=0]"
+        );
+        assert_eq!(
+            schematic.get_block((2, 1, 1)),
+            "minecraft:note_block[instrument=harp,note=1]"
+        );
+        assert_eq!(schematic.get_block((2, 0, 1)), "minecraft:dirt");
+        assert_eq!(
+            schematic.get_block((3, 1, 0)),
+            "minecraft:repeater[delay=4,facing=west]"
+        );
+        assert_eq!(
+            schematic.get_block((4, 1, 0)),
+            "minecraft:repeater[delay=2,facing=west]"
+        );
+        assert_eq!(
+            schematic.get_block((5, 1, -1)),
+            "minecraft:note_block[instrument=harp,note=6]"
+        );
+        assert_eq!(schematic.get_block((5, 1, 1)), "minecraft:air");
+    }
+
+    #[test]
+    fn reads_midi_files() {
+        let event = |delta: u32, kind| TrackEvent {
+            delta: u28::new(delta),
+            kind,
+        };
+        let note_on = |channel: u8, key: u8| TrackEventKind::Midi {
+            channel: u4::new(channel),
+            message: MidiMessage::NoteOn {
+                key: u7::new(key),
+                vel: u7::new(100),
+            },
+        };
+        // 96 ticks per beat, at 0.5 and then 0.25 seconds per beat
+        let track = vec![
+            event(0, note_on(0, 60)),
+            event(
+                96,
+                TrackEventKind::Meta(MetaMessage::Tempo(u24::new(250_000))),
+            ),
+            event(0, note_on(9, 38)),
+            event(96, note_on(0, 30)),
+            event(0, TrackEventKind::Meta(MetaMessage::EndOfTrack)),
+        ];
+        let smf = Smf {
+            header: Header::new(Format::SingleTrack, Timing::Metrical(u15::new(96))),
+            tracks: vec![track],
+        };
+        let path = std::env::temp_dir().join(format!("nbt_{}_song.mid", std::process::id()));
+        let path = path.to_string_lossy().into_owned();
+        smf.save(&path).unwrap();
+        let notes = notes_from_midi(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            notes,
+            Ok(vec![
+                Note::new(0, 60),
+                Note {
+                    tick: 5,
+                    key: 38,
+                    instrument: Instrument::Snare,
+                },
+                Note::new(8, 30),
+            ])
+        );
+        assert!(notes_from_midi(&path).is_err());
+    }
+}
@@ -0,0 +1,294 @@
+use std::borrow::Cow;
+
+use crate::block_state::namespaced;
+use crate::mcschematic::{Axis, Coords, AIR};
+
+type BlockFunction<'a> = Box<dyn Fn(Coords) -> String + 'a>;
+
+/// Describes which block gets placed at every position of a fill, shape or replace operation
+pub enum Pattern<'a> {
+    /// The same block everywhere
+    Block(String),
+    /// A weighted random mix of blocks. The choice only depends on the seed and the coords,
+    /// so the same seed always produces the same result
+    Random {
+        blocks: Vec<(String, u32)>,
+        seed: u64,
+    },
+    /// Two blocks alternating in every direction
+    Checkerboard(String, String),
+    /// Bands of blocks going from the first to the last one in the list,
+    /// spread evenly between `start` and `end` along the given axis
+    Gradient {
+        axis: Axis,
+        start: i16,
+        end: i16,
+        blocks: Vec<String>,
+    },
+    /// Any function of the coords
+    Function(BlockFunction<'a>),
+}
+
+impl<'a> Pattern<'a> {
+    /// Parses a WorldEdit-like pattern, such as "minecraft:stone" or "50%stone,50%andesite".
+    /// Entries without a percentage get a weight of 1, ids without a namespace get "minecraft:"
+    /// and random patterns use a seed of 0, see Pattern::with_seed.
+    /// Entries made only of properties, such as "[delay=1]", change those properties
+    /// of the block that is already there
+    pub fn parse(input: &str) -> Result<Pattern<'a>, String> {
+        let entries = split_entries(input);
+
+        if entries.len() == 1 && !entries[0].contains('%') {
+            return Ok(Pattern::Block(block_entry(entries[0])?));
+        }
+
+        let mut blocks = vec![];
+        for entry in entries {
+            let (weight, block) = match entry.split_once('%') {
+                Some((weight, block)) => match weight.trim().parse::<u32>() {
+                    Ok(weight) => (weight, block),
+                    Err(_) => return Err(format!("Invalid weight in pattern entry \"{}\"", entry)),
+                },
+                None => (1, entry),
+            };
+            blocks.push((block_entry(block)?, weight));
+        }
+
+        if blocks.iter().all(|&(_, weight)| weight == 0) {
+            return Err(format!(
+                "Pattern \"{}\" has no entry with a positive weight",
+                input
+            ));
+        }
+
+        Ok(Pattern::Random { blocks, seed: 0 })
+    }
+
+    /// Returns the same pattern with a different seed, if it's a random one
+    pub fn with_seed(self, seed: u64) -> Pattern<'a> {
+        match self {
+            Pattern::Random { blocks, .. } => Pattern::Random { blocks, seed },
+            other => other,
+        }
+    }
+
+    /// Returns the block this pattern places at the given coords
+    pub fn block_at(&self, coords: Coords) -> Cow<'_, str> {
+        match self {
+            Pattern::Block(block) => Cow::Borrowed(block),
+            Pattern::Random { blocks, seed } => {
+                let total: u64 = blocks.iter().map(|&(_, weight)| weight as u64).sum();
+                if total == 0 {
+                    return Cow::Borrowed(AIR);
+                }
+
+                let mut roll = hash_coords(*seed, coords) % total;
+
+                for (block, weight) in blocks {
+                    if roll < *weight as u64 {
+                        return Cow::Borrowed(block);
+                    }
+                    roll -= *weight as u64;
+                }
+                unreachable!("the roll is always lower than the total weight")
+            }
+            Pattern::Checkerboard(even, odd) => {
+                let (x, y, z) = coords;
+                match (x as i32 + y as i32 + z as i32).rem_euclid(2) {
+                    0 => Cow::Borrowed(even),
+                    _ => Cow::Borrowed(odd),
+                }
+            }
+            Pattern::Gradient {
+                axis,
+                start,
+                end,
+                blocks,
+            } => {
+                if blocks.is_empty() {
+                    return Cow::Borrowed(AIR);
+                }
+
+                let position = axis.component(coords) as f32;
+                let progress = match end == start {
+                    true => 0.0,
+                    false => (position - *start as f32) / (*end as f32 - *start as f32),
+                };
+                let index = (progress.clamp(0.0, 1.0) * (blocks.len() - 1) as f32).round();
+
+                Cow::Borrowed(&blocks[index as usize])
+            }
+            Pattern::Function(function) => Cow::Owned(function(coords)),
+        }
+    }
+}
+
+impl From<&str> for Pattern<'_> {
+    fn from(block: &str) -> Self {
+        Pattern::Block(block.to_string())
+    }
+}
+
+/// Splits a comma separated list of pattern entries, leaving commas between
+/// block state properties such as "repeater[delay=1,facing=north]" alone
+fn split_entries(input: &str) -> Vec<&str> {
+    let mut entries = vec![];
+    let mut depth = 0;
+    let mut entry_start = 0;
+
+    for (i, c) in input.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            ',' if depth == 0 => {
+                entries.push(input[entry_start..i].trim());
+                entry_start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    entries.push(input[entry_start..].trim());
+
+    entries
+}
+
+/// Reads the block of a pattern entry, prepending the "minecraft:" namespace if needed.
+/// Entries made of properties only are kept as they are
+fn block_entry(block: &str) -> Result<String, String> {
+    let block = block.trim();
+
+    match block.split('[').next().unwrap_or(block) {
+        "" if block.starts_with('[') => Ok(block.to_string()),
+        "" => Err(format!("Missing block id in pattern entry \"{}\"", block)),
+        _ => Ok(namespaced(block)),
+    }
+}
+
+/// Mixes the seed and coords into a pseudo random number (splitmix64),
+/// which doesn't depend on the order the positions are visited in
+fn hash_coords(seed: u64, (x, y, z): Coords) -> u64 {
+    let packed = (x as u16 as u64) | (y as u16 as u64) << 16 | (z as u16 as u64) << 32;
+
+    let mut hash = seed ^ packed.wrapping_mul(0x9E3779B97F4A7C15);
+    hash = (hash ^ (hash >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    hash = (hash ^ (hash >> 27)).wrapping_mul(0x94D049BB133111EB);
+    hash ^ (hash >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blocks_of(pattern: &Pattern) -> Vec<(String, u32)> {
+        match pattern {
+            Pattern::Random { blocks, .. } => blocks.clone(),
+            _ => panic!("not a random pattern"),
+        }
+    }
+
+    #[test]
+    fn parses_single_blocks() {
+        let pattern = Pattern::parse("stone").unwrap();
+        assert_eq!(pattern.block_at((0, 0, 0)), "minecraft:stone");
+
+        let pattern = Pattern::parse(" create:cogwheel[axis=x] ").unwrap();
+        assert_eq!(pattern.block_at((0, 0, 0)), "create:cogwheel[axis=x]");
+
+        let pattern = Pattern::parse("[delay=1,facing=north]").unwrap();
+        assert_eq!(pattern.block_at((0, 0, 0)), "[delay=1,facing=north]");
+    }
+
+    #[test]
+    fn parses_weighted_entries() {
+        let pattern =
+            Pattern::parse("50%stone,repeater[delay=2,facing=east], 25% andesite").unwrap();
+        assert_eq!(
+            blocks_of(&pattern),
+            vec![
+                ("minecraft:stone".to_string(), 50),
+                ("minecraft:repeater[delay=2,facing=east]".to_string(), 1),
+                ("minecraft:andesite".to_string(), 25),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_patterns() {
+        assert!(Pattern::parse("x%stone,dirt").is_err());
+        assert!(Pattern::parse("0%stone,0%dirt").is_err());
+        assert!(Pattern::parse("stone,").is_err());
+        assert!(Pattern::parse("").is_err());
+    }
+
+    #[test]
+    fn random_follows_weights_and_seed() {
+        let pattern = Pattern::parse("3%stone,1%dirt").unwrap().with_seed(42);
+        let positions: Vec<Coords> = (0..40)
+            .flat_map(|x| (0..40).map(move |z| (x, 0, z)))
+            .collect();
+
+        let stone = positions
+            .iter()
+            .filter(|&&coords| pattern.block_at(coords) == "minecraft:stone")
+            .count();
+        assert!((1100..1300).contains(&stone), "{} of 1600", stone);
+
+        let same = Pattern::parse("3%stone,1%dirt").unwrap().with_seed(42);
+        let other = Pattern::parse("3%stone,1%dirt").unwrap().with_seed(43);
+        assert!(positions
+            .iter()
+            .all(|&coords| pattern.block_at(coords) == same.block_at(coords)));
+        assert!(positions
+            .iter()
+            .any(|&coords| pattern.block_at(coords) != other.block_at(coords)));
+    }
+
+    #[test]
+    fn zero_weights_are_never_chosen() {
+        let pattern = Pattern::parse("0%stone,1%dirt").unwrap();
+        for x in -20..20 {
+            assert_eq!(pattern.block_at((x, x, -x)), "minecraft:dirt");
+        }
+    }
+
+    #[test]
+    fn checkerboard_alternates() {
+        let pattern = Pattern::Checkerboard("a".to_string(), "b".to_string());
+        assert_eq!(pattern.block_at((0, 0, 0)), "a");
+        assert_eq!(pattern.block_at((1, 0, 0)), "b");
+        assert_eq!(pattern.block_at((1, 1, 0)), "a");
+        assert_eq!(pattern.block_at((-1, 0, 0)), "b");
+        assert_eq!(pattern.block_at((i16::MAX, i16::MAX, 0)), "a");
+    }
+
+    #[test]
+    fn gradient_spreads_bands() {
+        let pattern = Pattern::Gradient {
+            axis: Axis::Y,
+            start: 0,
+            end: 4,
+            blocks: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+        };
+        let column: Vec<String> = (-2..=6)
+            .map(|y| pattern.block_at((0, y, 0)).into_owned())
+            .collect();
+        // The blocks are stops at 0, 2 and 4, each position taking the closest one
+        // and ties going to the later one
+        assert_eq!(column, ["a", "a", "a", "b", "b", "c", "c", "c", "c"]);
+
+        let reversed = Pattern::Gradient {
+            axis: Axis::X,
+            start: 4,
+            end: 0,
+            blocks: vec!["a".to_string(), "b".to_string()],
+        };
+        assert_eq!(reversed.block_at((4, 0, 0)), "a");
+        assert_eq!(reversed.block_at((0, 0, 0)), "b");
+    }
+
+    #[test]
+    fn function_gets_the_coords() {
+        let pattern = Pattern::Function(Box::new(|(x, y, z)| format!("{}{}{}", x, y, z)));
+        assert_eq!(pattern.block_at((1, -2, 3)), "1-23");
+    }
+}
@@ -0,0 +1,254 @@
+use crate::block_registry::block_info;
+use crate::block_state::{BlockMatcher, BlockState};
+use crate::versions::JE_1_13;
+
+/// A change to a block's id or property values made by a Minecraft version
+struct Rename {
+    /// DataVersion of the first version using the new names
+    data_version: i32,
+    /// Blocks the rename applies to, before (old) and after (new) it happened.
+    /// When both are the same, only the property values change
+    old_id: &'static str,
+    new_id: &'static str,
+    /// Property values that changed: (property, old value, new value).
+    /// The first entry wins when several map to the same value
+    values: &'static [(&'static str, &'static str, &'static str)],
+}
+
+const WALL_SIDES: &[(&str, &str, &str)] = &[
+    ("north", "true", "low"),
+    ("north", "true", "tall"),
+    ("north", "false", "none"),
+    ("east", "true", "low"),
+    ("east", "true", "tall"),
+    ("east", "false", "none"),
+    ("south", "true", "low"),
+    ("south", "true", "tall"),
+    ("south", "false", "none"),
+    ("west", "true", "low"),
+    ("west", "true", "tall"),
+    ("west", "false", "none"),
+];
+
+/// Block renames since the flattening (1.13), oldest first.
+/// Ids may contain "*" wildcards when only the property values change
+const RENAMES: &[Rename] = &[
+    // 18w43a
+    Rename {
+        data_version: 1901,
+        old_id: "minecraft:sign",
+        new_id: "minecraft:oak_sign",
+        values: &[],
+    },
+    Rename {
+        data_version: 1901,
+        old_id: "minecraft:wall_sign",
+        new_id: "minecraft:oak_wall_sign",
+        values: &[],
+    },
+    Rename {
+        data_version: 1901,
+        old_id: "minecraft:stone_slab",
+        new_id: "minecraft:smooth_stone_slab",
+        values: &[],
+    },
+    // 20w06a
+    Rename {
+        data_version: 2504,
+        old_id: "minecraft:*_wall",
+        new_id: "minecraft:*_wall",
+        values: WALL_SIDES,
+    },
+    // 20w45a
+    Rename {
+        data_version: 2681,
+        old_id: "minecraft:grass_path",
+        new_id: "minecraft:dirt_path",
+        values: &[],
+    },
+    // 23w40a
+    Rename {
+        data_version: 3679,
+        old_id: "minecraft:grass",
+        new_id: "minecraft:short_grass",
+        values: &[],
+    },
+];
+
+/// Turns a block written by a version with the given DataVersion into its current name.
+/// Versions before the flattening (1.13) used numeric ids and can't be converted
+pub fn upgrade(block: &str, data_version: i32) -> Result<String, String> {
+    check_flattened(data_version)?;
+    let mut state = BlockState::parse(block);
+
+    for rename in RENAMES
+        .iter()
+        .filter(|rename| rename.data_version > data_version)
+    {
+        apply(
+            &mut state,
+            rename.old_id,
+            rename.new_id,
+            rename.values,
+            false,
+        );
+    }
+
+    Ok(state.to_string())
+}
+
+/// Turns a block with its current name into the name used by the version with the given
+/// DataVersion. Fails for versions before the flattening (1.13) and for blocks the block
+/// registry knows were only added after the version, such as "minecraft:spruce_sign"
+/// before 1.14. Blocks the registry doesn't know are only renamed
+pub fn downgrade(block: &str, data_version: i32) -> Result<String, String> {
+    check_flattened(data_version)?;
+    let mut state = BlockState::parse(block);
+
+    // The current name may belong to a block that didn't exist yet, even if an older block
+    // had the same name, like "minecraft:stone_slab" being the smooth stone slab before 1.14
+    if let Some(info) = block_info(&state.id).filter(|info| info.since > data_version) {
+        return Err(format!(
+            "{} doesn't exist before DataVersion {}",
+            info.id, info.since
+        ));
+    }
+
+    for rename in RENAMES
+        .iter()
+        .rev()
+        .filter(|rename| rename.data_version > data_version)
+    {
+        apply(
+            &mut state,
+            rename.new_id,
+            rename.old_id,
+            rename.values,
+            true,
+        );
+    }
+
+    Ok(state.to_string())
+}
+
+/// Fails for DataVersions before the flattening, whose blocks can't be named by id
+fn check_flattened(data_version: i32) -> Result<(), String> {
+    match data_version < JE_1_13.data_version() {
+        true => Err(format!(
+            "DataVersion {} is older than the flattening (1.13), its blocks have numeric ids",
+            data_version
+        )),
+        false => Ok(()),
+    }
+}
+
+/// Changes the id and property values of a block state matching the `from` id,
+/// going from old to new values or the other way around when reversed
+fn apply(
+    state: &mut BlockState,
+    from: &str,
+    to: &str,
+    values: &[(&str, &str, &str)],
+    reversed: bool,
+) {
+    if !BlockMatcher::parse(from).matches(&state.id) {
+        return;
+    }
+    if from != to {
+        state.id = to.to_string();
+    }
+
+    for (key, value) in state.properties.iter_mut() {
+        let renamed = values.iter().find_map(|&(property, old, new)| {
+            let (before, after) = match reversed {
+                true => (new, old),
+                false => (old, new),
+            };
+            (property == key && before == value).then_some(after)
+        });
+        if let Some(after) = renamed {
+            *value = after.to_string();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::versions::{JE_1_12_2, JE_1_13_2, JE_1_15_2, JE_1_16, JE_1_20_1};
+
+    #[test]
+    fn upgrades_renamed_blocks() {
+        let data_version = JE_1_13_2.data_version();
+        assert_eq!(
+            upgrade("minecraft:sign[rotation=4]", data_version).unwrap(),
+            "minecraft:oak_sign[rotation=4]"
+        );
+        assert_eq!(
+            upgrade("minecraft:stone_slab[type=top]", data_version).unwrap(),
+            "minecraft:smooth_stone_slab[type=top]"
+        );
+        assert_eq!(
+            upgrade("minecraft:grass_path", data_version).unwrap(),
+            "minecraft:dirt_path"
+        );
+        assert_eq!(
+            upgrade("minecraft:grass", JE_1_20_1.data_version()).unwrap(),
+            "minecraft:short_grass"
+        );
+    }
+
+    #[test]
+    fn upgrades_and_downgrades_wall_sides() {
+        let old = "minecraft:cobblestone_wall[east=true,west=false,up=true]";
+        let new = "minecraft:cobblestone_wall[east=low,west=none,up=true]";
+
+        assert_eq!(upgrade(old, JE_1_15_2.data_version()).unwrap(), new);
+        assert_eq!(downgrade(new, JE_1_15_2.data_version()).unwrap(), old);
+        assert_eq!(
+            downgrade(
+                "minecraft:cobblestone_wall[east=tall]",
+                JE_1_15_2.data_version()
+            )
+            .unwrap(),
+            "minecraft:cobblestone_wall[east=true]"
+        );
+        assert_eq!(downgrade(new, JE_1_16.data_version()).unwrap(), new);
+    }
+
+    #[test]
+    fn downgrades_to_older_names() {
+        let data_version = JE_1_13_2.data_version();
+        assert_eq!(
+            downgrade("minecraft:oak_wall_sign[facing=east]", data_version).unwrap(),
+            "minecraft:wall_sign[facing=east]"
+        );
+        assert_eq!(
+            downgrade("minecraft:smooth_stone_slab[type=double]", data_version).unwrap(),
+            "minecraft:stone_slab[type=double]"
+        );
+        assert_eq!(
+            downgrade("minecraft:short_grass", data_version).unwrap(),
+            "minecraft:grass"
+        );
+    }
+
+    #[test]
+    fn rejects_blocks_added_after_the_version() {
+        let data_version = JE_1_13_2.data_version();
+        assert!(downgrade("minecraft:spruce_sign", data_version).is_err());
+        // The current stone slab is a different block than the one called so before 1.14
+        assert!(downgrade("minecraft:stone_slab", data_version).is_err());
+        assert_eq!(
+            downgrade("minecraft:stone_slab", JE_1_15_2.data_version()).unwrap(),
+            "minecraft:stone_slab"
+        );
+    }
+
+    #[test]
+    fn rejects_versions_before_the_flattening() {
+        let data_version = JE_1_12_2.data_version();
+        assert!(upgrade("minecraft:stone", data_version).is_err());
+        assert!(downgrade("minecraft:stone", data_version).is_err());
+    }
+}
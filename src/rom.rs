@@ -0,0 +1,283 @@
+use quartz_nbt::compound;
+
+use crate::containers::items_for_signal_strength;
+use crate::mcschematic::{step_coords, Coords, MCSchematic};
+
+type CellFunction<'a> = Box<dyn Fn(&mut MCSchematic, Coords, u8) + 'a>;
+
+/// How the bits of a word are split into the digits stored by the ROM cells
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RomEncoding {
+    /// One cell per bit, holding 0 or 1
+    #[default]
+    Binary,
+    /// One cell per hex digit, holding a value from 0 to 15,
+    /// usually read as the signal strength of a comparator
+    Hex,
+}
+
+impl RomEncoding {
+    /// Returns the number of bits stored by one cell
+    pub fn bits_per_digit(self) -> u8 {
+        match self {
+            RomEncoding::Binary => 1,
+            RomEncoding::Hex => 4,
+        }
+    }
+}
+
+/// What gets placed to store a single digit
+pub enum RomCell<'a> {
+    /// One block for ones and another one for zeros, such as a redstone torch
+    /// or air, or a solid block or glass. Only works with binary digits
+    Blocks { one: String, zero: String },
+    /// A container filled so a comparator reads the digit as its signal strength,
+    /// such as a barrel with 27 slots of redstone
+    Container {
+        block: String,
+        slots: u8,
+        item: String,
+        stack_size: u8,
+    },
+    /// Any function placing the cell for a digit at the given coords
+    Function(CellFunction<'a>),
+}
+
+impl Default for RomCell<'_> {
+    fn default() -> Self {
+        RomCell::Blocks {
+            one: "minecraft:redstone_torch".to_string(),
+            zero: "minecraft:air".to_string(),
+        }
+    }
+}
+
+/// Options for MCSchematic::build_rom
+pub struct RomOptions<'a> {
+    /// How the words are split into digits, see RomEncoding
+    pub encoding: RomEncoding,
+    /// Number of bits of each word, such as 8 for bytes
+    pub word_bits: u8,
+    /// What stores a single digit, see RomCell
+    pub cell: RomCell<'a>,
+    /// Optional schematic copied for every digit before the digit itself is placed,
+    /// such as the comparator and dust reading a barrel. Only its placed blocks are copied
+    pub base: Option<&'a MCSchematic>,
+    /// Position of the digit inside the base, relative to its lowest corner
+    pub digit_offset: Coords,
+    /// Offset between two consecutive digits of a word, least significant digit first
+    pub column_stride: Coords,
+    /// Offset between two consecutive words
+    pub row_stride: Coords,
+}
+
+impl Default for RomOptions<'_> {
+    fn default() -> Self {
+        RomOptions {
+            encoding: RomEncoding::Binary,
+            word_bits: 8,
+            cell: RomCell::default(),
+            base: None,
+            digit_offset: (0, 0, 0),
+            column_stride: (2, 0, 0),
+            row_stride: (0, 0, 2),
+        }
+    }
+}
+
+impl MCSchematic {
+    /// Builds a ROM storing the given words, one word per row and one digit per column
+    ///
+    /// origin - coords of the least significant digit of the first word
+    /// (or of the lowest corner of its base)
+    ///
+    /// data - the words to store, such as the bytes of a program
+    ///
+    /// options - encoding, cell template and strides, see RomOptions
+    pub fn build_rom(
+        &mut self,
+        origin: Coords,
+        data: &[u32],
+        options: &RomOptions,
+    ) -> Result<(), String> {
+        let bits = options.encoding.bits_per_digit();
+        if options.word_bits == 0 || options.word_bits > 32 {
+            return Err(format!("Words can't have {} bits", options.word_bits));
+        }
+        if let (RomCell::Blocks { .. }, RomEncoding::Hex) = (&options.cell, options.encoding) {
+            return Err("Block cells can only store binary digits".to_string());
+        }
+        if let Some(&word) = data
+            .iter()
+            .find(|&&word| options.word_bits < 32 && word >> options.word_bits != 0)
+        {
+            return Err(format!(
+                "{:#x} doesn't fit in {} bits",
+                word, options.word_bits
+            ));
+        }
+
+        let digits = options.word_bits.div_ceil(bits);
+        let mask = (1 << bits) - 1;
+
+        for (row, &word) in data.iter().enumerate() {
+            for column in 0..digits {
+                let digit = (word >> (column * bits) & mask) as u8;
+                let position = step(origin, options.row_stride, row)?;
+                let position = step(position, options.column_stride, column as usize)?;
+
+                if let Some(base) = options.base {
+                    self.array_with(base, position, (1, 1, 1), Default::default(), |_, _, _| {})?;
+                }
+
+                let coords = step(position, options.digit_offset, 1)?;
+                self.place_digit(coords, digit, &options.cell)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Places a single ROM cell holding the given digit
+    fn place_digit(&mut self, coords: Coords, digit: u8, cell: &RomCell) -> Result<(), String> {
+        match cell {
+            RomCell::Blocks { one, zero } => match digit {
+                0 => self.set_block(coords, zero),
+                _ => self.set_block(coords, one),
+            },
+            RomCell::Container {
+                block,
+                slots,
+                item,
+                stack_size,
+            } => {
+                let items = items_for_signal_strength(*slots, digit, item, *stack_size)?;
+                self.set_block_entity(coords, block, compound!("Items": items));
+            }
+            RomCell::Function(place) => place(self, coords, digit),
+        }
+
+        Ok(())
+    }
+}
+
+/// Moves coords by a number of strides, failing if they leave the range of coords
+fn step(from: Coords, stride: Coords, count: usize) -> Result<Coords, String> {
+    u32::try_from(count)
+        .ok()
+        .and_then(|count| step_coords(from, stride, count))
+        .ok_or_else(|| format!("The ROM doesn't fit in the range of coords at {:?}", from))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::containers::comparator_signal;
+
+    #[test]
+    fn stores_binary_words_least_significant_bit_first() {
+        let mut schematic = MCSchematic::new();
+        let options = RomOptions {
+            word_bits: 4,
+            ..Default::default()
+        };
+        schematic
+            .build_rom((0, 0, 0), &[0b0101, 0b1100], &options)
+            .unwrap();
+
+        let bits = |z: i16| -> Vec<bool> {
+            (0..4)
+                .map(|column| schematic.get_block((column * 2, 0, z)) != "minecraft:air")
+                .collect()
+        };
+        assert_eq!(bits(0), [true, false, true, false]);
+        assert_eq!(bits(2), [false, false, true, true]);
+        assert_eq!(schematic.get_block((0, 0, 0)), "minecraft:redstone_torch");
+    }
+
+    #[test]
+    fn stores_hex_digits_in_containers() {
+        let mut schematic = MCSchematic::new();
+        let options = RomOptions {
+            encoding: RomEncoding::Hex,
+            cell: RomCell::Container {
+                block: "minecraft:barrel".to_string(),
+                slots: 27,
+                item: "minecraft:redstone".to_string(),
+                stack_size: 64,
+            },
+            ..Default::default()
+        };
+        schematic
+            .build_rom((0, 0, 0), &[0xF0, 0x3A], &options)
+            .unwrap();
+
+        let digit = |coords: Coords| {
+            comparator_signal(
+                schematic.get_block(coords),
+                schematic.get_block_entity(coords),
+            )
+        };
+        assert_eq!(digit((0, 0, 0)), Some(0x0));
+        assert_eq!(digit((2, 0, 0)), Some(0xF));
+        assert_eq!(digit((0, 0, 2)), Some(0xA));
+        assert_eq!(digit((2, 0, 2)), Some(0x3));
+    }
+
+    #[test]
+    fn copies_the_base_and_calls_cell_functions() {
+        let mut base = MCSchematic::new();
+        base.set_block((0, 0, 0), "minecraft:stone");
+        base.set_block((0, 1, 0), "minecraft:comparator");
+
+        let mut schematic = MCSchematic::new();
+        let options = RomOptions {
+            word_bits: 2,
+            base: Some(&base),
+            digit_offset: (0, 2, 0),
+            cell: RomCell::Function(Box::new(|schematic, coords, digit| {
+                schematic.set_block(
+                    coords,
+                    ["minecraft:glass", "minecraft:dirt"][digit as usize],
+                );
+            })),
+            ..Default::default()
+        };
+        schematic.build_rom((0, 0, 0), &[0b10], &options).unwrap();
+
+        assert_eq!(schematic.get_block((2, 1, 0)), "minecraft:comparator");
+        assert_eq!(schematic.get_block((0, 2, 0)), "minecraft:glass");
+        assert_eq!(schematic.get_block((2, 2, 0)), "minecraft:dirt");
+    }
+
+    #[test]
+    fn rejects_words_and_cells_that_dont_fit() {
+        let mut schematic = MCSchematic::new();
+        let options = RomOptions {
+            word_bits: 4,
+            ..Default::default()
+        };
+        assert!(schematic.build_rom((0, 0, 0), &[0x10], &options).is_err());
+
+        let options = RomOptions {
+            encoding: RomEncoding::Hex,
+            ..Default::default()
+        };
+        assert!(schematic.build_rom((0, 0, 0), &[1], &options).is_err());
+
+        let options = RomOptions {
+            encoding: RomEncoding::Hex,
+            cell: RomCell::Container {
+                block: "minecraft:hopper".to_string(),
+                slots: 5,
+                item: "minecraft:diamond_sword".to_string(),
+                stack_size: 1,
+            },
+            ..Default::default()
+        };
+        assert!(schematic.build_rom((0, 0, 0), &[0x01], &options).is_err());
+        assert!(schematic
+            .build_rom((i16::MAX, 0, 0), &[0], &RomOptions::default())
+            .is_err());
+    }
+}
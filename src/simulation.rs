@@ -0,0 +1,901 @@
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use crate::block_state::BlockState;
+use crate::mcschematic::{Coords, MCSchematic};
+use crate::wiring::{is_solid, is_wire, wire_sides};
+
+/// Offsets of the six neighbours of a block
+const NEIGHBOURS: [Coords; 6] = [
+    (0, -1, 0),
+    (0, 1, 0),
+    (0, 0, -1),
+    (1, 0, 0),
+    (0, 0, 1),
+    (-1, 0, 0),
+];
+
+/// Torches turned off this many times within BURNOUT_TICKS burn out
+const BURNOUT_TOGGLES: usize = 8;
+/// Redstone ticks a torch remembers turning off for
+const BURNOUT_TICKS: u64 = 30;
+/// Redstone ticks burnt out torches stay off for
+const BURNOUT_COOLDOWN: u64 = 80;
+
+/// What a simulated block does. Input and output cells are the neighbours
+/// a component reads from and powers
+#[derive(Clone, Debug)]
+enum Kind {
+    /// Redstone wire, with the cells it points into and the wire it connects to
+    Wire {
+        targets: Vec<Coords>,
+        links: Vec<Coords>,
+    },
+    /// Standing or wall torch, with the block it's attached to
+    Torch {
+        attached: Coords,
+    },
+    /// Repeater with its delay in redstone ticks
+    Repeater {
+        input: Coords,
+        output: Coords,
+        delay: u64,
+    },
+    Comparator {
+        input: Coords,
+        output: Coords,
+        subtract: bool,
+    },
+    Lever {
+        attached: Coords,
+    },
+    RedstoneBlock,
+    Lamp,
+    /// Block a comparator reads, such as a container, see MCSchematic::comparator_signal.
+    /// The simulation doesn't move items, so its signal strength never changes
+    Container,
+}
+
+#[derive(Clone, Debug)]
+struct Component {
+    kind: Kind,
+    /// Signal strength of the component: the power level of wire, the output of torches,
+    /// repeaters, comparators and levers, 15 for lit lamps and the fullness of containers
+    signal: u8,
+    /// Signal strength the component switches to, and the tick it switches at
+    pending: Option<(u8, u64)>,
+}
+
+/// A simplified tick-based redstone simulation of the blocks of a schematic.
+/// Time goes in redstone ticks (0.1 seconds): torches and comparators switch one tick
+/// after their input changes, repeaters after their delay, and wire, solid blocks and
+/// lamps follow their power right away.
+///
+/// Covered are redstone wire, torches (including burning out), repeaters (including
+/// locking), comparators (including reading containers, also through a solid block),
+/// levers, redstone blocks and lamps. Every other block is either a solid block that redstone powers, or air.
+/// Quasi-connectivity, pistons, observers and update order aren't simulated
+pub struct Simulation {
+    components: HashMap<Coords, Component>,
+    /// Blocks redstone can power, see wiring::is_solid
+    solid_blocks: HashSet<Coords>,
+    /// Power of solid blocks from everything but wire, the only power wire picks up from them
+    strong_power: HashMap<Coords, u8>,
+    /// Power of solid blocks as read by torches, repeaters, comparators and lamps
+    power: HashMap<Coords, u8>,
+    /// Ticks at which torches turned off lately
+    torch_toggles: HashMap<Coords, Vec<u64>>,
+    /// Ticks until which burnt out torches stay off
+    burnt_out: HashMap<Coords, u64>,
+    tick: u64,
+}
+
+impl Simulation {
+    /// Sets up a simulation of the blocks of a schematic. Torches, repeaters, comparators
+    /// and levers start with the state stored in their block states, which doesn't have
+    /// to be consistent; see Simulation::run_until_stable
+    pub fn new(schematic: &MCSchematic) -> Simulation {
+        let mut components = HashMap::new();
+        let mut solid_blocks = HashSet::new();
+
+        for (coords, block) in schematic.blocks() {
+            let state = BlockState::parse(block);
+            let name = state.id.trim_start_matches("minecraft:");
+            let facing = state.property("facing").and_then(direction_offset);
+            let powered = state.property("powered") == Some("true");
+
+            let component = match (name, facing) {
+                ("redstone_wire", _) => Some(Component::new(
+                    Kind::Wire {
+                        targets: wire_targets(schematic, coords, &state),
+                        links: wire_links(schematic, coords),
+                    },
+                    0,
+                )),
+                ("redstone_torch", _) => Some(Component::new(
+                    Kind::Torch {
+                        attached: add(coords, (0, -1, 0)),
+                    },
+                    lit_signal(state.property("lit") != Some("false")),
+                )),
+                ("redstone_wall_torch", Some(facing)) => Some(Component::new(
+                    Kind::Torch {
+                        attached: sub(coords, facing),
+                    },
+                    lit_signal(state.property("lit") != Some("false")),
+                )),
+                ("repeater", Some(facing)) => Some(Component::new(
+                    Kind::Repeater {
+                        input: add(coords, facing),
+                        output: sub(coords, facing),
+                        delay: state
+                            .property("delay")
+                            .and_then(|delay| delay.parse().ok())
+                            .unwrap_or(1)
+                            .clamp(1, 4),
+                    },
+                    lit_signal(powered),
+                )),
+                ("comparator", Some(facing)) => Some(Component::new(
+                    Kind::Comparator {
+                        input: add(coords, facing),
+                        output: sub(coords, facing),
+                        subtract: state.property("mode") == Some("subtract"),
+                    },
+                    schematic
+                        .get_block_entity(coords)
+                        .and_then(|entity| entity.get::<_, i32>("OutputSignal").ok())
+                        .map_or(0, |signal| signal.clamp(0, 15) as u8),
+                )),
+                ("lever", facing) => {
+                    let attached = match (state.property("face"), facing) {
+                        (Some("floor"), _) => Some(add(coords, (0, -1, 0))),
+                        (Some("ceiling"), _) => Some(add(coords, (0, 1, 0))),
+                        (_, Some(facing)) => Some(sub(coords, facing)),
+                        _ => None,
+                    };
+                    attached.map(|attached| {
+                        Component::new(Kind::Lever { attached }, lit_signal(powered))
+                    })
+                }
+                ("redstone_block", _) => Some(Component::new(Kind::RedstoneBlock, 15)),
+                ("redstone_lamp", _) => Some(Component::new(Kind::Lamp, 0)),
+                _ => schematic
+                    .comparator_signal(coords)
+                    .map(|signal| Component::new(Kind::Container, signal)),
+            };
+
+            if let Some(component) = component {
+                components.insert(coords, component);
+            }
+            if is_solid(block) {
+                solid_blocks.insert(coords);
+            }
+        }
+
+        let mut simulation = Simulation {
+            components,
+            solid_blocks,
+            strong_power: HashMap::new(),
+            power: HashMap::new(),
+            torch_toggles: HashMap::new(),
+            burnt_out: HashMap::new(),
+            tick: 0,
+        };
+        simulation.settle();
+        simulation
+    }
+
+    /// Flips the lever at the given coords on or off. Wire, solid blocks and lamps
+    /// follow right away, everything else reacts in the next ticks
+    pub fn set_lever(&mut self, coords: Coords, on: bool) -> Result<(), String> {
+        match self.components.get_mut(&coords) {
+            Some(
+                lever @ Component {
+                    kind: Kind::Lever { .. },
+                    ..
+                },
+            ) => lever.signal = lit_signal(on),
+            _ => return Err(format!("There is no lever at {:?}", coords)),
+        }
+
+        self.settle();
+        Ok(())
+    }
+
+    /// Advances the simulation by one redstone tick
+    pub fn tick(&mut self) {
+        self.step();
+    }
+
+    /// Advances the simulation by the given number of redstone ticks
+    pub fn run(&mut self, ticks: u32) {
+        for _ in 0..ticks {
+            self.step();
+        }
+    }
+
+    /// Advances the simulation until nothing changes anymore, and returns the number
+    /// of ticks that took. Fails for circuits still changing after max_ticks, such as clocks
+    pub fn run_until_stable(&mut self, max_ticks: u32) -> Result<u32, String> {
+        for ticks in 0..max_ticks {
+            if !self.step() {
+                return Ok(ticks);
+            }
+        }
+
+        Err(format!(
+            "The circuit is still changing after {} redstone ticks",
+            max_ticks
+        ))
+    }
+
+    /// Returns the number of redstone ticks simulated so far
+    pub fn current_tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Returns the signal strength at the given coords: the power level of wire and of
+    /// solid blocks, the output of torches, repeaters, comparators and levers,
+    /// 15 for lit lamps and the fullness of containers
+    pub fn signal_strength(&self, coords: Coords) -> u8 {
+        let signal = self.components.get(&coords).map_or(0, |c| c.signal);
+        signal.max(self.power.get(&coords).copied().unwrap_or(0))
+    }
+
+    /// Returns true if the block at the given coords is powered, or lit for lamps and torches
+    pub fn is_powered(&self, coords: Coords) -> bool {
+        self.signal_strength(coords) > 0
+    }
+
+    /// Schedules the components whose input changed, then moves on to the next tick and
+    /// switches the components due in it. Returns false if nothing was scheduled,
+    /// meaning nothing changed
+    fn step(&mut self) -> bool {
+        let updates: Vec<(Coords, Option<(u8, u64)>)> = self
+            .components
+            .iter()
+            .filter_map(|(&coords, component)| {
+                self.next_signal(coords, component)
+                    .map(|update| (coords, update))
+            })
+            .collect();
+
+        let mut cooling = false;
+        for (coords, update) in updates {
+            let Some(component) = self.components.get_mut(&coords) else {
+                continue;
+            };
+            match update {
+                // Burnt out torches stay off until they cool down
+                None if matches!(component.kind, Kind::Torch { .. }) => {
+                    component.pending = None;
+                    cooling = true;
+                }
+                // Locked repeaters keep their signal
+                None => component.pending = None,
+                Some((target, _)) if component.pending.is_some_and(|(s, _)| s == target) => {}
+                Some((target, delay)) if target != component.signal => {
+                    component.pending = Some((target, self.tick + delay));
+                }
+                // Repeaters always finish a pulse they started, lengthening short pulses
+                Some(_) if matches!(component.kind, Kind::Repeater { .. }) => {}
+                Some(_) => component.pending = None,
+            }
+        }
+
+        let busy = cooling || self.components.values().any(|c| c.pending.is_some());
+        self.tick += 1;
+        for (&coords, component) in self.components.iter_mut() {
+            if let Some((signal, due)) = component.pending {
+                if due <= self.tick {
+                    if matches!(component.kind, Kind::Torch { .. }) && signal == 0 {
+                        let tick = self.tick;
+                        let toggles = self.torch_toggles.entry(coords).or_default();
+                        toggles.retain(|&toggle| toggle + BURNOUT_TICKS > tick);
+                        toggles.push(tick);
+                        if toggles.len() >= BURNOUT_TOGGLES {
+                            self.burnt_out.insert(coords, tick + BURNOUT_COOLDOWN);
+                        }
+                    }
+                    component.signal = signal;
+                    component.pending = None;
+                }
+            }
+        }
+        self.settle();
+
+        busy
+    }
+
+    /// Returns the signal a torch, repeater or comparator should switch to, with its delay.
+    /// Locked repeaters and burnt out torches get Some(None), components without a delay None
+    fn next_signal(&self, coords: Coords, component: &Component) -> Option<Option<(u8, u64)>> {
+        match component.kind {
+            Kind::Torch { attached } => {
+                let lit = self.signal_between(attached, coords) == 0;
+                if lit
+                    && self
+                        .burnt_out
+                        .get(&coords)
+                        .is_some_and(|&until| until > self.tick)
+                {
+                    return Some(None);
+                }
+                Some(Some((lit_signal(lit), 1)))
+            }
+            Kind::Repeater { input, delay, .. } => {
+                let locked = sides(coords, input)
+                    .iter()
+                    .any(|&side| self.side_signal(side, coords, true) > 0);
+                if locked {
+                    return Some(None);
+                }
+                let signal = lit_signal(self.rear_signal(coords, input, false) > 0);
+                Some(Some((signal, delay)))
+            }
+            Kind::Comparator {
+                input, subtract, ..
+            } => {
+                let rear = self.rear_signal(coords, input, true);
+                let side = sides(coords, input)
+                    .iter()
+                    .map(|&side| self.side_signal(side, coords, false))
+                    .max()
+                    .unwrap_or(0);
+                let signal = match (subtract, rear >= side) {
+                    (true, _) => rear.saturating_sub(side),
+                    (false, true) => rear,
+                    (false, false) => 0,
+                };
+                Some(Some((signal, 1)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Recomputes the power of wire, solid blocks and lamps from the components around them
+    fn settle(&mut self) {
+        // Solid blocks powered by torches, repeaters, comparators and levers
+        let mut strong_power: HashMap<Coords, u8> = HashMap::new();
+        for (&coords, component) in self.components.iter() {
+            for neighbour in neighbours(coords) {
+                let signal = component.strong_signal(coords, neighbour);
+                if signal > 0 && self.solid_blocks.contains(&neighbour) {
+                    let power = strong_power.entry(neighbour).or_default();
+                    *power = (*power).max(signal);
+                }
+            }
+        }
+
+        // Wire picks up the strong power of solid blocks and the signal of other components,
+        // then loses one signal strength per block it spreads along
+        let mut levels: HashMap<Coords, u8> = HashMap::new();
+        let mut queue = BinaryHeap::new();
+        for (&coords, component) in self.components.iter() {
+            if !matches!(component.kind, Kind::Wire { .. }) {
+                continue;
+            }
+            let level = neighbours(coords)
+                .map(|neighbour| {
+                    let strong = strong_power.get(&neighbour).copied().unwrap_or(0);
+                    match self.components.get(&neighbour) {
+                        Some(Component {
+                            kind: Kind::Wire { .. },
+                            ..
+                        })
+                        | None => strong,
+                        Some(other) => strong.max(other.signal_to(neighbour, coords)),
+                    }
+                })
+                .max()
+                .unwrap_or(0);
+            levels.insert(coords, level);
+            queue.push((level, coords));
+        }
+        while let Some((level, coords)) = queue.pop() {
+            if level < levels[&coords] {
+                continue;
+            }
+            let Some(Kind::Wire { links, .. }) = self.components.get(&coords).map(|c| &c.kind)
+            else {
+                continue;
+            };
+            for link in links {
+                let Some(link_level) = levels.get_mut(link) else {
+                    continue;
+                };
+                if level > *link_level + 1 {
+                    *link_level = level - 1;
+                    queue.push((level - 1, *link));
+                }
+            }
+        }
+
+        // Wire powers the block below it and the blocks it points into
+        let mut power = strong_power.clone();
+        for (&coords, &level) in levels.iter() {
+            let component = self
+                .components
+                .get_mut(&coords)
+                .expect("levels are of wire");
+            component.signal = level;
+            let Kind::Wire { targets, .. } = &component.kind else {
+                continue;
+            };
+            for target in targets {
+                if level > 0 && self.solid_blocks.contains(target) {
+                    let target_power = power.entry(*target).or_default();
+                    *target_power = (*target_power).max(level);
+                }
+            }
+        }
+        self.strong_power = strong_power;
+        self.power = power;
+
+        let lamps: Vec<(Coords, bool)> = self
+            .components
+            .iter()
+            .filter(|(_, component)| matches!(component.kind, Kind::Lamp))
+            .map(|(&coords, _)| {
+                let lit =
+                    neighbours(coords).any(|neighbour| self.signal_between(neighbour, coords) > 0);
+                (coords, lit)
+            })
+            .collect();
+        for (coords, lit) in lamps {
+            if let Some(lamp) = self.components.get_mut(&coords) {
+                lamp.signal = lit_signal(lit);
+            }
+        }
+    }
+
+    /// Returns the signal the block at `from` gives its neighbour at `to`,
+    /// as read by torches, repeaters, comparators and lamps
+    fn signal_between(&self, from: Coords, to: Coords) -> u8 {
+        let signal = self
+            .components
+            .get(&from)
+            .map_or(0, |component| component.signal_to(from, to));
+        signal.max(self.power.get(&from).copied().unwrap_or(0))
+    }
+
+    /// Returns the signal a repeater or comparator reads from behind. Both read the power
+    /// of wire no matter where it points, comparators also read containers
+    fn rear_signal(&self, coords: Coords, input: Coords, reads_containers: bool) -> u8 {
+        let container_signal = |at: Coords| match self.components.get(&at) {
+            Some(Component {
+                kind: Kind::Container,
+                signal,
+                ..
+            }) if reads_containers => Some(*signal),
+            _ => None,
+        };
+
+        match self.components.get(&input) {
+            Some(Component {
+                kind: Kind::Wire { .. },
+                signal,
+                ..
+            }) => return *signal,
+            _ => {
+                if let Some(signal) = container_signal(input) {
+                    return signal;
+                }
+            }
+        }
+
+        // Comparators read containers through a solid block not fully powered
+        let signal = self.signal_between(input, coords);
+        if signal < 15 && self.solid_blocks.contains(&input) {
+            let beyond = add(input, sub(input, coords));
+            if let Some(container) = container_signal(beyond) {
+                return signal.max(container);
+            }
+        }
+        signal
+    }
+
+    /// Returns the signal a repeater or comparator reads from a side. Repeaters only
+    /// get locked by repeaters and comparators, comparators also read wire and redstone blocks
+    fn side_signal(&self, side: Coords, coords: Coords, diodes_only: bool) -> u8 {
+        let Some(component) = self.components.get(&side) else {
+            return 0;
+        };
+        match component.kind {
+            Kind::Repeater { output, .. } | Kind::Comparator { output, .. } if output == coords => {
+                component.signal
+            }
+            Kind::Wire { .. } | Kind::RedstoneBlock if !diodes_only => component.signal,
+            _ => 0,
+        }
+    }
+}
+
+impl Component {
+    fn new(kind: Kind, signal: u8) -> Component {
+        Component {
+            kind,
+            signal,
+            pending: None,
+        }
+    }
+
+    /// Returns the signal the component at `from` gives its neighbour at `to`
+    fn signal_to(&self, from: Coords, to: Coords) -> u8 {
+        let gives = match &self.kind {
+            Kind::Wire { targets, .. } => targets.contains(&to),
+            // Torches don't power the block they're attached to
+            Kind::Torch { attached } => *attached != to,
+            Kind::Repeater { output, .. } | Kind::Comparator { output, .. } => *output == to,
+            Kind::Lever { .. } | Kind::RedstoneBlock => true,
+            Kind::Lamp | Kind::Container => false,
+        };
+
+        if gives && from != to {
+            self.signal
+        } else {
+            0
+        }
+    }
+
+    /// Returns the power the component at `from` gives the solid block at `to`,
+    /// strong enough for wire next to the block to pick it up
+    fn strong_signal(&self, from: Coords, to: Coords) -> u8 {
+        let gives = match &self.kind {
+            Kind::Torch { .. } => to == add(from, (0, 1, 0)),
+            Kind::Repeater { output, .. } | Kind::Comparator { output, .. } => *output == to,
+            Kind::Lever { attached } => *attached == to,
+            _ => false,
+        };
+
+        if gives {
+            self.signal
+        } else {
+            0
+        }
+    }
+}
+
+/// Returns the cells the wire at the given coords points into: the block below it and its
+/// connected sides. Wire without any connection properties gets the ones the game would give it
+fn wire_targets(schematic: &MCSchematic, coords: Coords, state: &BlockState) -> Vec<Coords> {
+    let directions = ["north", "east", "south", "west"];
+    let sides: Vec<(&str, &str)> = if directions.iter().any(|&d| state.property(d).is_some()) {
+        directions
+            .iter()
+            .map(|&direction| (direction, state.property(direction).unwrap_or("none")))
+            .collect()
+    } else {
+        wire_sides(schematic, coords)
+    };
+
+    let mut targets = vec![add(coords, (0, -1, 0))];
+    for (direction, side) in sides {
+        if side != "none" {
+            targets.extend(direction_offset(direction).map(|offset| add(coords, offset)));
+        }
+    }
+    targets
+}
+
+/// Returns the wire the wire at the given coords passes its signal to: next to it, one block
+/// up unless a solid block above cuts it, or one block down unless a solid block is in the way
+fn wire_links(schematic: &MCSchematic, coords: Coords) -> Vec<Coords> {
+    let cut_above = is_solid(schematic.get_block(add(coords, (0, 1, 0))));
+
+    let mut links = vec![];
+    for (dx, _, dz) in NEIGHBOURS.into_iter().filter(|&(_, dy, _)| dy == 0) {
+        let side = add(coords, (dx, 0, dz));
+        let candidates = [
+            (side, true),
+            (add(side, (0, 1, 0)), !cut_above),
+            (add(side, (0, -1, 0)), !is_solid(schematic.get_block(side))),
+        ];
+        for (candidate, open) in candidates {
+            if open && is_wire(schematic.get_block(candidate)) {
+                links.push(candidate);
+            }
+        }
+    }
+    links
+}
+
+/// Returns the two neighbours beside a repeater or comparator reading from `input`
+fn sides(coords: Coords, input: Coords) -> [Coords; 2] {
+    let (dx, _, dz) = sub(input, coords);
+    [add(coords, (dz, 0, dx)), sub(coords, (dz, 0, dx))]
+}
+
+fn neighbours(coords: Coords) -> impl Iterator<Item = Coords> {
+    NEIGHBOURS
+        .into_iter()
+        .map(move |offset| add(coords, offset))
+}
+
+fn direction_offset(direction: &str) -> Option<Coords> {
+    match direction {
+        "down" => Some((0, -1, 0)),
+        "up" => Some((0, 1, 0)),
+        "north" => Some((0, 0, -1)),
+        "east" => Some((1, 0, 0)),
+        "south" => Some((0, 0, 1)),
+        "west" => Some((-1, 0, 0)),
+        _ => None,
+    }
+}
+
+fn lit_signal(lit: bool) -> u8 {
+    if lit {
+        15
+    } else {
+        0
+    }
+}
+
+fn add((x, y, z): Coords, (dx, dy, dz): Coords) -> Coords {
+    (x + dx, y + dy, z + dz)
+}
+
+fn sub((x, y, z): Coords, (dx, dy, dz): Coords) -> Coords {
+    (x - dx, y - dy, z - dz)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::containers::items_for_signal_strength;
+    use quartz_nbt::compound;
+
+    const FLOOR_LEVER: &str = "minecraft:lever[face=floor,facing=north,powered=false]";
+    const LAMP: &str = "minecraft:redstone_lamp[lit=false]";
+
+    /// Places a chest a comparator reads the given signal strength from
+    fn set_chest(schematic: &mut MCSchematic, coords: Coords, signal_strength: u8) {
+        let items =
+            items_for_signal_strength(27, signal_strength, "minecraft:redstone", 64).unwrap();
+        schematic.set_block_entity(
+            coords,
+            "minecraft:chest[facing=north,type=single]",
+            compound!("Items": items),
+        );
+    }
+
+    #[test]
+    fn repeaters_switch_after_their_delay() {
+        let mut schematic = MCSchematic::new();
+        schematic.set_block((0, 0, 0), FLOOR_LEVER);
+        schematic.set_block(
+            (1, 0, 0),
+            "minecraft:repeater[delay=3,facing=west,powered=false]",
+        );
+        schematic.set_block((2, 0, 0), LAMP);
+
+        let mut simulation = Simulation::new(&schematic);
+        simulation.set_lever((0, 0, 0), true).unwrap();
+        simulation.run(2);
+        assert!(!simulation.is_powered((2, 0, 0)));
+        simulation.tick();
+        assert_eq!(simulation.current_tick(), 3);
+        assert_eq!(simulation.signal_strength((1, 0, 0)), 15);
+        assert!(simulation.is_powered((2, 0, 0)));
+
+        simulation.set_lever((0, 0, 0), false).unwrap();
+        simulation.run(2);
+        assert!(simulation.is_powered((2, 0, 0)));
+        simulation.tick();
+        assert!(!simulation.is_powered((2, 0, 0)));
+
+        assert!(simulation.set_lever((1, 0, 0), true).is_err());
+    }
+
+    #[test]
+    fn repeaters_powered_from_the_side_are_locked() {
+        let mut schematic = MCSchematic::new();
+        schematic.set_block((0, 0, 0), FLOOR_LEVER);
+        schematic.set_block(
+            (1, 0, 0),
+            "minecraft:repeater[delay=1,facing=west,powered=false]",
+        );
+        schematic.set_block((2, 0, 0), LAMP);
+        schematic.set_block(
+            (1, 0, 1),
+            "minecraft:repeater[delay=1,facing=south,powered=false]",
+        );
+        schematic.set_block((1, 0, 2), FLOOR_LEVER);
+
+        let mut simulation = Simulation::new(&schematic);
+        simulation.set_lever((1, 0, 2), true).unwrap();
+        simulation.run_until_stable(10).unwrap();
+        simulation.set_lever((0, 0, 0), true).unwrap();
+        simulation.run(10);
+        assert!(!simulation.is_powered((2, 0, 0)));
+
+        simulation.set_lever((1, 0, 2), false).unwrap();
+        simulation.run_until_stable(10).unwrap();
+        assert!(simulation.is_powered((2, 0, 0)));
+
+        // Locked while on, the repeater stays on after its input turns off
+        simulation.set_lever((1, 0, 2), true).unwrap();
+        simulation.run_until_stable(10).unwrap();
+        simulation.set_lever((0, 0, 0), false).unwrap();
+        simulation.run(10);
+        assert!(simulation.is_powered((2, 0, 0)));
+    }
+
+    #[test]
+    fn comparators_compare_and_subtract_their_side() {
+        for (mode, side_on, expected) in [
+            ("compare", false, 14),
+            ("compare", true, 14),
+            ("subtract", false, 14),
+            ("subtract", true, 1),
+        ] {
+            // The side wire loses one signal strength per block: 13 next to the comparator
+            let mut schematic = MCSchematic::new();
+            set_chest(&mut schematic, (0, 0, 0), 14);
+            schematic.set_block(
+                (1, 0, 0),
+                &format!(
+                    "minecraft:comparator[facing=west,mode={},powered=false]",
+                    mode
+                ),
+            );
+            for z in 1..=3 {
+                schematic.set_block((1, 0, z), "minecraft:redstone_wire");
+            }
+            schematic.set_block((1, 0, 4), FLOOR_LEVER);
+
+            let mut simulation = Simulation::new(&schematic);
+            simulation.set_lever((1, 0, 4), side_on).unwrap();
+            simulation.run_until_stable(10).unwrap();
+            assert_eq!(
+                simulation.signal_strength((1, 0, 1)),
+                lit_signal(side_on) * 13 / 15
+            );
+            assert_eq!(
+                simulation.signal_strength((1, 0, 0)),
+                expected,
+                "{} with the side {}",
+                mode,
+                side_on
+            );
+        }
+
+        // Compare mode turns off for a stronger side
+        let mut schematic = MCSchematic::new();
+        set_chest(&mut schematic, (0, 0, 0), 5);
+        schematic.set_block(
+            (1, 0, 0),
+            "minecraft:comparator[facing=west,mode=compare,powered=false]",
+        );
+        schematic.set_block((1, 0, 1), "minecraft:redstone_block");
+        let mut simulation = Simulation::new(&schematic);
+        simulation.run_until_stable(10).unwrap();
+        assert_eq!(simulation.signal_strength((1, 0, 0)), 0);
+    }
+
+    #[test]
+    fn comparators_read_containers_through_solid_blocks() {
+        for signal_strength in [0, 1, 7, 15] {
+            let mut schematic = MCSchematic::new();
+            set_chest(&mut schematic, (-1, 0, 0), signal_strength);
+            schematic.set_block((0, 0, 0), "minecraft:stone");
+            schematic.set_block(
+                (1, 0, 0),
+                "minecraft:comparator[facing=west,mode=compare,powered=false]",
+            );
+            schematic.set_block((2, 0, 0), "minecraft:redstone_wire");
+
+            let mut simulation = Simulation::new(&schematic);
+            assert_eq!(simulation.signal_strength((-1, 0, 0)), signal_strength);
+            simulation.run_until_stable(10).unwrap();
+            assert_eq!(simulation.signal_strength((1, 0, 0)), signal_strength);
+            assert_eq!(simulation.signal_strength((2, 0, 0)), signal_strength);
+        }
+
+        // Not through other blocks
+        let mut schematic = MCSchematic::new();
+        set_chest(&mut schematic, (-1, 0, 0), 15);
+        schematic.set_block((0, 0, 0), "minecraft:glass");
+        schematic.set_block(
+            (1, 0, 0),
+            "minecraft:comparator[facing=west,mode=compare,powered=false]",
+        );
+        let mut simulation = Simulation::new(&schematic);
+        simulation.run_until_stable(10).unwrap();
+        assert_eq!(simulation.signal_strength((1, 0, 0)), 0);
+    }
+
+    #[test]
+    fn torches_invert_the_block_they_are_attached_to() {
+        let mut schematic = MCSchematic::new();
+        schematic.set_block((0, 0, 0), "minecraft:stone");
+        schematic.set_block(
+            (0, 0, 1),
+            "minecraft:lever[face=wall,facing=south,powered=false]",
+        );
+        schematic.set_block((0, 1, 0), "minecraft:redstone_torch[lit=true]");
+        schematic.set_block((0, 2, 0), LAMP);
+        schematic.set_block(
+            (1, 0, 0),
+            "minecraft:redstone_wall_torch[facing=east,lit=true]",
+        );
+
+        let mut simulation = Simulation::new(&schematic);
+        assert_eq!(simulation.run_until_stable(10), Ok(0));
+        assert!(simulation.is_powered((0, 2, 0)));
+
+        simulation.set_lever((0, 0, 1), true).unwrap();
+        assert!(simulation.is_powered((0, 0, 0)));
+        assert!(simulation.is_powered((0, 1, 0)));
+        simulation.tick();
+        assert!(!simulation.is_powered((0, 1, 0)));
+        assert!(!simulation.is_powered((1, 0, 0)));
+        assert!(!simulation.is_powered((0, 2, 0)));
+
+        simulation.set_lever((0, 0, 1), false).unwrap();
+        simulation.tick();
+        assert!(simulation.is_powered((0, 1, 0)));
+        assert!(simulation.is_powered((1, 0, 0)));
+        assert!(simulation.is_powered((0, 2, 0)));
+    }
+
+    #[test]
+    fn torches_burn_out_when_toggled_too_often() {
+        let mut schematic = MCSchematic::new();
+        schematic.set_block((0, 0, 0), "minecraft:stone");
+        schematic.set_block(
+            (0, 0, 1),
+            "minecraft:lever[face=wall,facing=south,powered=false]",
+        );
+        schematic.set_block((0, 1, 0), "minecraft:redstone_torch[lit=true]");
+
+        let mut simulation = Simulation::new(&schematic);
+        for toggle in 1..=BURNOUT_TOGGLES {
+            simulation.set_lever((0, 0, 1), true).unwrap();
+            simulation.run(2);
+            assert!(!simulation.is_powered((0, 1, 0)));
+            simulation.set_lever((0, 0, 1), false).unwrap();
+            simulation.run(2);
+            assert_eq!(
+                simulation.is_powered((0, 1, 0)),
+                toggle < BURNOUT_TOGGLES,
+                "after {} toggles",
+                toggle
+            );
+        }
+
+        // Lights up again once it cooled down
+        simulation.run(BURNOUT_COOLDOWN as u32 - 10);
+        assert!(!simulation.is_powered((0, 1, 0)));
+        assert!(simulation.run_until_stable(20).unwrap() > 0);
+        assert!(simulation.is_powered((0, 1, 0)));
+    }
+
+    #[test]
+    fn lamps_light_up_right_away() {
+        let mut schematic = MCSchematic::new();
+        schematic.set_block((0, 0, 0), FLOOR_LEVER);
+        schematic.set_block((1, 0, 0), LAMP);
+        schematic.set_block((0, 1, 0), "minecraft:stone");
+        schematic.set_block((0, 2, 0), LAMP);
+        schematic.set_block((0, 0, 1), "minecraft:redstone_wire[north=side,south=side]");
+        schematic.set_block((0, 0, 2), "minecraft:redstone_wire[north=side,south=side]");
+        schematic.set_block((1, 0, 2), LAMP);
+        schematic.set_block((0, 0, 3), LAMP);
+
+        let mut simulation = Simulation::new(&schematic);
+        simulation.set_lever((0, 0, 0), true).unwrap();
+        assert_eq!(simulation.current_tick(), 0);
+        // Next to the lever, but not above the block it's attached to
+        assert!(simulation.is_powered((1, 0, 0)));
+        assert!(!simulation.is_powered((0, 2, 0)));
+        // Only where the wire points
+        assert!(simulation.is_powered((0, 0, 3)));
+        assert!(!simulation.is_powered((1, 0, 2)));
+
+        simulation.set_lever((0, 0, 0), false).unwrap();
+        assert!(!simulation.is_powered((1, 0, 0)));
+        assert!(!simulation.is_powered((0, 0, 3)));
+    }
+}
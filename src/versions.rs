@@ -1,490 +1,1252 @@
-#![allow(dead_code)]
 #![allow(non_upper_case_globals)]
 
-pub const JE_1_20_1: i32 = 3465;
-pub const JE_1_20_1_RELEASE_CANDIDATE_1: i32 = 3464;
-pub const JE_1_20: i32 = 3463;
-pub const JE_1_20_RELEASE_CANDIDATE_1: i32 = 3462;
-pub const JE_1_20_PRE_RELEASE_7: i32 = 3461;
-pub const JE_1_20_PRE_RELEASE_6: i32 = 3460;
-pub const JE_1_20_PRE_RELEASE_5: i32 = 3458;
-pub const JE_1_20_PRE_RELEASE_4: i32 = 3457;
-pub const JE_1_20_PRE_RELEASE_3: i32 = 3456;
-pub const JE_1_20_PRE_RELEASE_2: i32 = 3455;
-pub const JE_1_20_PRE_RELEASE_1: i32 = 3454;
-pub const JE_23W18A: i32 = 3453;
-pub const JE_23W17A: i32 = 3452;
-pub const JE_23W16A: i32 = 3449;
-pub const JE_23W14A: i32 = 3445;
-pub const JE_23W13A: i32 = 3443;
-pub const JE_23W12A: i32 = 3442;
-pub const JE_1_19_4: i32 = 3337;
-pub const JE_1_19_4_RELEASE_CANDIDATE_3: i32 = 3336;
-pub const JE_1_19_4_RELEASE_CANDIDATE_2: i32 = 3335;
-pub const JE_1_19_4_RELEASE_CANDIDATE_1: i32 = 3334;
-pub const JE_1_19_4_PRE_RELEASE_4: i32 = 3333;
-pub const JE_1_19_4_PRE_RELEASE_3: i32 = 3332;
-pub const JE_1_19_4_PRE_RELEASE_2: i32 = 3331;
-pub const JE_1_19_4_PRE_RELEASE_1: i32 = 3330;
-pub const JE_23W07A: i32 = 3329;
-pub const JE_23W06A: i32 = 3326;
-pub const JE_23W05A: i32 = 3323;
-pub const JE_23W04A: i32 = 3321;
-pub const JE_23W03A: i32 = 3320;
-pub const JE_1_19_3: i32 = 3218;
-pub const JE_1_19_3_RELEASE_CANDIDATE_3: i32 = 3217;
-pub const JE_1_19_3_RELEASE_CANDIDATE_2: i32 = 3216;
-pub const JE_1_19_3_RELEASE_CANDIDATE_1: i32 = 3215;
-pub const JE_1_19_3_PRE_RELEASE_3: i32 = 3213;
-pub const JE_1_19_3_PRE_RELEASE_2: i32 = 3212;
-pub const JE_1_19_3_PRE_RELEASE_1: i32 = 3211;
-pub const JE_22W46A: i32 = 3210;
-pub const JE_22W45A: i32 = 3208;
-pub const JE_22W44A: i32 = 3207;
-pub const JE_22W43A: i32 = 3206;
-pub const JE_22W42A: i32 = 3205;
-pub const JE_1_19_2: i32 = 3120;
-pub const JE_1_19_2_RELEASE_CANDIDATE_2: i32 = 3119;
-pub const JE_1_19_2_RELEASE_CANDIDATE_1: i32 = 3118;
-pub const JE_1_19_1: i32 = 3117;
-pub const JE_1_19_1_RELEASE_CANDIDATE_3: i32 = 3116;
-pub const JE_1_19_1_RELEASE_CANDIDATE_2: i32 = 3115;
-pub const JE_1_19_1_PRE_RELEASE_6: i32 = 3114;
-pub const JE_1_19_1_PRE_RELEASE_5: i32 = 3113;
-pub const JE_1_19_1_PRE_RELEASE_4: i32 = 3112;
-pub const JE_1_19_1_PRE_RELEASE_3: i32 = 3111;
-pub const JE_1_19_1_PRE_RELEASE_2: i32 = 3110;
-pub const JE_1_19_1_RELEASE_CANDIDATE_1: i32 = 3109;
-pub const JE_1_19_1_PRE_RELEASE_1: i32 = 3107;
-pub const JE_22W24A: i32 = 3106;
-pub const JE_1_19: i32 = 3105;
-pub const JE_1_19_RELEASE_CANDIDATE_2: i32 = 3104;
-pub const JE_1_19_RELEASE_CANDIDATE_1: i32 = 3103;
-pub const JE_1_19_PRE_RELEASE_5: i32 = 3102;
-pub const JE_1_19_PRE_RELEASE_4: i32 = 3101;
-pub const JE_1_19_PRE_RELEASE_3: i32 = 3100;
-pub const JE_1_19_PRE_RELEASE_2: i32 = 3099;
-pub const JE_1_19_PRE_RELEASE_1: i32 = 3098;
-pub const JE_22W19A: i32 = 3096;
-pub const JE_22W18A: i32 = 3095;
-pub const JE_22W17A: i32 = 3093;
-pub const JE_22W16B: i32 = 3092;
-pub const JE_22W16A: i32 = 3091;
-pub const JE_22W15A: i32 = 3089;
-pub const JE_22W14A: i32 = 3088;
-pub const JE_22W13A: i32 = 3085;
-pub const JE_22W12A: i32 = 3082;
-pub const JE_22W11A: i32 = 3080;
-pub const JE_DEEP_DARK_EXPERIMENTAL_SNAPSHOT_1: i32 = 3066;
-pub const JE_1_18_2: i32 = 2975;
-pub const JE_1_18_2_RELEASE_CANDIDATE_1: i32 = 2974;
-pub const JE_1_18_2_PRE_RELEASE_3: i32 = 2973;
-pub const JE_1_18_2_PRE_RELEASE_2: i32 = 2972;
-pub const JE_1_18_2_PRE_RELEASE_1: i32 = 2971;
-pub const JE_22W07A: i32 = 2969;
-pub const JE_22W06A: i32 = 2968;
-pub const JE_22W05A: i32 = 2967;
-pub const JE_22W03A: i32 = 2966;
-pub const JE_1_18_1: i32 = 2865;
-pub const JE_1_18_1_RELEASE_CANDIDATE_3: i32 = 2864;
-pub const JE_1_18_1_RELEASE_CANDIDATE_2: i32 = 2863;
-pub const JE_1_18_1_RELEASE_CANDIDATE_1: i32 = 2862;
-pub const JE_1_18_1_PRE_RELEASE_1: i32 = 2861;
-pub const JE_1_18: i32 = 2860;
-pub const JE_1_18_RELEASE_CANDIDATE_4: i32 = 2859;
-pub const JE_1_18_RELEASE_CANDIDATE_3: i32 = 2858;
-pub const JE_1_18_RELEASE_CANDIDATE_2: i32 = 2857;
-pub const JE_1_18_RELEASE_CANDIDATE_1: i32 = 2856;
-pub const JE_1_18_PRE_RELEASE_8: i32 = 2855;
-pub const JE_1_18_PRE_RELEASE_7: i32 = 2854;
-pub const JE_1_18_PRE_RELEASE_6: i32 = 2853;
-pub const JE_1_18_PRE_RELEASE_5: i32 = 2851;
-pub const JE_1_18_PRE_RELEASE_4: i32 = 2850;
-pub const JE_1_18_PRE_RELEASE_3: i32 = 2849;
-pub const JE_1_18_PRE_RELEASE_2: i32 = 2848;
-pub const JE_1_18_PRE_RELEASE_1: i32 = 2847;
-pub const JE_21W44A: i32 = 2845;
-pub const JE_21W43A: i32 = 2844;
-pub const JE_21W42A: i32 = 2840;
-pub const JE_21W41A: i32 = 2839;
-pub const JE_21W40A: i32 = 2838;
-pub const JE_21W39A: i32 = 2836;
-pub const JE_21W38A: i32 = 2835;
-pub const JE_21W37A: i32 = 2834;
-pub const JE_1_18_EXPERIMENTAL_SNAPSHOT_7: i32 = 2831;
-pub const JE_1_18_EXPERIMENTAL_SNAPSHOT_6: i32 = 2830;
-pub const JE_1_18_EXPERIMENTAL_SNAPSHOT_5: i32 = 2829;
-pub const JE_1_18_EXPERIMENTAL_SNAPSHOT_4: i32 = 2828;
-pub const JE_1_18_EXPERIMENTAL_SNAPSHOT_3: i32 = 2827;
-pub const JE_1_18_EXPERIMENTAL_SNAPSHOT_2: i32 = 2826;
-pub const JE_1_18_EXPERIMENTAL_SNAPSHOT_1: i32 = 2825;
-pub const JE_1_17_1: i32 = 2730;
-pub const JE_1_17_1_RELEASE_CANDIDATE_2: i32 = 2729;
-pub const JE_1_17_1_RELEASE_CANDIDATE_1: i32 = 2728;
-pub const JE_1_17_1_PRE_RELEASE_3: i32 = 2727;
-pub const JE_1_17_1_PRE_RELEASE_2: i32 = 2726;
-pub const JE_1_17_1_PRE_RELEASE_1: i32 = 2725;
-pub const JE_1_17: i32 = 2724;
-pub const JE_1_17_RELEASE_CANDIDATE_2: i32 = 2723;
-pub const JE_1_17_RELEASE_CANDIDATE_1: i32 = 2722;
-pub const JE_1_17_PRE_RELEASE_5: i32 = 2721;
-pub const JE_1_17_PRE_RELEASE_4: i32 = 2720;
-pub const JE_1_17_PRE_RELEASE_3: i32 = 2719;
-pub const JE_1_17_PRE_RELEASE_2: i32 = 2718;
-pub const JE_1_17_PRE_RELEASE_1: i32 = 2716;
-pub const JE_21W20A: i32 = 2715;
-pub const JE_21W19A: i32 = 2714;
-pub const JE_21W18A: i32 = 2713;
-pub const JE_21W17A: i32 = 2712;
-pub const JE_21W16A: i32 = 2711;
-pub const JE_21W15A: i32 = 2709;
-pub const JE_21W14A: i32 = 2706;
-pub const JE_21W13A: i32 = 2705;
-pub const JE_21W11A: i32 = 2703;
-pub const JE_21W10A: i32 = 2699;
-pub const JE_21W08B: i32 = 2698;
-pub const JE_21W08A: i32 = 2697;
-pub const JE_21W07A: i32 = 2695;
-pub const JE_21W06A: i32 = 2694;
-pub const JE_21W05B: i32 = 2692;
-pub const JE_21W05A: i32 = 2690;
-pub const JE_21W03A: i32 = 2689;
-pub const JE_20W51A: i32 = 2687;
-pub const JE_20W49A: i32 = 2685;
-pub const JE_20W48A: i32 = 2683;
-pub const JE_20W46A: i32 = 2682;
-pub const JE_20W45A: i32 = 2681;
-pub const JE_COMBAT_TEST_8C: i32 = 2707;
-pub const JE_COMBAT_TEST_8B: i32 = 2706;
-pub const JE_COMBAT_TEST_8: i32 = 2705;
-pub const JE_COMBAT_TEST_7C: i32 = 2704;
-pub const JE_COMBAT_TEST_7B: i32 = 2703;
-pub const JE_COMBAT_TEST_7: i32 = 2702;
-pub const JE_COMBAT_TEST_6: i32 = 2701;
-pub const JE_1_16_5: i32 = 2586;
-pub const JE_1_16_5_RELEASE_CANDIDATE_1: i32 = 2585;
-pub const JE_1_16_4: i32 = 2584;
-pub const JE_1_16_4_RELEASE_CANDIDATE_1: i32 = 2583;
-pub const JE_1_16_4_PRE_RELEASE_2: i32 = 2582;
-pub const JE_1_16_4_PRE_RELEASE_1: i32 = 2581;
-pub const JE_1_16_3: i32 = 2580;
-pub const JE_1_16_3_RELEASE_CANDIDATE_1: i32 = 2579;
-pub const JE_1_16_2: i32 = 2578;
-pub const JE_1_16_2_RELEASE_CANDIDATE_2: i32 = 2577;
-pub const JE_1_16_2_RELEASE_CANDIDATE_1: i32 = 2576;
-pub const JE_1_16_2_PRE_RELEASE_3: i32 = 2575;
-pub const JE_1_16_2_PRE_RELEASE_2: i32 = 2574;
-pub const JE_1_16_2_PRE_RELEASE_1: i32 = 2573;
-pub const JE_20W30A: i32 = 2572;
-pub const JE_20W29A: i32 = 2571;
-pub const JE_20W28A: i32 = 2570;
-pub const JE_20W27A: i32 = 2569;
-pub const JE_1_16_1: i32 = 2567;
-pub const JE_1_16: i32 = 2566;
-pub const JE_1_16_RELEASE_CANDIDATE_1: i32 = 2565;
-pub const JE_1_16_PRE_RELEASE_8: i32 = 2564;
-pub const JE_1_16_PRE_RELEASE_7: i32 = 2563;
-pub const JE_1_16_PRE_RELEASE_6: i32 = 2562;
-pub const JE_1_16_PRE_RELEASE_5: i32 = 2561;
-pub const JE_1_16_PRE_RELEASE_4: i32 = 2560;
-pub const JE_1_16_PRE_RELEASE_3: i32 = 2559;
-pub const JE_1_16_PRE_RELEASE_2: i32 = 2557;
-pub const JE_1_16_PRE_RELEASE_1: i32 = 2556;
-pub const JE_20W22A: i32 = 2555;
-pub const JE_20W21A: i32 = 2554;
-pub const JE_20W20B: i32 = 2537;
-pub const JE_20W20A: i32 = 2536;
-pub const JE_20W19A: i32 = 2534;
-pub const JE_20W18A: i32 = 2532;
-pub const JE_20W17A: i32 = 2529;
-pub const JE_20W16A: i32 = 2526;
-pub const JE_20W15A: i32 = 2525;
-pub const JE_20W14A: i32 = 2524;
-pub const JE_20W13B: i32 = 2521;
-pub const JE_20W13A: i32 = 2520;
-pub const JE_20W12A: i32 = 2515;
-pub const JE_20W11A: i32 = 2513;
-pub const JE_20W10A: i32 = 2512;
-pub const JE_20W09A: i32 = 2510;
-pub const JE_20W08A: i32 = 2507;
-pub const JE_20W07A: i32 = 2506;
-pub const JE_SNAPSHOT_20W06A: i32 = 2504;
-pub const JE_COMBAT_TEST_5: i32 = 2321;
-pub const JE_COMBAT_TEST_4: i32 = 2320;
-pub const JE_1_15_2: i32 = 2230;
-pub const JE_1_15_2_PRE_RELEASE_2: i32 = 2229;
-pub const JE_1_15_2_PRE_RELEASE_1: i32 = 2228;
-pub const JE_1_15_1: i32 = 2227;
-pub const JE_1_15_1_PRE_RELEASE_1: i32 = 2226;
-pub const JE_1_15: i32 = 2225;
-pub const JE_1_15_PRE_RELEASE_7: i32 = 2224;
-pub const JE_1_15_PRE_RELEASE_6: i32 = 2223;
-pub const JE_1_15_PRE_RELEASE_5: i32 = 2222;
-pub const JE_1_15_PRE_RELEASE_4: i32 = 2221;
-pub const JE_1_15_PRE_RELEASE_3: i32 = 2220;
-pub const JE_1_15_PRE_RELEASE_2: i32 = 2219;
-pub const JE_1_15_PRE_RELEASE_1: i32 = 2218;
-pub const JE_19W46B: i32 = 2217;
-pub const JE_19W46A: i32 = 2216;
-pub const JE_19W45B: i32 = 2215;
-pub const JE_19W45A: i32 = 2214;
-pub const JE_19W44A: i32 = 2213;
-pub const JE_19W42A: i32 = 2212;
-pub const JE_19W41A: i32 = 2210;
-pub const JE_19W40A: i32 = 2208;
-pub const JE_19W39A: i32 = 2207;
-pub const JE_19W38B: i32 = 2206;
-pub const JE_19W38A: i32 = 2205;
-pub const JE_19W37A: i32 = 2204;
-pub const JE_19W36A: i32 = 2203;
-pub const JE_19W35A: i32 = 2201;
-pub const JE_19W34A: i32 = 2200;
-pub const JE_COMBAT_TEST_3: i32 = 2069;
-pub const JE_COMBAT_TEST_2: i32 = 2068;
-pub const JE_1_14_3___COMBAT_TEST: i32 = 2067;
-pub const JE_1_14_4: i32 = 1976;
-pub const JE_1_14_4_PRE_RELEASE_7: i32 = 1975;
-pub const JE_1_14_4_PRE_RELEASE_6: i32 = 1974;
-pub const JE_1_14_4_PRE_RELEASE_5: i32 = 1973;
-pub const JE_1_14_4_PRE_RELEASE_4: i32 = 1972;
-pub const JE_1_14_4_PRE_RELEASE_3: i32 = 1971;
-pub const JE_1_14_4_PRE_RELEASE_2: i32 = 1970;
-pub const JE_1_14_4_PRE_RELEASE_1: i32 = 1969;
-pub const JE_1_14_3: i32 = 1968;
-pub const JE_1_14_3_PRE_RELEASE_4: i32 = 1967;
-pub const JE_1_14_3_PRE_RELEASE_3: i32 = 1966;
-pub const JE_1_14_3_PRE_RELEASE_2: i32 = 1965;
-pub const JE_1_14_3_PRE_RELEASE_1: i32 = 1964;
-pub const JE_1_14_2: i32 = 1963;
-pub const JE_1_14_2_PRE_RELEASE_4: i32 = 1962;
-pub const JE_1_14_2_PRE_RELEASE_3: i32 = 1960;
-pub const JE_1_14_2_PRE_RELEASE_2: i32 = 1959;
-pub const JE_1_14_2_PRE_RELEASE_1: i32 = 1958;
-pub const JE_1_14_1: i32 = 1957;
-pub const JE_1_14_1_PRE_RELEASE_2: i32 = 1956;
-pub const JE_1_14_1_PRE_RELEASE_1: i32 = 1955;
-pub const JE_1_14: i32 = 1952;
-pub const JE_1_14_PRE_RELEASE_5: i32 = 1951;
-pub const JE_1_14_PRE_RELEASE_4: i32 = 1950;
-pub const JE_1_14_PRE_RELEASE_3: i32 = 1949;
-pub const JE_1_14_PRE_RELEASE_2: i32 = 1948;
-pub const JE_1_14_PRE_RELEASE_1: i32 = 1947;
-pub const JE_19W14B: i32 = 1945;
-pub const JE_19W14A: i32 = 1944;
-pub const JE_19W13B: i32 = 1943;
-pub const JE_19W13A: i32 = 1942;
-pub const JE_19W12B: i32 = 1941;
-pub const JE_19W12A: i32 = 1940;
-pub const JE_19W11B: i32 = 1938;
-pub const JE_19W11A: i32 = 1937;
-pub const JE_19W09A: i32 = 1935;
-pub const JE_19W08B: i32 = 1934;
-pub const JE_19W08A: i32 = 1933;
-pub const JE_19W07A: i32 = 1932;
-pub const JE_19W06A: i32 = 1931;
-pub const JE_19W05A: i32 = 1930;
-pub const JE_19W04B: i32 = 1927;
-pub const JE_19W04A: i32 = 1926;
-pub const JE_19W03C: i32 = 1924;
-pub const JE_19W03B: i32 = 1923;
-pub const JE_19W03A: i32 = 1922;
-pub const JE_19W02A: i32 = 1921;
-pub const JE_18W50A: i32 = 1919;
-pub const JE_18W49A: i32 = 1916;
-pub const JE_18W48B: i32 = 1915;
-pub const JE_18W48A: i32 = 1914;
-pub const JE_18W47B: i32 = 1913;
-pub const JE_18W47A: i32 = 1912;
-pub const JE_18W46A: i32 = 1910;
-pub const JE_18W45A: i32 = 1908;
-pub const JE_18W44A: i32 = 1907;
-pub const JE_18W43C: i32 = 1903;
-pub const JE_18W43B: i32 = 1902;
-pub const JE_18W43A: i32 = 1901;
-pub const JE_1_13_2: i32 = 1631;
-pub const JE_1_13_2_PRE2: i32 = 1630;
-pub const JE_1_13_2_PRE1: i32 = 1629;
-pub const JE_1_13_1: i32 = 1628;
-pub const JE_1_13_1_PRE2: i32 = 1627;
-pub const JE_1_13_1_PRE1: i32 = 1626;
-pub const JE_18W33A: i32 = 1625;
-pub const JE_18W32A: i32 = 1623;
-pub const JE_18W31A: i32 = 1622;
-pub const JE_18W30B: i32 = 1621;
-pub const JE_18W30A: i32 = 1620;
-pub const JE_1_13: i32 = 1519;
-pub const JE_1_13_PRE10: i32 = 1518;
-pub const JE_1_13_PRE9: i32 = 1517;
-pub const JE_1_13_PRE8: i32 = 1516;
-pub const JE_1_13_PRE7: i32 = 1513;
-pub const JE_1_13_PRE6: i32 = 1512;
-pub const JE_1_13_PRE5: i32 = 1511;
-pub const JE_1_13_PRE4: i32 = 1504;
-pub const JE_1_13_PRE3: i32 = 1503;
-pub const JE_1_13_PRE2: i32 = 1502;
-pub const JE_1_13_PRE1: i32 = 1501;
-pub const JE_18W22C: i32 = 1499;
-pub const JE_18W22B: i32 = 1498;
-pub const JE_18W22A: i32 = 1497;
-pub const JE_18W21B: i32 = 1496;
-pub const JE_18W21A: i32 = 1495;
-pub const JE_18W20C: i32 = 1493;
-pub const JE_18W20B: i32 = 1491;
-pub const JE_18W20A: i32 = 1489;
-pub const JE_18W19B: i32 = 1485;
-pub const JE_18W19A: i32 = 1484;
-pub const JE_18W16A: i32 = 1483;
-pub const JE_18W15A: i32 = 1482;
-pub const JE_18W14B: i32 = 1481;
-pub const JE_18W14A: i32 = 1479;
-pub const JE_18W11A: i32 = 1478;
-pub const JE_18W10D: i32 = 1477;
-pub const JE_18W10C: i32 = 1476;
-pub const JE_18W10B: i32 = 1474;
-pub const JE_18W10A: i32 = 1473;
-pub const JE_18W09A: i32 = 1472;
-pub const JE_18W08B: i32 = 1471;
-pub const JE_18W08A: i32 = 1470;
-pub const JE_18W07C: i32 = 1469;
-pub const JE_18W07B: i32 = 1468;
-pub const JE_18W07A: i32 = 1467;
-pub const JE_18W06A: i32 = 1466;
-pub const JE_18W05A: i32 = 1464;
-pub const JE_18W03B: i32 = 1463;
-pub const JE_18W03A: i32 = 1462;
-pub const JE_18W02A: i32 = 1461;
-pub const JE_18W01A: i32 = 1459;
-pub const JE_17W50A: i32 = 1457;
-pub const JE_17W49B: i32 = 1455;
-pub const JE_17W49A: i32 = 1454;
-pub const JE_17W48A: i32 = 1453;
-pub const JE_17W47B: i32 = 1452;
-pub const JE_17W47A: i32 = 1451;
-pub const JE_17W46A: i32 = 1449;
-pub const JE_17W45B: i32 = 1448;
-pub const JE_17W45A: i32 = 1447;
-pub const JE_17W43B: i32 = 1445;
-pub const JE_17W43A: i32 = 1444;
-pub const JE_1_12_2: i32 = 1343;
-pub const JE_1_12_2_PRE2: i32 = 1342;
-pub const JE_1_12_2_PRE1: i32 = 1341;
-pub const JE_1_12_1: i32 = 1241;
-pub const JE_1_12_1_PRE1: i32 = 1240;
-pub const JE_17W31A: i32 = 1239;
-pub const JE_1_12: i32 = 1139;
-pub const JE_1_12_PRE7: i32 = 1138;
-pub const JE_1_12_PRE6: i32 = 1137;
-pub const JE_1_12_PRE5: i32 = 1136;
-pub const JE_1_12_PRE4: i32 = 1135;
-pub const JE_1_12_PRE3: i32 = 1134;
-pub const JE_1_12_PRE2: i32 = 1133;
-pub const JE_1_12_PRE1: i32 = 1132;
-pub const JE_17W18B: i32 = 1131;
-pub const JE_17W18A: i32 = 1130;
-pub const JE_17W17B: i32 = 1129;
-pub const JE_17W17A: i32 = 1128;
-pub const JE_17W16B: i32 = 1127;
-pub const JE_17W16A: i32 = 1126;
-pub const JE_17W15A: i32 = 1125;
-pub const JE_17W14A: i32 = 1124;
-pub const JE_17W13B: i32 = 1123;
-pub const JE_17W13A: i32 = 1122;
-pub const JE_17W06A: i32 = 1022;
-pub const JE_1_11_2: i32 = 922;
-pub const JE_1_11_1: i32 = 921;
-pub const JE_16W50A: i32 = 920;
-pub const JE_1_11: i32 = 819;
-pub const JE_1_11_PRE1: i32 = 818;
-pub const JE_16W44A: i32 = 817;
-pub const JE_16W43A: i32 = 816;
-pub const JE_16W42A: i32 = 815;
-pub const JE_16W41A: i32 = 814;
-pub const JE_16W40A: i32 = 813;
-pub const JE_16W39C: i32 = 812;
-pub const JE_16W39B: i32 = 811;
-pub const JE_16W39A: i32 = 809;
-pub const JE_16W38A: i32 = 807;
-pub const JE_16W36A: i32 = 805;
-pub const JE_16W35A: i32 = 803;
-pub const JE_16W33A: i32 = 802;
-pub const JE_16W32B: i32 = 801;
-pub const JE_16W32A: i32 = 800;
-pub const JE_1_10_2: i32 = 512;
-pub const JE_1_10_1: i32 = 511;
-pub const JE_1_10: i32 = 510;
-pub const JE_1_10_PRE2: i32 = 507;
-pub const JE_1_10_PRE1: i32 = 506;
-pub const JE_16W21B: i32 = 504;
-pub const JE_16W21A: i32 = 503;
-pub const JE_16W20A: i32 = 501;
-pub const JE_1_9_4: i32 = 184;
-pub const JE_1_9_3: i32 = 183;
-pub const JE_1_9_3_PRE3: i32 = 182;
-pub const JE_1_9_3_PRE2: i32 = 181;
-pub const JE_1_9_3_PRE1: i32 = 180;
-pub const JE_16W15B: i32 = 179;
-pub const JE_16W15A: i32 = 178;
-pub const JE_16W14A: i32 = 177;
-pub const JE_1_9_2: i32 = 176;
-pub const JE_1_9_1: i32 = 175;
-pub const JE_1_9_1_PRE3: i32 = 172;
-pub const JE_1_9_1_PRE2: i32 = 171;
-pub const JE_1_9_1_PRE1: i32 = 170;
-pub const JE_1_9: i32 = 169;
-pub const JE_1_9_PRE4: i32 = 168;
-pub const JE_1_9_PRE3: i32 = 167;
-pub const JE_1_9_PRE2: i32 = 165;
-pub const JE_1_9_PRE1: i32 = 164;
-pub const JE_16W07B: i32 = 163;
-pub const JE_16W07A: i32 = 162;
-pub const JE_16W06A: i32 = 161;
-pub const JE_16W05B: i32 = 160;
-pub const JE_16W05A: i32 = 159;
-pub const JE_16W04A: i32 = 158;
-pub const JE_16W03A: i32 = 157;
-pub const JE_16W02A: i32 = 156;
-pub const JE_15W51B: i32 = 155;
-pub const JE_15W51A: i32 = 154;
-pub const JE_15W50A: i32 = 153;
-pub const JE_15W49B: i32 = 152;
-pub const JE_15W49A: i32 = 151;
-pub const JE_15W47C: i32 = 150;
-pub const JE_15W47B: i32 = 149;
-pub const JE_15W47A: i32 = 148;
-pub const JE_15W46A: i32 = 146;
-pub const JE_15W45A: i32 = 145;
-pub const JE_15W44B: i32 = 143;
-pub const JE_15W44A: i32 = 142;
-pub const JE_15W43C: i32 = 141;
-pub const JE_15W43B: i32 = 140;
-pub const JE_15W43A: i32 = 139;
-pub const JE_15W42A: i32 = 138;
-pub const JE_15W41B: i32 = 137;
-pub const JE_15W41A: i32 = 136;
-pub const JE_15W40B: i32 = 134;
-pub const JE_15W40A: i32 = 133;
-pub const JE_15W39C: i32 = 132;
-pub const JE_15W39B: i32 = 131;
-pub const JE_15W39A: i32 = 130;
-pub const JE_15W38B: i32 = 129;
-pub const JE_15W38A: i32 = 128;
-pub const JE_15W37A: i32 = 127;
-pub const JE_15W36D: i32 = 126;
-pub const JE_15W36C: i32 = 125;
-pub const JE_15W36B: i32 = 124;
-pub const JE_15W36A: i32 = 123;
-pub const JE_15W35E: i32 = 122;
-pub const JE_15W35D: i32 = 121;
-pub const JE_15W35C: i32 = 120;
-pub const JE_15W35B: i32 = 119;
-pub const JE_15W35A: i32 = 118;
-pub const JE_15W34D: i32 = 117;
-pub const JE_15W34C: i32 = 116;
-pub const JE_15W34B: i32 = 115;
-pub const JE_15W34A: i32 = 114;
-pub const JE_15W33C: i32 = 112;
-pub const JE_15W33B: i32 = 111;
-pub const JE_15W33A: i32 = 55;
-pub const JE_15W32C: i32 = 104;
-pub const JE_15W32B: i32 = 103;
-pub const JE_15W32A: i32 = 100;
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+/// Kind of a Minecraft: Java Edition release
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ReleaseType {
+    Release,
+    Snapshot,
+    PreRelease,
+    ReleaseCandidate,
+    Experimental,
+}
+
+/// A Minecraft: Java Edition version together with the DataVersion
+/// written into the files it saves. Versions are ordered by their DataVersion,
+/// so checks like `version >= JE_1_13` work as expected
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct McVersion {
+    name: &'static str,
+    data_version: i32,
+    release_type: ReleaseType,
+}
+
+impl McVersion {
+    /// Returns the name of the version, such as "1.20.1", "1.20-pre7", "1.20.1-rc1" or "23w18a"
+    pub fn name(self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the DataVersion of the version
+    pub fn data_version(self) -> i32 {
+        self.data_version
+    }
+
+    /// Returns whether the version is a release, snapshot, pre-release, release candidate
+    /// or an experimental build
+    pub fn release_type(self) -> ReleaseType {
+        self.release_type
+    }
+
+    /// Looks a version up by its name, ignoring the case
+    pub fn from_name(name: &str) -> Option<McVersion> {
+        McVersion::all().find(|version| version.name.eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Looks a version up by its DataVersion. A few experimental builds share their
+    /// DataVersion with a snapshot, in which case the snapshot is returned
+    pub fn from_data_version(data_version: i32) -> Option<McVersion> {
+        McVersion::all()
+            .filter(|version| version.data_version == data_version)
+            .min_by_key(|version| version.release_type == ReleaseType::Experimental)
+    }
+
+    /// Iterates over all known versions, newest first
+    pub fn all() -> impl Iterator<Item = McVersion> {
+        ALL.iter().copied()
+    }
+}
+
+impl Ord for McVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.data_version, self.name).cmp(&(other.data_version, other.name))
+    }
+}
+
+impl PartialOrd for McVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for McVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl FromStr for McVersion {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        McVersion::from_name(name).ok_or_else(|| format!("Unknown Minecraft version \"{}\"", name))
+    }
+}
+
+/// A Minecraft: Bedrock Edition version. Bedrock stores the version that last changed
+/// a block's states next to every block, packed into an Int as one byte per version number
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BedrockVersion {
+    block_version: i32,
+    name: &'static str,
+}
+
+impl BedrockVersion {
+    /// Returns the name of the version, such as "1.20.0"
+    pub fn name(self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the block state version written next to the blocks of a structure
+    pub fn block_version(self) -> i32 {
+        self.block_version
+    }
+
+    /// Looks a version up by its name
+    pub fn from_name(name: &str) -> Option<BedrockVersion> {
+        BedrockVersion::all().find(|version| version.name == name.trim())
+    }
+
+    /// Iterates over all known versions, newest first
+    pub fn all() -> impl Iterator<Item = BedrockVersion> {
+        BEDROCK_ALL.iter().copied()
+    }
+}
+
+impl fmt::Display for BedrockVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+const fn bedrock(name: &'static str, [major, minor, patch, revision]: [i32; 4]) -> BedrockVersion {
+    BedrockVersion {
+        block_version: major << 24 | minor << 16 | patch << 8 | revision,
+        name,
+    }
+}
+
+const fn release(name: &'static str, data_version: i32) -> McVersion {
+    McVersion {
+        name,
+        data_version,
+        release_type: ReleaseType::Release,
+    }
+}
+
+const fn snapshot(name: &'static str, data_version: i32) -> McVersion {
+    McVersion {
+        name,
+        data_version,
+        release_type: ReleaseType::Snapshot,
+    }
+}
+
+const fn pre_release(name: &'static str, data_version: i32) -> McVersion {
+    McVersion {
+        name,
+        data_version,
+        release_type: ReleaseType::PreRelease,
+    }
+}
+
+const fn release_candidate(name: &'static str, data_version: i32) -> McVersion {
+    McVersion {
+        name,
+        data_version,
+        release_type: ReleaseType::ReleaseCandidate,
+    }
+}
+
+const fn experimental(name: &'static str, data_version: i32) -> McVersion {
+    McVersion {
+        name,
+        data_version,
+        release_type: ReleaseType::Experimental,
+    }
+}
+
+pub const JE_1_20_1: McVersion = release("1.20.1", 3465);
+pub const JE_1_20_1_RELEASE_CANDIDATE_1: McVersion = release_candidate("1.20.1-rc1", 3464);
+pub const JE_1_20: McVersion = release("1.20", 3463);
+pub const JE_1_20_RELEASE_CANDIDATE_1: McVersion = release_candidate("1.20-rc1", 3462);
+pub const JE_1_20_PRE_RELEASE_7: McVersion = pre_release("1.20-pre7", 3461);
+pub const JE_1_20_PRE_RELEASE_6: McVersion = pre_release("1.20-pre6", 3460);
+pub const JE_1_20_PRE_RELEASE_5: McVersion = pre_release("1.20-pre5", 3458);
+pub const JE_1_20_PRE_RELEASE_4: McVersion = pre_release("1.20-pre4", 3457);
+pub const JE_1_20_PRE_RELEASE_3: McVersion = pre_release("1.20-pre3", 3456);
+pub const JE_1_20_PRE_RELEASE_2: McVersion = pre_release("1.20-pre2", 3455);
+pub const JE_1_20_PRE_RELEASE_1: McVersion = pre_release("1.20-pre1", 3454);
+pub const JE_23W18A: McVersion = snapshot("23w18a", 3453);
+pub const JE_23W17A: McVersion = snapshot("23w17a", 3452);
+pub const JE_23W16A: McVersion = snapshot("23w16a", 3449);
+pub const JE_23W14A: McVersion = snapshot("23w14a", 3445);
+pub const JE_23W13A: McVersion = snapshot("23w13a", 3443);
+pub const JE_23W12A: McVersion = snapshot("23w12a", 3442);
+pub const JE_1_19_4: McVersion = release("1.19.4", 3337);
+pub const JE_1_19_4_RELEASE_CANDIDATE_3: McVersion = release_candidate("1.19.4-rc3", 3336);
+pub const JE_1_19_4_RELEASE_CANDIDATE_2: McVersion = release_candidate("1.19.4-rc2", 3335);
+pub const JE_1_19_4_RELEASE_CANDIDATE_1: McVersion = release_candidate("1.19.4-rc1", 3334);
+pub const JE_1_19_4_PRE_RELEASE_4: McVersion = pre_release("1.19.4-pre4", 3333);
+pub const JE_1_19_4_PRE_RELEASE_3: McVersion = pre_release("1.19.4-pre3", 3332);
+pub const JE_1_19_4_PRE_RELEASE_2: McVersion = pre_release("1.19.4-pre2", 3331);
+pub const JE_1_19_4_PRE_RELEASE_1: McVersion = pre_release("1.19.4-pre1", 3330);
+pub const JE_23W07A: McVersion = snapshot("23w07a", 3329);
+pub const JE_23W06A: McVersion = snapshot("23w06a", 3326);
+pub const JE_23W05A: McVersion = snapshot("23w05a", 3323);
+pub const JE_23W04A: McVersion = snapshot("23w04a", 3321);
+pub const JE_23W03A: McVersion = snapshot("23w03a", 3320);
+pub const JE_1_19_3: McVersion = release("1.19.3", 3218);
+pub const JE_1_19_3_RELEASE_CANDIDATE_3: McVersion = release_candidate("1.19.3-rc3", 3217);
+pub const JE_1_19_3_RELEASE_CANDIDATE_2: McVersion = release_candidate("1.19.3-rc2", 3216);
+pub const JE_1_19_3_RELEASE_CANDIDATE_1: McVersion = release_candidate("1.19.3-rc1", 3215);
+pub const JE_1_19_3_PRE_RELEASE_3: McVersion = pre_release("1.19.3-pre3", 3213);
+pub const JE_1_19_3_PRE_RELEASE_2: McVersion = pre_release("1.19.3-pre2", 3212);
+pub const JE_1_19_3_PRE_RELEASE_1: McVersion = pre_release("1.19.3-pre1", 3211);
+pub const JE_22W46A: McVersion = snapshot("22w46a", 3210);
+pub const JE_22W45A: McVersion = snapshot("22w45a", 3208);
+pub const JE_22W44A: McVersion = snapshot("22w44a", 3207);
+pub const JE_22W43A: McVersion = snapshot("22w43a", 3206);
+pub const JE_22W42A: McVersion = snapshot("22w42a", 3205);
+pub const JE_1_19_2: McVersion = release("1.19.2", 3120);
+pub const JE_1_19_2_RELEASE_CANDIDATE_2: McVersion = release_candidate("1.19.2-rc2", 3119);
+pub const JE_1_19_2_RELEASE_CANDIDATE_1: McVersion = release_candidate("1.19.2-rc1", 3118);
+pub const JE_1_19_1: McVersion = release("1.19.1", 3117);
+pub const JE_1_19_1_RELEASE_CANDIDATE_3: McVersion = release_candidate("1.19.1-rc3", 3116);
+pub const JE_1_19_1_RELEASE_CANDIDATE_2: McVersion = release_candidate("1.19.1-rc2", 3115);
+pub const JE_1_19_1_PRE_RELEASE_6: McVersion = pre_release("1.19.1-pre6", 3114);
+pub const JE_1_19_1_PRE_RELEASE_5: McVersion = pre_release("1.19.1-pre5", 3113);
+pub const JE_1_19_1_PRE_RELEASE_4: McVersion = pre_release("1.19.1-pre4", 3112);
+pub const JE_1_19_1_PRE_RELEASE_3: McVersion = pre_release("1.19.1-pre3", 3111);
+pub const JE_1_19_1_PRE_RELEASE_2: McVersion = pre_release("1.19.1-pre2", 3110);
+pub const JE_1_19_1_RELEASE_CANDIDATE_1: McVersion = release_candidate("1.19.1-rc1", 3109);
+pub const JE_1_19_1_PRE_RELEASE_1: McVersion = pre_release("1.19.1-pre1", 3107);
+pub const JE_22W24A: McVersion = snapshot("22w24a", 3106);
+pub const JE_1_19: McVersion = release("1.19", 3105);
+pub const JE_1_19_RELEASE_CANDIDATE_2: McVersion = release_candidate("1.19-rc2", 3104);
+pub const JE_1_19_RELEASE_CANDIDATE_1: McVersion = release_candidate("1.19-rc1", 3103);
+pub const JE_1_19_PRE_RELEASE_5: McVersion = pre_release("1.19-pre5", 3102);
+pub const JE_1_19_PRE_RELEASE_4: McVersion = pre_release("1.19-pre4", 3101);
+pub const JE_1_19_PRE_RELEASE_3: McVersion = pre_release("1.19-pre3", 3100);
+pub const JE_1_19_PRE_RELEASE_2: McVersion = pre_release("1.19-pre2", 3099);
+pub const JE_1_19_PRE_RELEASE_1: McVersion = pre_release("1.19-pre1", 3098);
+pub const JE_22W19A: McVersion = snapshot("22w19a", 3096);
+pub const JE_22W18A: McVersion = snapshot("22w18a", 3095);
+pub const JE_22W17A: McVersion = snapshot("22w17a", 3093);
+pub const JE_22W16B: McVersion = snapshot("22w16b", 3092);
+pub const JE_22W16A: McVersion = snapshot("22w16a", 3091);
+pub const JE_22W15A: McVersion = snapshot("22w15a", 3089);
+pub const JE_22W14A: McVersion = snapshot("22w14a", 3088);
+pub const JE_22W13A: McVersion = snapshot("22w13a", 3085);
+pub const JE_22W12A: McVersion = snapshot("22w12a", 3082);
+pub const JE_22W11A: McVersion = snapshot("22w11a", 3080);
+pub const JE_DEEP_DARK_EXPERIMENTAL_SNAPSHOT_1: McVersion =
+    experimental("Deep Dark Experimental Snapshot 1", 3066);
+pub const JE_1_18_2: McVersion = release("1.18.2", 2975);
+pub const JE_1_18_2_RELEASE_CANDIDATE_1: McVersion = release_candidate("1.18.2-rc1", 2974);
+pub const JE_1_18_2_PRE_RELEASE_3: McVersion = pre_release("1.18.2-pre3", 2973);
+pub const JE_1_18_2_PRE_RELEASE_2: McVersion = pre_release("1.18.2-pre2", 2972);
+pub const JE_1_18_2_PRE_RELEASE_1: McVersion = pre_release("1.18.2-pre1", 2971);
+pub const JE_22W07A: McVersion = snapshot("22w07a", 2969);
+pub const JE_22W06A: McVersion = snapshot("22w06a", 2968);
+pub const JE_22W05A: McVersion = snapshot("22w05a", 2967);
+pub const JE_22W03A: McVersion = snapshot("22w03a", 2966);
+pub const JE_1_18_1: McVersion = release("1.18.1", 2865);
+pub const JE_1_18_1_RELEASE_CANDIDATE_3: McVersion = release_candidate("1.18.1-rc3", 2864);
+pub const JE_1_18_1_RELEASE_CANDIDATE_2: McVersion = release_candidate("1.18.1-rc2", 2863);
+pub const JE_1_18_1_RELEASE_CANDIDATE_1: McVersion = release_candidate("1.18.1-rc1", 2862);
+pub const JE_1_18_1_PRE_RELEASE_1: McVersion = pre_release("1.18.1-pre1", 2861);
+pub const JE_1_18: McVersion = release("1.18", 2860);
+pub const JE_1_18_RELEASE_CANDIDATE_4: McVersion = release_candidate("1.18-rc4", 2859);
+pub const JE_1_18_RELEASE_CANDIDATE_3: McVersion = release_candidate("1.18-rc3", 2858);
+pub const JE_1_18_RELEASE_CANDIDATE_2: McVersion = release_candidate("1.18-rc2", 2857);
+pub const JE_1_18_RELEASE_CANDIDATE_1: McVersion = release_candidate("1.18-rc1", 2856);
+pub const JE_1_18_PRE_RELEASE_8: McVersion = pre_release("1.18-pre8", 2855);
+pub const JE_1_18_PRE_RELEASE_7: McVersion = pre_release("1.18-pre7", 2854);
+pub const JE_1_18_PRE_RELEASE_6: McVersion = pre_release("1.18-pre6", 2853);
+pub const JE_1_18_PRE_RELEASE_5: McVersion = pre_release("1.18-pre5", 2851);
+pub const JE_1_18_PRE_RELEASE_4: McVersion = pre_release("1.18-pre4", 2850);
+pub const JE_1_18_PRE_RELEASE_3: McVersion = pre_release("1.18-pre3", 2849);
+pub const JE_1_18_PRE_RELEASE_2: McVersion = pre_release("1.18-pre2", 2848);
+pub const JE_1_18_PRE_RELEASE_1: McVersion = pre_release("1.18-pre1", 2847);
+pub const JE_21W44A: McVersion = snapshot("21w44a", 2845);
+pub const JE_21W43A: McVersion = snapshot("21w43a", 2844);
+pub const JE_21W42A: McVersion = snapshot("21w42a", 2840);
+pub const JE_21W41A: McVersion = snapshot("21w41a", 2839);
+pub const JE_21W40A: McVersion = snapshot("21w40a", 2838);
+pub const JE_21W39A: McVersion = snapshot("21w39a", 2836);
+pub const JE_21W38A: McVersion = snapshot("21w38a", 2835);
+pub const JE_21W37A: McVersion = snapshot("21w37a", 2834);
+pub const JE_1_18_EXPERIMENTAL_SNAPSHOT_7: McVersion =
+    experimental("1.18 Experimental Snapshot 7", 2831);
+pub const JE_1_18_EXPERIMENTAL_SNAPSHOT_6: McVersion =
+    experimental("1.18 Experimental Snapshot 6", 2830);
+pub const JE_1_18_EXPERIMENTAL_SNAPSHOT_5: McVersion =
+    experimental("1.18 Experimental Snapshot 5", 2829);
+pub const JE_1_18_EXPERIMENTAL_SNAPSHOT_4: McVersion =
+    experimental("1.18 Experimental Snapshot 4", 2828);
+pub const JE_1_18_EXPERIMENTAL_SNAPSHOT_3: McVersion =
+    experimental("1.18 Experimental Snapshot 3", 2827);
+pub const JE_1_18_EXPERIMENTAL_SNAPSHOT_2: McVersion =
+    experimental("1.18 Experimental Snapshot 2", 2826);
+pub const JE_1_18_EXPERIMENTAL_SNAPSHOT_1: McVersion =
+    experimental("1.18 Experimental Snapshot 1", 2825);
+pub const JE_1_17_1: McVersion = release("1.17.1", 2730);
+pub const JE_1_17_1_RELEASE_CANDIDATE_2: McVersion = release_candidate("1.17.1-rc2", 2729);
+pub const JE_1_17_1_RELEASE_CANDIDATE_1: McVersion = release_candidate("1.17.1-rc1", 2728);
+pub const JE_1_17_1_PRE_RELEASE_3: McVersion = pre_release("1.17.1-pre3", 2727);
+pub const JE_1_17_1_PRE_RELEASE_2: McVersion = pre_release("1.17.1-pre2", 2726);
+pub const JE_1_17_1_PRE_RELEASE_1: McVersion = pre_release("1.17.1-pre1", 2725);
+pub const JE_1_17: McVersion = release("1.17", 2724);
+pub const JE_1_17_RELEASE_CANDIDATE_2: McVersion = release_candidate("1.17-rc2", 2723);
+pub const JE_1_17_RELEASE_CANDIDATE_1: McVersion = release_candidate("1.17-rc1", 2722);
+pub const JE_1_17_PRE_RELEASE_5: McVersion = pre_release("1.17-pre5", 2721);
+pub const JE_1_17_PRE_RELEASE_4: McVersion = pre_release("1.17-pre4", 2720);
+pub const JE_1_17_PRE_RELEASE_3: McVersion = pre_release("1.17-pre3", 2719);
+pub const JE_1_17_PRE_RELEASE_2: McVersion = pre_release("1.17-pre2", 2718);
+pub const JE_1_17_PRE_RELEASE_1: McVersion = pre_release("1.17-pre1", 2716);
+pub const JE_21W20A: McVersion = snapshot("21w20a", 2715);
+pub const JE_21W19A: McVersion = snapshot("21w19a", 2714);
+pub const JE_21W18A: McVersion = snapshot("21w18a", 2713);
+pub const JE_21W17A: McVersion = snapshot("21w17a", 2712);
+pub const JE_21W16A: McVersion = snapshot("21w16a", 2711);
+pub const JE_21W15A: McVersion = snapshot("21w15a", 2709);
+pub const JE_21W14A: McVersion = snapshot("21w14a", 2706);
+pub const JE_21W13A: McVersion = snapshot("21w13a", 2705);
+pub const JE_21W11A: McVersion = snapshot("21w11a", 2703);
+pub const JE_21W10A: McVersion = snapshot("21w10a", 2699);
+pub const JE_21W08B: McVersion = snapshot("21w08b", 2698);
+pub const JE_21W08A: McVersion = snapshot("21w08a", 2697);
+pub const JE_21W07A: McVersion = snapshot("21w07a", 2695);
+pub const JE_21W06A: McVersion = snapshot("21w06a", 2694);
+pub const JE_21W05B: McVersion = snapshot("21w05b", 2692);
+pub const JE_21W05A: McVersion = snapshot("21w05a", 2690);
+pub const JE_21W03A: McVersion = snapshot("21w03a", 2689);
+pub const JE_20W51A: McVersion = snapshot("20w51a", 2687);
+pub const JE_20W49A: McVersion = snapshot("20w49a", 2685);
+pub const JE_20W48A: McVersion = snapshot("20w48a", 2683);
+pub const JE_20W46A: McVersion = snapshot("20w46a", 2682);
+pub const JE_20W45A: McVersion = snapshot("20w45a", 2681);
+pub const JE_COMBAT_TEST_8C: McVersion = experimental("Combat Test 8c", 2707);
+pub const JE_COMBAT_TEST_8B: McVersion = experimental("Combat Test 8b", 2706);
+pub const JE_COMBAT_TEST_8: McVersion = experimental("Combat Test 8", 2705);
+pub const JE_COMBAT_TEST_7C: McVersion = experimental("Combat Test 7c", 2704);
+pub const JE_COMBAT_TEST_7B: McVersion = experimental("Combat Test 7b", 2703);
+pub const JE_COMBAT_TEST_7: McVersion = experimental("Combat Test 7", 2702);
+pub const JE_COMBAT_TEST_6: McVersion = experimental("Combat Test 6", 2701);
+pub const JE_1_16_5: McVersion = release("1.16.5", 2586);
+pub const JE_1_16_5_RELEASE_CANDIDATE_1: McVersion = release_candidate("1.16.5-rc1", 2585);
+pub const JE_1_16_4: McVersion = release("1.16.4", 2584);
+pub const JE_1_16_4_RELEASE_CANDIDATE_1: McVersion = release_candidate("1.16.4-rc1", 2583);
+pub const JE_1_16_4_PRE_RELEASE_2: McVersion = pre_release("1.16.4-pre2", 2582);
+pub const JE_1_16_4_PRE_RELEASE_1: McVersion = pre_release("1.16.4-pre1", 2581);
+pub const JE_1_16_3: McVersion = release("1.16.3", 2580);
+pub const JE_1_16_3_RELEASE_CANDIDATE_1: McVersion = release_candidate("1.16.3-rc1", 2579);
+pub const JE_1_16_2: McVersion = release("1.16.2", 2578);
+pub const JE_1_16_2_RELEASE_CANDIDATE_2: McVersion = release_candidate("1.16.2-rc2", 2577);
+pub const JE_1_16_2_RELEASE_CANDIDATE_1: McVersion = release_candidate("1.16.2-rc1", 2576);
+pub const JE_1_16_2_PRE_RELEASE_3: McVersion = pre_release("1.16.2-pre3", 2575);
+pub const JE_1_16_2_PRE_RELEASE_2: McVersion = pre_release("1.16.2-pre2", 2574);
+pub const JE_1_16_2_PRE_RELEASE_1: McVersion = pre_release("1.16.2-pre1", 2573);
+pub const JE_20W30A: McVersion = snapshot("20w30a", 2572);
+pub const JE_20W29A: McVersion = snapshot("20w29a", 2571);
+pub const JE_20W28A: McVersion = snapshot("20w28a", 2570);
+pub const JE_20W27A: McVersion = snapshot("20w27a", 2569);
+pub const JE_1_16_1: McVersion = release("1.16.1", 2567);
+pub const JE_1_16: McVersion = release("1.16", 2566);
+pub const JE_1_16_RELEASE_CANDIDATE_1: McVersion = release_candidate("1.16-rc1", 2565);
+pub const JE_1_16_PRE_RELEASE_8: McVersion = pre_release("1.16-pre8", 2564);
+pub const JE_1_16_PRE_RELEASE_7: McVersion = pre_release("1.16-pre7", 2563);
+pub const JE_1_16_PRE_RELEASE_6: McVersion = pre_release("1.16-pre6", 2562);
+pub const JE_1_16_PRE_RELEASE_5: McVersion = pre_release("1.16-pre5", 2561);
+pub const JE_1_16_PRE_RELEASE_4: McVersion = pre_release("1.16-pre4", 2560);
+pub const JE_1_16_PRE_RELEASE_3: McVersion = pre_release("1.16-pre3", 2559);
+pub const JE_1_16_PRE_RELEASE_2: McVersion = pre_release("1.16-pre2", 2557);
+pub const JE_1_16_PRE_RELEASE_1: McVersion = pre_release("1.16-pre1", 2556);
+pub const JE_20W22A: McVersion = snapshot("20w22a", 2555);
+pub const JE_20W21A: McVersion = snapshot("20w21a", 2554);
+pub const JE_20W20B: McVersion = snapshot("20w20b", 2537);
+pub const JE_20W20A: McVersion = snapshot("20w20a", 2536);
+pub const JE_20W19A: McVersion = snapshot("20w19a", 2534);
+pub const JE_20W18A: McVersion = snapshot("20w18a", 2532);
+pub const JE_20W17A: McVersion = snapshot("20w17a", 2529);
+pub const JE_20W16A: McVersion = snapshot("20w16a", 2526);
+pub const JE_20W15A: McVersion = snapshot("20w15a", 2525);
+pub const JE_20W14A: McVersion = snapshot("20w14a", 2524);
+pub const JE_20W13B: McVersion = snapshot("20w13b", 2521);
+pub const JE_20W13A: McVersion = snapshot("20w13a", 2520);
+pub const JE_20W12A: McVersion = snapshot("20w12a", 2515);
+pub const JE_20W11A: McVersion = snapshot("20w11a", 2513);
+pub const JE_20W10A: McVersion = snapshot("20w10a", 2512);
+pub const JE_20W09A: McVersion = snapshot("20w09a", 2510);
+pub const JE_20W08A: McVersion = snapshot("20w08a", 2507);
+pub const JE_20W07A: McVersion = snapshot("20w07a", 2506);
+pub const JE_SNAPSHOT_20W06A: McVersion = snapshot("20w06a", 2504);
+pub const JE_COMBAT_TEST_5: McVersion = experimental("Combat Test 5", 2321);
+pub const JE_COMBAT_TEST_4: McVersion = experimental("Combat Test 4", 2320);
+pub const JE_1_15_2: McVersion = release("1.15.2", 2230);
+pub const JE_1_15_2_PRE_RELEASE_2: McVersion = pre_release("1.15.2-pre2", 2229);
+pub const JE_1_15_2_PRE_RELEASE_1: McVersion = pre_release("1.15.2-pre1", 2228);
+pub const JE_1_15_1: McVersion = release("1.15.1", 2227);
+pub const JE_1_15_1_PRE_RELEASE_1: McVersion = pre_release("1.15.1-pre1", 2226);
+pub const JE_1_15: McVersion = release("1.15", 2225);
+pub const JE_1_15_PRE_RELEASE_7: McVersion = pre_release("1.15-pre7", 2224);
+pub const JE_1_15_PRE_RELEASE_6: McVersion = pre_release("1.15-pre6", 2223);
+pub const JE_1_15_PRE_RELEASE_5: McVersion = pre_release("1.15-pre5", 2222);
+pub const JE_1_15_PRE_RELEASE_4: McVersion = pre_release("1.15-pre4", 2221);
+pub const JE_1_15_PRE_RELEASE_3: McVersion = pre_release("1.15-pre3", 2220);
+pub const JE_1_15_PRE_RELEASE_2: McVersion = pre_release("1.15-pre2", 2219);
+pub const JE_1_15_PRE_RELEASE_1: McVersion = pre_release("1.15-pre1", 2218);
+pub const JE_19W46B: McVersion = snapshot("19w46b", 2217);
+pub const JE_19W46A: McVersion = snapshot("19w46a", 2216);
+pub const JE_19W45B: McVersion = snapshot("19w45b", 2215);
+pub const JE_19W45A: McVersion = snapshot("19w45a", 2214);
+pub const JE_19W44A: McVersion = snapshot("19w44a", 2213);
+pub const JE_19W42A: McVersion = snapshot("19w42a", 2212);
+pub const JE_19W41A: McVersion = snapshot("19w41a", 2210);
+pub const JE_19W40A: McVersion = snapshot("19w40a", 2208);
+pub const JE_19W39A: McVersion = snapshot("19w39a", 2207);
+pub const JE_19W38B: McVersion = snapshot("19w38b", 2206);
+pub const JE_19W38A: McVersion = snapshot("19w38a", 2205);
+pub const JE_19W37A: McVersion = snapshot("19w37a", 2204);
+pub const JE_19W36A: McVersion = snapshot("19w36a", 2203);
+pub const JE_19W35A: McVersion = snapshot("19w35a", 2201);
+pub const JE_19W34A: McVersion = snapshot("19w34a", 2200);
+pub const JE_COMBAT_TEST_3: McVersion = experimental("Combat Test 3", 2069);
+pub const JE_COMBAT_TEST_2: McVersion = experimental("Combat Test 2", 2068);
+pub const JE_1_14_3___COMBAT_TEST: McVersion = experimental("1.14.3 - Combat Test", 2067);
+pub const JE_1_14_4: McVersion = release("1.14.4", 1976);
+pub const JE_1_14_4_PRE_RELEASE_7: McVersion = pre_release("1.14.4-pre7", 1975);
+pub const JE_1_14_4_PRE_RELEASE_6: McVersion = pre_release("1.14.4-pre6", 1974);
+pub const JE_1_14_4_PRE_RELEASE_5: McVersion = pre_release("1.14.4-pre5", 1973);
+pub const JE_1_14_4_PRE_RELEASE_4: McVersion = pre_release("1.14.4-pre4", 1972);
+pub const JE_1_14_4_PRE_RELEASE_3: McVersion = pre_release("1.14.4-pre3", 1971);
+pub const JE_1_14_4_PRE_RELEASE_2: McVersion = pre_release("1.14.4-pre2", 1970);
+pub const JE_1_14_4_PRE_RELEASE_1: McVersion = pre_release("1.14.4-pre1", 1969);
+pub const JE_1_14_3: McVersion = release("1.14.3", 1968);
+pub const JE_1_14_3_PRE_RELEASE_4: McVersion = pre_release("1.14.3-pre4", 1967);
+pub const JE_1_14_3_PRE_RELEASE_3: McVersion = pre_release("1.14.3-pre3", 1966);
+pub const JE_1_14_3_PRE_RELEASE_2: McVersion = pre_release("1.14.3-pre2", 1965);
+pub const JE_1_14_3_PRE_RELEASE_1: McVersion = pre_release("1.14.3-pre1", 1964);
+pub const JE_1_14_2: McVersion = release("1.14.2", 1963);
+pub const JE_1_14_2_PRE_RELEASE_4: McVersion = pre_release("1.14.2-pre4", 1962);
+pub const JE_1_14_2_PRE_RELEASE_3: McVersion = pre_release("1.14.2-pre3", 1960);
+pub const JE_1_14_2_PRE_RELEASE_2: McVersion = pre_release("1.14.2-pre2", 1959);
+pub const JE_1_14_2_PRE_RELEASE_1: McVersion = pre_release("1.14.2-pre1", 1958);
+pub const JE_1_14_1: McVersion = release("1.14.1", 1957);
+pub const JE_1_14_1_PRE_RELEASE_2: McVersion = pre_release("1.14.1-pre2", 1956);
+pub const JE_1_14_1_PRE_RELEASE_1: McVersion = pre_release("1.14.1-pre1", 1955);
+pub const JE_1_14: McVersion = release("1.14", 1952);
+pub const JE_1_14_PRE_RELEASE_5: McVersion = pre_release("1.14-pre5", 1951);
+pub const JE_1_14_PRE_RELEASE_4: McVersion = pre_release("1.14-pre4", 1950);
+pub const JE_1_14_PRE_RELEASE_3: McVersion = pre_release("1.14-pre3", 1949);
+pub const JE_1_14_PRE_RELEASE_2: McVersion = pre_release("1.14-pre2", 1948);
+pub const JE_1_14_PRE_RELEASE_1: McVersion = pre_release("1.14-pre1", 1947);
+pub const JE_19W14B: McVersion = snapshot("19w14b", 1945);
+pub const JE_19W14A: McVersion = snapshot("19w14a", 1944);
+pub const JE_19W13B: McVersion = snapshot("19w13b", 1943);
+pub const JE_19W13A: McVersion = snapshot("19w13a", 1942);
+pub const JE_19W12B: McVersion = snapshot("19w12b", 1941);
+pub const JE_19W12A: McVersion = snapshot("19w12a", 1940);
+pub const JE_19W11B: McVersion = snapshot("19w11b", 1938);
+pub const JE_19W11A: McVersion = snapshot("19w11a", 1937);
+pub const JE_19W09A: McVersion = snapshot("19w09a", 1935);
+pub const JE_19W08B: McVersion = snapshot("19w08b", 1934);
+pub const JE_19W08A: McVersion = snapshot("19w08a", 1933);
+pub const JE_19W07A: McVersion = snapshot("19w07a", 1932);
+pub const JE_19W06A: McVersion = snapshot("19w06a", 1931);
+pub const JE_19W05A: McVersion = snapshot("19w05a", 1930);
+pub const JE_19W04B: McVersion = snapshot("19w04b", 1927);
+pub const JE_19W04A: McVersion = snapshot("19w04a", 1926);
+pub const JE_19W03C: McVersion = snapshot("19w03c", 1924);
+pub const JE_19W03B: McVersion = snapshot("19w03b", 1923);
+pub const JE_19W03A: McVersion = snapshot("19w03a", 1922);
+pub const JE_19W02A: McVersion = snapshot("19w02a", 1921);
+pub const JE_18W50A: McVersion = snapshot("18w50a", 1919);
+pub const JE_18W49A: McVersion = snapshot("18w49a", 1916);
+pub const JE_18W48B: McVersion = snapshot("18w48b", 1915);
+pub const JE_18W48A: McVersion = snapshot("18w48a", 1914);
+pub const JE_18W47B: McVersion = snapshot("18w47b", 1913);
+pub const JE_18W47A: McVersion = snapshot("18w47a", 1912);
+pub const JE_18W46A: McVersion = snapshot("18w46a", 1910);
+pub const JE_18W45A: McVersion = snapshot("18w45a", 1908);
+pub const JE_18W44A: McVersion = snapshot("18w44a", 1907);
+pub const JE_18W43C: McVersion = snapshot("18w43c", 1903);
+pub const JE_18W43B: McVersion = snapshot("18w43b", 1902);
+pub const JE_18W43A: McVersion = snapshot("18w43a", 1901);
+pub const JE_1_13_2: McVersion = release("1.13.2", 1631);
+pub const JE_1_13_2_PRE2: McVersion = pre_release("1.13.2-pre2", 1630);
+pub const JE_1_13_2_PRE1: McVersion = pre_release("1.13.2-pre1", 1629);
+pub const JE_1_13_1: McVersion = release("1.13.1", 1628);
+pub const JE_1_13_1_PRE2: McVersion = pre_release("1.13.1-pre2", 1627);
+pub const JE_1_13_1_PRE1: McVersion = pre_release("1.13.1-pre1", 1626);
+pub const JE_18W33A: McVersion = snapshot("18w33a", 1625);
+pub const JE_18W32A: McVersion = snapshot("18w32a", 1623);
+pub const JE_18W31A: McVersion = snapshot("18w31a", 1622);
+pub const JE_18W30B: McVersion = snapshot("18w30b", 1621);
+pub const JE_18W30A: McVersion = snapshot("18w30a", 1620);
+pub const JE_1_13: McVersion = release("1.13", 1519);
+pub const JE_1_13_PRE10: McVersion = pre_release("1.13-pre10", 1518);
+pub const JE_1_13_PRE9: McVersion = pre_release("1.13-pre9", 1517);
+pub const JE_1_13_PRE8: McVersion = pre_release("1.13-pre8", 1516);
+pub const JE_1_13_PRE7: McVersion = pre_release("1.13-pre7", 1513);
+pub const JE_1_13_PRE6: McVersion = pre_release("1.13-pre6", 1512);
+pub const JE_1_13_PRE5: McVersion = pre_release("1.13-pre5", 1511);
+pub const JE_1_13_PRE4: McVersion = pre_release("1.13-pre4", 1504);
+pub const JE_1_13_PRE3: McVersion = pre_release("1.13-pre3", 1503);
+pub const JE_1_13_PRE2: McVersion = pre_release("1.13-pre2", 1502);
+pub const JE_1_13_PRE1: McVersion = pre_release("1.13-pre1", 1501);
+pub const JE_18W22C: McVersion = snapshot("18w22c", 1499);
+pub const JE_18W22B: McVersion = snapshot("18w22b", 1498);
+pub const JE_18W22A: McVersion = snapshot("18w22a", 1497);
+pub const JE_18W21B: McVersion = snapshot("18w21b", 1496);
+pub const JE_18W21A: McVersion = snapshot("18w21a", 1495);
+pub const JE_18W20C: McVersion = snapshot("18w20c", 1493);
+pub const JE_18W20B: McVersion = snapshot("18w20b", 1491);
+pub const JE_18W20A: McVersion = snapshot("18w20a", 1489);
+pub const JE_18W19B: McVersion = snapshot("18w19b", 1485);
+pub const JE_18W19A: McVersion = snapshot("18w19a", 1484);
+pub const JE_18W16A: McVersion = snapshot("18w16a", 1483);
+pub const JE_18W15A: McVersion = snapshot("18w15a", 1482);
+pub const JE_18W14B: McVersion = snapshot("18w14b", 1481);
+pub const JE_18W14A: McVersion = snapshot("18w14a", 1479);
+pub const JE_18W11A: McVersion = snapshot("18w11a", 1478);
+pub const JE_18W10D: McVersion = snapshot("18w10d", 1477);
+pub const JE_18W10C: McVersion = snapshot("18w10c", 1476);
+pub const JE_18W10B: McVersion = snapshot("18w10b", 1474);
+pub const JE_18W10A: McVersion = snapshot("18w10a", 1473);
+pub const JE_18W09A: McVersion = snapshot("18w09a", 1472);
+pub const JE_18W08B: McVersion = snapshot("18w08b", 1471);
+pub const JE_18W08A: McVersion = snapshot("18w08a", 1470);
+pub const JE_18W07C: McVersion = snapshot("18w07c", 1469);
+pub const JE_18W07B: McVersion = snapshot("18w07b", 1468);
+pub const JE_18W07A: McVersion = snapshot("18w07a", 1467);
+pub const JE_18W06A: McVersion = snapshot("18w06a", 1466);
+pub const JE_18W05A: McVersion = snapshot("18w05a", 1464);
+pub const JE_18W03B: McVersion = snapshot("18w03b", 1463);
+pub const JE_18W03A: McVersion = snapshot("18w03a", 1462);
+pub const JE_18W02A: McVersion = snapshot("18w02a", 1461);
+pub const JE_18W01A: McVersion = snapshot("18w01a", 1459);
+pub const JE_17W50A: McVersion = snapshot("17w50a", 1457);
+pub const JE_17W49B: McVersion = snapshot("17w49b", 1455);
+pub const JE_17W49A: McVersion = snapshot("17w49a", 1454);
+pub const JE_17W48A: McVersion = snapshot("17w48a", 1453);
+pub const JE_17W47B: McVersion = snapshot("17w47b", 1452);
+pub const JE_17W47A: McVersion = snapshot("17w47a", 1451);
+pub const JE_17W46A: McVersion = snapshot("17w46a", 1449);
+pub const JE_17W45B: McVersion = snapshot("17w45b", 1448);
+pub const JE_17W45A: McVersion = snapshot("17w45a", 1447);
+pub const JE_17W43B: McVersion = snapshot("17w43b", 1445);
+pub const JE_17W43A: McVersion = snapshot("17w43a", 1444);
+pub const JE_1_12_2: McVersion = release("1.12.2", 1343);
+pub const JE_1_12_2_PRE2: McVersion = pre_release("1.12.2-pre2", 1342);
+pub const JE_1_12_2_PRE1: McVersion = pre_release("1.12.2-pre1", 1341);
+pub const JE_1_12_1: McVersion = release("1.12.1", 1241);
+pub const JE_1_12_1_PRE1: McVersion = pre_release("1.12.1-pre1", 1240);
+pub const JE_17W31A: McVersion = snapshot("17w31a", 1239);
+pub const JE_1_12: McVersion = release("1.12", 1139);
+pub const JE_1_12_PRE7: McVersion = pre_release("1.12-pre7", 1138);
+pub const JE_1_12_PRE6: McVersion = pre_release("1.12-pre6", 1137);
+pub const JE_1_12_PRE5: McVersion = pre_release("1.12-pre5", 1136);
+pub const JE_1_12_PRE4: McVersion = pre_release("1.12-pre4", 1135);
+pub const JE_1_12_PRE3: McVersion = pre_release("1.12-pre3", 1134);
+pub const JE_1_12_PRE2: McVersion = pre_release("1.12-pre2", 1133);
+pub const JE_1_12_PRE1: McVersion = pre_release("1.12-pre1", 1132);
+pub const JE_17W18B: McVersion = snapshot("17w18b", 1131);
+pub const JE_17W18A: McVersion = snapshot("17w18a", 1130);
+pub const JE_17W17B: McVersion = snapshot("17w17b", 1129);
+pub const JE_17W17A: McVersion = snapshot("17w17a", 1128);
+pub const JE_17W16B: McVersion = snapshot("17w16b", 1127);
+pub const JE_17W16A: McVersion = snapshot("17w16a", 1126);
+pub const JE_17W15A: McVersion = snapshot("17w15a", 1125);
+pub const JE_17W14A: McVersion = snapshot("17w14a", 1124);
+pub const JE_17W13B: McVersion = snapshot("17w13b", 1123);
+pub const JE_17W13A: McVersion = snapshot("17w13a", 1122);
+pub const JE_17W06A: McVersion = snapshot("17w06a", 1022);
+pub const JE_1_11_2: McVersion = release("1.11.2", 922);
+pub const JE_1_11_1: McVersion = release("1.11.1", 921);
+pub const JE_16W50A: McVersion = snapshot("16w50a", 920);
+pub const JE_1_11: McVersion = release("1.11", 819);
+pub const JE_1_11_PRE1: McVersion = pre_release("1.11-pre1", 818);
+pub const JE_16W44A: McVersion = snapshot("16w44a", 817);
+pub const JE_16W43A: McVersion = snapshot("16w43a", 816);
+pub const JE_16W42A: McVersion = snapshot("16w42a", 815);
+pub const JE_16W41A: McVersion = snapshot("16w41a", 814);
+pub const JE_16W40A: McVersion = snapshot("16w40a", 813);
+pub const JE_16W39C: McVersion = snapshot("16w39c", 812);
+pub const JE_16W39B: McVersion = snapshot("16w39b", 811);
+pub const JE_16W39A: McVersion = snapshot("16w39a", 809);
+pub const JE_16W38A: McVersion = snapshot("16w38a", 807);
+pub const JE_16W36A: McVersion = snapshot("16w36a", 805);
+pub const JE_16W35A: McVersion = snapshot("16w35a", 803);
+pub const JE_16W33A: McVersion = snapshot("16w33a", 802);
+pub const JE_16W32B: McVersion = snapshot("16w32b", 801);
+pub const JE_16W32A: McVersion = snapshot("16w32a", 800);
+pub const JE_1_10_2: McVersion = release("1.10.2", 512);
+pub const JE_1_10_1: McVersion = release("1.10.1", 511);
+pub const JE_1_10: McVersion = release("1.10", 510);
+pub const JE_1_10_PRE2: McVersion = pre_release("1.10-pre2", 507);
+pub const JE_1_10_PRE1: McVersion = pre_release("1.10-pre1", 506);
+pub const JE_16W21B: McVersion = snapshot("16w21b", 504);
+pub const JE_16W21A: McVersion = snapshot("16w21a", 503);
+pub const JE_16W20A: McVersion = snapshot("16w20a", 501);
+pub const JE_1_9_4: McVersion = release("1.9.4", 184);
+pub const JE_1_9_3: McVersion = release("1.9.3", 183);
+pub const JE_1_9_3_PRE3: McVersion = pre_release("1.9.3-pre3", 182);
+pub const JE_1_9_3_PRE2: McVersion = pre_release("1.9.3-pre2", 181);
+pub const JE_1_9_3_PRE1: McVersion = pre_release("1.9.3-pre1", 180);
+pub const JE_16W15B: McVersion = snapshot("16w15b", 179);
+pub const JE_16W15A: McVersion = snapshot("16w15a", 178);
+pub const JE_16W14A: McVersion = snapshot("16w14a", 177);
+pub const JE_1_9_2: McVersion = release("1.9.2", 176);
+pub const JE_1_9_1: McVersion = release("1.9.1", 175);
+pub const JE_1_9_1_PRE3: McVersion = pre_release("1.9.1-pre3", 172);
+pub const JE_1_9_1_PRE2: McVersion = pre_release("1.9.1-pre2", 171);
+pub const JE_1_9_1_PRE1: McVersion = pre_release("1.9.1-pre1", 170);
+pub const JE_1_9: McVersion = release("1.9", 169);
+pub const JE_1_9_PRE4: McVersion = pre_release("1.9-pre4", 168);
+pub const JE_1_9_PRE3: McVersion = pre_release("1.9-pre3", 167);
+pub const JE_1_9_PRE2: McVersion = pre_release("1.9-pre2", 165);
+pub const JE_1_9_PRE1: McVersion = pre_release("1.9-pre1", 164);
+pub const JE_16W07B: McVersion = snapshot("16w07b", 163);
+pub const JE_16W07A: McVersion = snapshot("16w07a", 162);
+pub const JE_16W06A: McVersion = snapshot("16w06a", 161);
+pub const JE_16W05B: McVersion = snapshot("16w05b", 160);
+pub const JE_16W05A: McVersion = snapshot("16w05a", 159);
+pub const JE_16W04A: McVersion = snapshot("16w04a", 158);
+pub const JE_16W03A: McVersion = snapshot("16w03a", 157);
+pub const JE_16W02A: McVersion = snapshot("16w02a", 156);
+pub const JE_15W51B: McVersion = snapshot("15w51b", 155);
+pub const JE_15W51A: McVersion = snapshot("15w51a", 154);
+pub const JE_15W50A: McVersion = snapshot("15w50a", 153);
+pub const JE_15W49B: McVersion = snapshot("15w49b", 152);
+pub const JE_15W49A: McVersion = snapshot("15w49a", 151);
+pub const JE_15W47C: McVersion = snapshot("15w47c", 150);
+pub const JE_15W47B: McVersion = snapshot("15w47b", 149);
+pub const JE_15W47A: McVersion = snapshot("15w47a", 148);
+pub const JE_15W46A: McVersion = snapshot("15w46a", 146);
+pub const JE_15W45A: McVersion = snapshot("15w45a", 145);
+pub const JE_15W44B: McVersion = snapshot("15w44b", 143);
+pub const JE_15W44A: McVersion = snapshot("15w44a", 142);
+pub const JE_15W43C: McVersion = snapshot("15w43c", 141);
+pub const JE_15W43B: McVersion = snapshot("15w43b", 140);
+pub const JE_15W43A: McVersion = snapshot("15w43a", 139);
+pub const JE_15W42A: McVersion = snapshot("15w42a", 138);
+pub const JE_15W41B: McVersion = snapshot("15w41b", 137);
+pub const JE_15W41A: McVersion = snapshot("15w41a", 136);
+pub const JE_15W40B: McVersion = snapshot("15w40b", 134);
+pub const JE_15W40A: McVersion = snapshot("15w40a", 133);
+pub const JE_15W39C: McVersion = snapshot("15w39c", 132);
+pub const JE_15W39B: McVersion = snapshot("15w39b", 131);
+pub const JE_15W39A: McVersion = snapshot("15w39a", 130);
+pub const JE_15W38B: McVersion = snapshot("15w38b", 129);
+pub const JE_15W38A: McVersion = snapshot("15w38a", 128);
+pub const JE_15W37A: McVersion = snapshot("15w37a", 127);
+pub const JE_15W36D: McVersion = snapshot("15w36d", 126);
+pub const JE_15W36C: McVersion = snapshot("15w36c", 125);
+pub const JE_15W36B: McVersion = snapshot("15w36b", 124);
+pub const JE_15W36A: McVersion = snapshot("15w36a", 123);
+pub const JE_15W35E: McVersion = snapshot("15w35e", 122);
+pub const JE_15W35D: McVersion = snapshot("15w35d", 121);
+pub const JE_15W35C: McVersion = snapshot("15w35c", 120);
+pub const JE_15W35B: McVersion = snapshot("15w35b", 119);
+pub const JE_15W35A: McVersion = snapshot("15w35a", 118);
+pub const JE_15W34D: McVersion = snapshot("15w34d", 117);
+pub const JE_15W34C: McVersion = snapshot("15w34c", 116);
+pub const JE_15W34B: McVersion = snapshot("15w34b", 115);
+pub const JE_15W34A: McVersion = snapshot("15w34a", 114);
+pub const JE_15W33C: McVersion = snapshot("15w33c", 112);
+pub const JE_15W33B: McVersion = snapshot("15w33b", 111);
+pub const JE_15W33A: McVersion = snapshot("15w33a", 55);
+pub const JE_15W32C: McVersion = snapshot("15w32c", 104);
+pub const JE_15W32B: McVersion = snapshot("15w32b", 103);
+pub const JE_15W32A: McVersion = snapshot("15w32a", 100);
+
+pub const BE_1_20_10: BedrockVersion = bedrock("1.20.10", [1, 20, 10, 0]);
+pub const BE_1_20_0: BedrockVersion = bedrock("1.20.0", [1, 20, 0, 0]);
+pub const BE_1_19_80: BedrockVersion = bedrock("1.19.80", [1, 19, 80, 0]);
+pub const BE_1_19_70: BedrockVersion = bedrock("1.19.70", [1, 19, 70, 0]);
+pub const BE_1_19_50: BedrockVersion = bedrock("1.19.50", [1, 19, 50, 0]);
+pub const BE_1_19_0: BedrockVersion = bedrock("1.19.0", [1, 19, 0, 0]);
+pub const BE_1_18_30: BedrockVersion = bedrock("1.18.30", [1, 18, 30, 0]);
+pub const BE_1_18_0: BedrockVersion = bedrock("1.18.0", [1, 18, 0, 0]);
+pub const BE_1_17_0: BedrockVersion = bedrock("1.17.0", [1, 17, 0, 0]);
+pub const BE_1_16_100: BedrockVersion = bedrock("1.16.100", [1, 16, 100, 0]);
+
+/// Every known Java Edition version, newest first
+const ALL: &[McVersion] = &[
+    JE_1_20_1,
+    JE_1_20_1_RELEASE_CANDIDATE_1,
+    JE_1_20,
+    JE_1_20_RELEASE_CANDIDATE_1,
+    JE_1_20_PRE_RELEASE_7,
+    JE_1_20_PRE_RELEASE_6,
+    JE_1_20_PRE_RELEASE_5,
+    JE_1_20_PRE_RELEASE_4,
+    JE_1_20_PRE_RELEASE_3,
+    JE_1_20_PRE_RELEASE_2,
+    JE_1_20_PRE_RELEASE_1,
+    JE_23W18A,
+    JE_23W17A,
+    JE_23W16A,
+    JE_23W14A,
+    JE_23W13A,
+    JE_23W12A,
+    JE_1_19_4,
+    JE_1_19_4_RELEASE_CANDIDATE_3,
+    JE_1_19_4_RELEASE_CANDIDATE_2,
+    JE_1_19_4_RELEASE_CANDIDATE_1,
+    JE_1_19_4_PRE_RELEASE_4,
+    JE_1_19_4_PRE_RELEASE_3,
+    JE_1_19_4_PRE_RELEASE_2,
+    JE_1_19_4_PRE_RELEASE_1,
+    JE_23W07A,
+    JE_23W06A,
+    JE_23W05A,
+    JE_23W04A,
+    JE_23W03A,
+    JE_1_19_3,
+    JE_1_19_3_RELEASE_CANDIDATE_3,
+    JE_1_19_3_RELEASE_CANDIDATE_2,
+    JE_1_19_3_RELEASE_CANDIDATE_1,
+    JE_1_19_3_PRE_RELEASE_3,
+    JE_1_19_3_PRE_RELEASE_2,
+    JE_1_19_3_PRE_RELEASE_1,
+    JE_22W46A,
+    JE_22W45A,
+    JE_22W44A,
+    JE_22W43A,
+    JE_22W42A,
+    JE_1_19_2,
+    JE_1_19_2_RELEASE_CANDIDATE_2,
+    JE_1_19_2_RELEASE_CANDIDATE_1,
+    JE_1_19_1,
+    JE_1_19_1_RELEASE_CANDIDATE_3,
+    JE_1_19_1_RELEASE_CANDIDATE_2,
+    JE_1_19_1_PRE_RELEASE_6,
+    JE_1_19_1_PRE_RELEASE_5,
+    JE_1_19_1_PRE_RELEASE_4,
+    JE_1_19_1_PRE_RELEASE_3,
+    JE_1_19_1_PRE_RELEASE_2,
+    JE_1_19_1_RELEASE_CANDIDATE_1,
+    JE_1_19_1_PRE_RELEASE_1,
+    JE_22W24A,
+    JE_1_19,
+    JE_1_19_RELEASE_CANDIDATE_2,
+    JE_1_19_RELEASE_CANDIDATE_1,
+    JE_1_19_PRE_RELEASE_5,
+    JE_1_19_PRE_RELEASE_4,
+    JE_1_19_PRE_RELEASE_3,
+    JE_1_19_PRE_RELEASE_2,
+    JE_1_19_PRE_RELEASE_1,
+    JE_22W19A,
+    JE_22W18A,
+    JE_22W17A,
+    JE_22W16B,
+    JE_22W16A,
+    JE_22W15A,
+    JE_22W14A,
+    JE_22W13A,
+    JE_22W12A,
+    JE_22W11A,
+    JE_DEEP_DARK_EXPERIMENTAL_SNAPSHOT_1,
+    JE_1_18_2,
+    JE_1_18_2_RELEASE_CANDIDATE_1,
+    JE_1_18_2_PRE_RELEASE_3,
+    JE_1_18_2_PRE_RELEASE_2,
+    JE_1_18_2_PRE_RELEASE_1,
+    JE_22W07A,
+    JE_22W06A,
+    JE_22W05A,
+    JE_22W03A,
+    JE_1_18_1,
+    JE_1_18_1_RELEASE_CANDIDATE_3,
+    JE_1_18_1_RELEASE_CANDIDATE_2,
+    JE_1_18_1_RELEASE_CANDIDATE_1,
+    JE_1_18_1_PRE_RELEASE_1,
+    JE_1_18,
+    JE_1_18_RELEASE_CANDIDATE_4,
+    JE_1_18_RELEASE_CANDIDATE_3,
+    JE_1_18_RELEASE_CANDIDATE_2,
+    JE_1_18_RELEASE_CANDIDATE_1,
+    JE_1_18_PRE_RELEASE_8,
+    JE_1_18_PRE_RELEASE_7,
+    JE_1_18_PRE_RELEASE_6,
+    JE_1_18_PRE_RELEASE_5,
+    JE_1_18_PRE_RELEASE_4,
+    JE_1_18_PRE_RELEASE_3,
+    JE_1_18_PRE_RELEASE_2,
+    JE_1_18_PRE_RELEASE_1,
+    JE_21W44A,
+    JE_21W43A,
+    JE_21W42A,
+    JE_21W41A,
+    JE_21W40A,
+    JE_21W39A,
+    JE_21W38A,
+    JE_21W37A,
+    JE_1_18_EXPERIMENTAL_SNAPSHOT_7,
+    JE_1_18_EXPERIMENTAL_SNAPSHOT_6,
+    JE_1_18_EXPERIMENTAL_SNAPSHOT_5,
+    JE_1_18_EXPERIMENTAL_SNAPSHOT_4,
+    JE_1_18_EXPERIMENTAL_SNAPSHOT_3,
+    JE_1_18_EXPERIMENTAL_SNAPSHOT_2,
+    JE_1_18_EXPERIMENTAL_SNAPSHOT_1,
+    JE_1_17_1,
+    JE_1_17_1_RELEASE_CANDIDATE_2,
+    JE_1_17_1_RELEASE_CANDIDATE_1,
+    JE_1_17_1_PRE_RELEASE_3,
+    JE_1_17_1_PRE_RELEASE_2,
+    JE_1_17_1_PRE_RELEASE_1,
+    JE_1_17,
+    JE_1_17_RELEASE_CANDIDATE_2,
+    JE_1_17_RELEASE_CANDIDATE_1,
+    JE_1_17_PRE_RELEASE_5,
+    JE_1_17_PRE_RELEASE_4,
+    JE_1_17_PRE_RELEASE_3,
+    JE_1_17_PRE_RELEASE_2,
+    JE_1_17_PRE_RELEASE_1,
+    JE_21W20A,
+    JE_21W19A,
+    JE_21W18A,
+    JE_21W17A,
+    JE_21W16A,
+    JE_21W15A,
+    JE_21W14A,
+    JE_21W13A,
+    JE_21W11A,
+    JE_21W10A,
+    JE_21W08B,
+    JE_21W08A,
+    JE_21W07A,
+    JE_21W06A,
+    JE_21W05B,
+    JE_21W05A,
+    JE_21W03A,
+    JE_20W51A,
+    JE_20W49A,
+    JE_20W48A,
+    JE_20W46A,
+    JE_20W45A,
+    JE_COMBAT_TEST_8C,
+    JE_COMBAT_TEST_8B,
+    JE_COMBAT_TEST_8,
+    JE_COMBAT_TEST_7C,
+    JE_COMBAT_TEST_7B,
+    JE_COMBAT_TEST_7,
+    JE_COMBAT_TEST_6,
+    JE_1_16_5,
+    JE_1_16_5_RELEASE_CANDIDATE_1,
+    JE_1_16_4,
+    JE_1_16_4_RELEASE_CANDIDATE_1,
+    JE_1_16_4_PRE_RELEASE_2,
+    JE_1_16_4_PRE_RELEASE_1,
+    JE_1_16_3,
+    JE_1_16_3_RELEASE_CANDIDATE_1,
+    JE_1_16_2,
+    JE_1_16_2_RELEASE_CANDIDATE_2,
+    JE_1_16_2_RELEASE_CANDIDATE_1,
+    JE_1_16_2_PRE_RELEASE_3,
+    JE_1_16_2_PRE_RELEASE_2,
+    JE_1_16_2_PRE_RELEASE_1,
+    JE_20W30A,
+    JE_20W29A,
+    JE_20W28A,
+    JE_20W27A,
+    JE_1_16_1,
+    JE_1_16,
+    JE_1_16_RELEASE_CANDIDATE_1,
+    JE_1_16_PRE_RELEASE_8,
+    JE_1_16_PRE_RELEASE_7,
+    JE_1_16_PRE_RELEASE_6,
+    JE_1_16_PRE_RELEASE_5,
+    JE_1_16_PRE_RELEASE_4,
+    JE_1_16_PRE_RELEASE_3,
+    JE_1_16_PRE_RELEASE_2,
+    JE_1_16_PRE_RELEASE_1,
+    JE_20W22A,
+    JE_20W21A,
+    JE_20W20B,
+    JE_20W20A,
+    JE_20W19A,
+    JE_20W18A,
+    JE_20W17A,
+    JE_20W16A,
+    JE_20W15A,
+    JE_20W14A,
+    JE_20W13B,
+    JE_20W13A,
+    JE_20W12A,
+    JE_20W11A,
+    JE_20W10A,
+    JE_20W09A,
+    JE_20W08A,
+    JE_20W07A,
+    JE_SNAPSHOT_20W06A,
+    JE_COMBAT_TEST_5,
+    JE_COMBAT_TEST_4,
+    JE_1_15_2,
+    JE_1_15_2_PRE_RELEASE_2,
+    JE_1_15_2_PRE_RELEASE_1,
+    JE_1_15_1,
+    JE_1_15_1_PRE_RELEASE_1,
+    JE_1_15,
+    JE_1_15_PRE_RELEASE_7,
+    JE_1_15_PRE_RELEASE_6,
+    JE_1_15_PRE_RELEASE_5,
+    JE_1_15_PRE_RELEASE_4,
+    JE_1_15_PRE_RELEASE_3,
+    JE_1_15_PRE_RELEASE_2,
+    JE_1_15_PRE_RELEASE_1,
+    JE_19W46B,
+    JE_19W46A,
+    JE_19W45B,
+    JE_19W45A,
+    JE_19W44A,
+    JE_19W42A,
+    JE_19W41A,
+    JE_19W40A,
+    JE_19W39A,
+    JE_19W38B,
+    JE_19W38A,
+    JE_19W37A,
+    JE_19W36A,
+    JE_19W35A,
+    JE_19W34A,
+    JE_COMBAT_TEST_3,
+    JE_COMBAT_TEST_2,
+    JE_1_14_3___COMBAT_TEST,
+    JE_1_14_4,
+    JE_1_14_4_PRE_RELEASE_7,
+    JE_1_14_4_PRE_RELEASE_6,
+    JE_1_14_4_PRE_RELEASE_5,
+    JE_1_14_4_PRE_RELEASE_4,
+    JE_1_14_4_PRE_RELEASE_3,
+    JE_1_14_4_PRE_RELEASE_2,
+    JE_1_14_4_PRE_RELEASE_1,
+    JE_1_14_3,
+    JE_1_14_3_PRE_RELEASE_4,
+    JE_1_14_3_PRE_RELEASE_3,
+    JE_1_14_3_PRE_RELEASE_2,
+    JE_1_14_3_PRE_RELEASE_1,
+    JE_1_14_2,
+    JE_1_14_2_PRE_RELEASE_4,
+    JE_1_14_2_PRE_RELEASE_3,
+    JE_1_14_2_PRE_RELEASE_2,
+    JE_1_14_2_PRE_RELEASE_1,
+    JE_1_14_1,
+    JE_1_14_1_PRE_RELEASE_2,
+    JE_1_14_1_PRE_RELEASE_1,
+    JE_1_14,
+    JE_1_14_PRE_RELEASE_5,
+    JE_1_14_PRE_RELEASE_4,
+    JE_1_14_PRE_RELEASE_3,
+    JE_1_14_PRE_RELEASE_2,
+    JE_1_14_PRE_RELEASE_1,
+    JE_19W14B,
+    JE_19W14A,
+    JE_19W13B,
+    JE_19W13A,
+    JE_19W12B,
+    JE_19W12A,
+    JE_19W11B,
+    JE_19W11A,
+    JE_19W09A,
+    JE_19W08B,
+    JE_19W08A,
+    JE_19W07A,
+    JE_19W06A,
+    JE_19W05A,
+    JE_19W04B,
+    JE_19W04A,
+    JE_19W03C,
+    JE_19W03B,
+    JE_19W03A,
+    JE_19W02A,
+    JE_18W50A,
+    JE_18W49A,
+    JE_18W48B,
+    JE_18W48A,
+    JE_18W47B,
+    JE_18W47A,
+    JE_18W46A,
+    JE_18W45A,
+    JE_18W44A,
+    JE_18W43C,
+    JE_18W43B,
+    JE_18W43A,
+    JE_1_13_2,
+    JE_1_13_2_PRE2,
+    JE_1_13_2_PRE1,
+    JE_1_13_1,
+    JE_1_13_1_PRE2,
+    JE_1_13_1_PRE1,
+    JE_18W33A,
+    JE_18W32A,
+    JE_18W31A,
+    JE_18W30B,
+    JE_18W30A,
+    JE_1_13,
+    JE_1_13_PRE10,
+    JE_1_13_PRE9,
+    JE_1_13_PRE8,
+    JE_1_13_PRE7,
+    JE_1_13_PRE6,
+    JE_1_13_PRE5,
+    JE_1_13_PRE4,
+    JE_1_13_PRE3,
+    JE_1_13_PRE2,
+    JE_1_13_PRE1,
+    JE_18W22C,
+    JE_18W22B,
+    JE_18W22A,
+    JE_18W21B,
+    JE_18W21A,
+    JE_18W20C,
+    JE_18W20B,
+    JE_18W20A,
+    JE_18W19B,
+    JE_18W19A,
+    JE_18W16A,
+    JE_18W15A,
+    JE_18W14B,
+    JE_18W14A,
+    JE_18W11A,
+    JE_18W10D,
+    JE_18W10C,
+    JE_18W10B,
+    JE_18W10A,
+    JE_18W09A,
+    JE_18W08B,
+    JE_18W08A,
+    JE_18W07C,
+    JE_18W07B,
+    JE_18W07A,
+    JE_18W06A,
+    JE_18W05A,
+    JE_18W03B,
+    JE_18W03A,
+    JE_18W02A,
+    JE_18W01A,
+    JE_17W50A,
+    JE_17W49B,
+    JE_17W49A,
+    JE_17W48A,
+    JE_17W47B,
+    JE_17W47A,
+    JE_17W46A,
+    JE_17W45B,
+    JE_17W45A,
+    JE_17W43B,
+    JE_17W43A,
+    JE_1_12_2,
+    JE_1_12_2_PRE2,
+    JE_1_12_2_PRE1,
+    JE_1_12_1,
+    JE_1_12_1_PRE1,
+    JE_17W31A,
+    JE_1_12,
+    JE_1_12_PRE7,
+    JE_1_12_PRE6,
+    JE_1_12_PRE5,
+    JE_1_12_PRE4,
+    JE_1_12_PRE3,
+    JE_1_12_PRE2,
+    JE_1_12_PRE1,
+    JE_17W18B,
+    JE_17W18A,
+    JE_17W17B,
+    JE_17W17A,
+    JE_17W16B,
+    JE_17W16A,
+    JE_17W15A,
+    JE_17W14A,
+    JE_17W13B,
+    JE_17W13A,
+    JE_17W06A,
+    JE_1_11_2,
+    JE_1_11_1,
+    JE_16W50A,
+    JE_1_11,
+    JE_1_11_PRE1,
+    JE_16W44A,
+    JE_16W43A,
+    JE_16W42A,
+    JE_16W41A,
+    JE_16W40A,
+    JE_16W39C,
+    JE_16W39B,
+    JE_16W39A,
+    JE_16W38A,
+    JE_16W36A,
+    JE_16W35A,
+    JE_16W33A,
+    JE_16W32B,
+    JE_16W32A,
+    JE_1_10_2,
+    JE_1_10_1,
+    JE_1_10,
+    JE_1_10_PRE2,
+    JE_1_10_PRE1,
+    JE_16W21B,
+    JE_16W21A,
+    JE_16W20A,
+    JE_1_9_4,
+    JE_1_9_3,
+    JE_1_9_3_PRE3,
+    JE_1_9_3_PRE2,
+    JE_1_9_3_PRE1,
+    JE_16W15B,
+    JE_16W15A,
+    JE_16W14A,
+    JE_1_9_2,
+    JE_1_9_1,
+    JE_1_9_1_PRE3,
+    JE_1_9_1_PRE2,
+    JE_1_9_1_PRE1,
+    JE_1_9,
+    JE_1_9_PRE4,
+    JE_1_9_PRE3,
+    JE_1_9_PRE2,
+    JE_1_9_PRE1,
+    JE_16W07B,
+    JE_16W07A,
+    JE_16W06A,
+    JE_16W05B,
+    JE_16W05A,
+    JE_16W04A,
+    JE_16W03A,
+    JE_16W02A,
+    JE_15W51B,
+    JE_15W51A,
+    JE_15W50A,
+    JE_15W49B,
+    JE_15W49A,
+    JE_15W47C,
+    JE_15W47B,
+    JE_15W47A,
+    JE_15W46A,
+    JE_15W45A,
+    JE_15W44B,
+    JE_15W44A,
+    JE_15W43C,
+    JE_15W43B,
+    JE_15W43A,
+    JE_15W42A,
+    JE_15W41B,
+    JE_15W41A,
+    JE_15W40B,
+    JE_15W40A,
+    JE_15W39C,
+    JE_15W39B,
+    JE_15W39A,
+    JE_15W38B,
+    JE_15W38A,
+    JE_15W37A,
+    JE_15W36D,
+    JE_15W36C,
+    JE_15W36B,
+    JE_15W36A,
+    JE_15W35E,
+    JE_15W35D,
+    JE_15W35C,
+    JE_15W35B,
+    JE_15W35A,
+    JE_15W34D,
+    JE_15W34C,
+    JE_15W34B,
+    JE_15W34A,
+    JE_15W33C,
+    JE_15W33B,
+    JE_15W33A,
+    JE_15W32C,
+    JE_15W32B,
+    JE_15W32A,
+];
+
+/// Every known Bedrock Edition version, newest first
+const BEDROCK_ALL: &[BedrockVersion] = &[
+    BE_1_20_10,
+    BE_1_20_0,
+    BE_1_19_80,
+    BE_1_19_70,
+    BE_1_19_50,
+    BE_1_19_0,
+    BE_1_18_30,
+    BE_1_18_0,
+    BE_1_17_0,
+    BE_1_16_100,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orders_by_data_version() {
+        assert!(JE_1_12_2 < JE_1_13);
+        assert!(JE_1_20_PRE_RELEASE_7 < JE_1_20_RELEASE_CANDIDATE_1);
+        assert!(JE_1_20 < JE_1_20_1);
+        assert_eq!(JE_1_13.max(JE_1_20_1), JE_1_20_1);
+    }
+
+    #[test]
+    fn lists_every_version_newest_first() {
+        let all: Vec<McVersion> = McVersion::all().collect();
+        assert_eq!(all.first(), Some(&JE_1_20_1));
+        // Test builds and old snapshots are listed by release date, which doesn't
+        // always follow their DataVersion, so only the releases are compared
+        let releases: Vec<i32> = all
+            .iter()
+            .filter(|version| version.release_type() == ReleaseType::Release)
+            .map(|version| version.data_version())
+            .collect();
+        assert!(releases.windows(2).all(|pair| pair[0] > pair[1]));
+
+        for version in all.iter() {
+            assert_eq!(McVersion::from_name(version.name()), Some(*version));
+        }
+    }
+
+    #[test]
+    fn looks_up_names() {
+        assert_eq!(
+            McVersion::from_name(" 1.20-PRE7 "),
+            Some(JE_1_20_PRE_RELEASE_7)
+        );
+        assert_eq!("23w12a".parse(), Ok(JE_23W12A));
+        assert_eq!(JE_23W12A.release_type(), ReleaseType::Snapshot);
+        assert_eq!(JE_1_20_1.to_string(), "1.20.1");
+        assert_eq!(
+            "1.99".parse::<McVersion>(),
+            Err("Unknown Minecraft version \"1.99\"".to_string())
+        );
+    }
+
+    #[test]
+    fn data_versions_prefer_non_experimental_builds() {
+        assert_eq!(McVersion::from_data_version(1519), Some(JE_1_13));
+        assert_eq!(McVersion::from_data_version(-5), None);
+
+        for version in McVersion::all() {
+            let found = McVersion::from_data_version(version.data_version()).unwrap();
+            assert_eq!(found.data_version(), version.data_version());
+            let shared = McVersion::all().any(|other| {
+                other.data_version() == version.data_version()
+                    && other.release_type() != ReleaseType::Experimental
+            });
+            if shared {
+                assert_ne!(found.release_type(), ReleaseType::Experimental);
+            }
+        }
+    }
+
+    #[test]
+    fn packs_bedrock_block_versions() {
+        assert_eq!(BE_1_20_10.block_version(), 0x01140A00);
+        assert_eq!(BedrockVersion::from_name("1.19.80"), Some(BE_1_19_80));
+        assert_eq!(BedrockVersion::all().next(), Some(BE_1_20_10));
+        assert!(BE_1_16_100 < BE_1_17_0);
+    }
+}
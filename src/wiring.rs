@@ -0,0 +1,768 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use crate::block_state::BlockState;
+use crate::mcschematic::{Coords, MCSchematic};
+
+/// Horizontal directions with their offsets, in the order of the game's direction ids
+const DIRECTIONS: [(&str, (i16, i16)); 4] = [
+    ("north", (0, -1)),
+    ("east", (1, 0)),
+    ("south", (0, 1)),
+    ("west", (-1, 0)),
+];
+
+/// Lowest signal strength a wire gets a repeater at. Leaves room for a few cells
+/// where repeaters can't go, such as slopes and branches, before the signal dies out
+const REPEATER_THRESHOLD: u8 = 5;
+
+/// How routed wire changes its height
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Tower {
+    /// Staircases of support blocks only, one block up or down per step
+    #[default]
+    None,
+    /// Staircases of top smooth stone slabs instead of the support block. Slabs don't
+    /// cut the wire below them, so the wire can wind up or down in a tight spiral
+    Slabs,
+    /// Staircases, or columns of blocks and redstone torches going straight up
+    /// three blocks at a time. Torch towers only carry signals upwards
+    Torches,
+}
+
+/// Options for MCSchematic::route_redstone
+#[derive(Clone, Debug)]
+pub struct RouteOptions {
+    /// Block placed below the wire where there's nothing yet, and used by torch towers
+    pub support_block: String,
+    /// How the wire goes up and down, see Tower
+    pub tower: Tower,
+    /// Signal strength at the start of the wire, 15 next to a repeater or a lever
+    pub strength: u8,
+    /// How far the wire may go around obstacles, outside of the box between its ends
+    pub margin: i16,
+}
+
+impl Default for RouteOptions {
+    fn default() -> Self {
+        RouteOptions {
+            support_block: "minecraft:smooth_stone".to_string(),
+            tower: Tower::None,
+            strength: 15,
+            margin: 8,
+        }
+    }
+}
+
+/// A cell of a route being searched, with the signal strength of the wire there
+type RouteState = (Coords, u8);
+
+/// A step of a route, from the previous cell to the next one
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Wire,
+    /// Goes through a torch tower standing on the given cell
+    Tower(Coords),
+}
+
+/// Lays redstone wire along paths of neighbouring cells, each step going one block
+/// north, east, south or west and at most one block up or down. Paths starting at
+/// the same cell form a tree and share the blocks of their common part. A repeater
+/// replaces the wire before the signal gets too weak, wherever the path goes straight
+///
+/// paths - the cells of every path, from the one next to the power source to the one
+/// next to what it powers
+///
+/// strength - signal strength of the first cell, 15 next to a repeater or a lever
+///
+/// support_block - block placed below the wire where there's nothing yet
+pub(crate) fn lay_paths(
+    schematic: &mut MCSchematic,
+    paths: &[Vec<Coords>],
+    strength: u8,
+    support_block: &str,
+) {
+    // Cells the paths leave in different directions can't hold repeaters
+    let mut next_cells: HashMap<Coords, HashSet<Coords>> = HashMap::new();
+    for path in paths {
+        for pair in path.windows(2) {
+            next_cells.entry(pair[0]).or_default().insert(pair[1]);
+        }
+    }
+
+    let mut wire = vec![];
+    for path in paths {
+        let mut signal = strength;
+        for (index, &cell) in path.iter().enumerate() {
+            let straight = index > 0
+                && index + 1 < path.len()
+                && is_straight(path[index - 1], cell, path[index + 1])
+                && next_cells.get(&cell).is_some_and(|next| next.len() == 1);
+
+            place_support(schematic, cell, support_block);
+            if straight && signal <= REPEATER_THRESHOLD {
+                let facing = direction_name(cell, path[index - 1]);
+                schematic.set_block(
+                    cell,
+                    &format!("minecraft:repeater[delay=1,facing={}]", facing),
+                );
+                signal = 15;
+            } else {
+                if !schematic.get_block(cell).contains("repeater") {
+                    schematic.set_block(cell, "minecraft:redstone_wire");
+                    wire.push(cell);
+                }
+                signal = signal.saturating_sub(1);
+            }
+        }
+    }
+
+    connect_wire(schematic, &wire);
+}
+
+impl MCSchematic {
+    /// Runs redstone wire between two cells along the shortest path through free space,
+    /// laying it on support blocks and adding repeaters before the signal dies out.
+    /// Placed blocks are avoided, and so is any redstone the wire would connect to
+    /// on the way, including the wire's own further along. Slopes get flat landings for
+    /// repeaters where the signal would die out otherwise. Returns the cells holding wire
+    /// or repeaters, in order, or an error if no route the signal makes it through is free
+    ///
+    /// from - first cell of the wire, next to what powers it
+    ///
+    /// to - last cell of the wire, next to what it powers
+    ///
+    /// options - support block, towers and search area, see RouteOptions
+    pub fn route_redstone(
+        &mut self,
+        from: Coords,
+        to: Coords,
+        options: &RouteOptions,
+    ) -> Result<Vec<Coords>, String> {
+        for end in [from, to] {
+            if self.get_block(end) != "minecraft:air" {
+                return Err(format!("{:?} isn't free for the wire", end));
+            }
+        }
+        let support_block = match options.tower {
+            Tower::Slabs => "minecraft:smooth_stone_slab[type=top]",
+            _ => options.support_block.as_str(),
+        };
+
+        let steps = self.find_route(from, to, options)?;
+
+        // Split the route into stretches of wire between the torch towers
+        let mut stretches = vec![(vec![from], options.strength)];
+        for &(cell, step) in steps.iter() {
+            if let Step::Tower(base) = step {
+                let block = options.support_block.as_str();
+                for (dy, block) in [block, "minecraft:redstone_torch"]
+                    .repeat(2)
+                    .iter()
+                    .enumerate()
+                {
+                    self.set_block((base.0, base.1 + dy as i16, base.2), block);
+                }
+                stretches.push((vec![], 15));
+            }
+            if let Some((stretch, _)) = stretches.last_mut() {
+                stretch.push(cell);
+            }
+        }
+
+        for (stretch, strength) in stretches.iter() {
+            lay_paths(
+                self,
+                std::slice::from_ref(stretch),
+                *strength,
+                support_block,
+            );
+        }
+
+        Ok(stretches
+            .into_iter()
+            .flat_map(|(stretch, _)| stretch)
+            .collect())
+    }
+
+    /// Finds the cheapest route with A*, returning every cell after the first one
+    /// together with the step leading to it
+    fn find_route(
+        &self,
+        from: Coords,
+        to: Coords,
+        options: &RouteOptions,
+    ) -> Result<Vec<(Coords, Step)>, String> {
+        let margin = options.margin;
+        let lowest = (
+            from.0.min(to.0) - margin,
+            from.1.min(to.1) - margin,
+            from.2.min(to.2) - margin,
+        );
+        let highest = (
+            from.0.max(to.0) + margin,
+            from.1.max(to.1) + margin,
+            from.2.max(to.2) + margin,
+        );
+        let inside = |(x, y, z): Coords| {
+            (lowest.0..=highest.0).contains(&x)
+                && (lowest.1..=highest.1).contains(&y)
+                && (lowest.2..=highest.2).contains(&z)
+        };
+        let estimate =
+            |(x, y, z): Coords| ((x - to.0).abs() + (y - to.1).abs() + (z - to.2).abs()) as u32;
+
+        // Cells are searched together with the signal strength the wire gets there,
+        // so the route only goes where the signal makes it. Of the routes that seem
+        // equally good, the longest ones go first, as they're the closest to the end
+        let start = (from, options.strength);
+        let mut queue = BinaryHeap::from([Reverse((estimate(from), Reverse(0), start))]);
+        let mut costs = HashMap::from([(start, 0)]);
+        let mut came_from: HashMap<RouteState, (RouteState, Step)> = HashMap::new();
+
+        while let Some(Reverse((_, Reverse(cost), state))) = queue.pop() {
+            if costs.get(&state).is_some_and(|&best| best < cost) {
+                continue;
+            }
+            let (cell, signal) = state;
+            let route = route_to(state, &came_from);
+            if cell == to {
+                return Ok(route);
+            }
+
+            // The route may not run into the blocks it places itself
+            let (redstone, claimed) = route_blocks(from, &route);
+            let previous = came_from.get(&state).map(|&((previous, _), _)| previous);
+            for (next, step, step_cost) in self.route_steps(cell, previous, options.tower) {
+                if !inside(next) || (next != to && self.near_redstone(next, from, to)) {
+                    continue;
+                }
+                let Some(next_signal) = next_signal(previous, cell, next, step, signal) else {
+                    continue;
+                };
+                let mut placed = vec![next];
+                if let Step::Tower(base) = step {
+                    placed.extend((0..4).map(|dy| (base.0, base.1 + dy, base.2)));
+                }
+                let runs_into_route = placed.iter().any(|&block| {
+                    claimed.contains(&block)
+                        || claimed.contains(&(block.0, block.1 - 1, block.2))
+                        || redstone
+                            .iter()
+                            .any(|&other| other != cell && touches(other, block))
+                });
+                if runs_into_route {
+                    continue;
+                }
+                let next_state = (next, next_signal);
+                let next_cost = cost + step_cost;
+                if costs
+                    .get(&next_state)
+                    .is_some_and(|&best| best <= next_cost)
+                {
+                    continue;
+                }
+                costs.insert(next_state, next_cost);
+                came_from.insert(next_state, (state, step));
+                queue.push(Reverse((
+                    next_cost + estimate(next),
+                    Reverse(next_cost),
+                    next_state,
+                )));
+            }
+        }
+
+        Err(format!("No free route from {:?} to {:?}", from, to))
+    }
+
+    /// Returns the cells the wire can go to from the given one, with the step and its cost.
+    /// Going up or down costs more than going straight, so the wire stays flat where it can
+    fn route_steps(
+        &self,
+        (x, y, z): Coords,
+        previous: Option<Coords>,
+        tower: Tower,
+    ) -> Vec<(Coords, Step, u32)> {
+        let free = |coords: Coords| self.get_block(coords) == "minecraft:air";
+        let can_support = |coords: Coords| free(coords) || supports_wire(self.get_block(coords));
+        let cuts = |coords: Coords| !free(coords) && is_solid(self.get_block(coords));
+
+        let mut steps = vec![];
+        for &(_, (dx, dz)) in DIRECTIONS.iter() {
+            let side = (x + dx, y, z + dz);
+
+            if free(side) && can_support((side.0, y - 1, side.2)) {
+                steps.push((side, Step::Wire, 1));
+            }
+
+            // Up onto the support of the next cell, which the block above this one would cut
+            let up = (side.0, y + 1, side.2);
+            if free(up) && can_support(side) && !cuts((x, y + 1, z)) {
+                steps.push((up, Step::Wire, 2));
+            }
+
+            // Down past the side of this cell, which has to stay open
+            let down = (side.0, y - 1, side.2);
+            if free(side) && free(down) && can_support((side.0, y - 2, side.2)) {
+                steps.push((down, Step::Wire, 2));
+            }
+
+            // A torch tower has to be entered straight, so the wire points into its base
+            let straight =
+                previous.is_none_or(|(px, py, pz)| (x - px, z - pz) == (dx, dz) && py == y);
+            if tower == Tower::Torches
+                && straight
+                && (0..4).all(|dy| free((side.0, y + dy, side.2)))
+            {
+                for &(_, (ex, ez)) in DIRECTIONS.iter() {
+                    let exit = (side.0 + ex, y + 3, side.2 + ez);
+                    if (ex, ez) != (-dx, -dz) && free(exit) && can_support((exit.0, y + 2, exit.2))
+                    {
+                        steps.push((exit, Step::Tower(side), 5));
+                    }
+                }
+            }
+        }
+
+        steps
+    }
+
+    /// Whether wire at the given cell would connect to redstone that isn't part of the route,
+    /// leaving out the neighbours of its ends
+    fn near_redstone(&self, (x, y, z): Coords, from: Coords, to: Coords) -> bool {
+        DIRECTIONS.iter().any(|&(_, (dx, dz))| {
+            (-1..=1).any(|dy| {
+                let neighbour = (x + dx, y + dy, z + dz);
+                let block = self.get_block(neighbour);
+                if block == "minecraft:air" {
+                    return false;
+                }
+                let adjacent_to_end = [from, to].iter().any(|&end| {
+                    (end.0 - neighbour.0).abs()
+                        + (end.1 - neighbour.1).abs()
+                        + (end.2 - neighbour.2).abs()
+                        <= 1
+                });
+                !adjacent_to_end && is_redstone(block) && (dy == 0 || is_wire(block))
+            })
+        })
+    }
+}
+
+/// Returns the steps of the route found so far up to the given cell, in order
+fn route_to(
+    state: RouteState,
+    came_from: &HashMap<RouteState, (RouteState, Step)>,
+) -> Vec<(Coords, Step)> {
+    let mut steps = vec![];
+    let mut current = state;
+    while let Some(&(previous, step)) = came_from.get(&current) {
+        steps.push((current.0, step));
+        current = previous;
+    }
+    steps.reverse();
+    steps
+}
+
+/// Returns the signal strength of the wire at the next cell of a route, the way lay_paths
+/// places it: the cell becomes a repeater if the signal got weak and the wire goes straight
+/// through it, and torch towers start over at 15. None if the signal would die out, which
+/// keeps slopes from going on for longer than the signal lasts without a landing
+fn next_signal(
+    previous: Option<Coords>,
+    cell: Coords,
+    next: Coords,
+    step: Step,
+    signal: u8,
+) -> Option<u8> {
+    let straight = previous.is_some_and(|previous| is_straight(previous, cell, next));
+    match step {
+        Step::Tower(_) => Some(15),
+        Step::Wire if straight && signal <= REPEATER_THRESHOLD => Some(15),
+        Step::Wire => signal.checked_sub(1).filter(|&signal| signal > 0),
+    }
+}
+
+/// Returns the blocks a route places: the cells of its wire and torch towers, and the
+/// cells it needs for itself, being the support blocks and the cells above the lower
+/// end of every slope, which have to stay open for the wire to connect up
+fn route_blocks(from: Coords, steps: &[(Coords, Step)]) -> (Vec<Coords>, HashSet<Coords>) {
+    let below = |(x, y, z): Coords| (x, y - 1, z);
+    let mut redstone = vec![from];
+    let mut claimed = HashSet::from([below(from)]);
+
+    let mut previous = from;
+    for &(cell, step) in steps {
+        match step {
+            Step::Tower(base) => {
+                for dy in 0..4 {
+                    redstone.push((base.0, base.1 + dy, base.2));
+                    claimed.insert((base.0, base.1 + dy, base.2));
+                }
+            }
+            Step::Wire if cell.1 != previous.1 => {
+                let lower = if cell.1 < previous.1 { cell } else { previous };
+                claimed.insert((lower.0, lower.1 + 1, lower.2));
+            }
+            Step::Wire => {}
+        }
+        redstone.push(cell);
+        claimed.insert(below(cell));
+        previous = cell;
+    }
+
+    (redstone, claimed)
+}
+
+/// Whether redstone at the two cells gets in the way of each other: wire next to the other
+/// one or a block higher or lower, which it would connect to, or one of them in the column
+/// of the other, where it would take the other's support or cut its connections
+fn touches(a: Coords, b: Coords) -> bool {
+    let (dx, dy, dz) = ((a.0 - b.0).abs(), (a.1 - b.1).abs(), (a.2 - b.2).abs());
+    (dx + dz == 1 && dy <= 1) || (dx + dz == 0 && dy <= 2)
+}
+
+/// Sets the connections of redstone wire to its neighbours, as the game would after
+/// placing it. Pasted blocks don't get updated, so wire without its connections
+/// stays a dot powering nothing around it
+pub(crate) fn connect_wire(schematic: &mut MCSchematic, cells: &[Coords]) {
+    let mut connected = vec![];
+    for &cell in cells {
+        if !is_wire(schematic.get_block(cell)) {
+            continue;
+        }
+
+        let mut state = BlockState::parse(schematic.get_block(cell));
+        for (name, side) in wire_sides(schematic, cell) {
+            state.set_property(name, side);
+        }
+        connected.push((cell, state.to_string()));
+    }
+
+    for (cell, block) in connected {
+        schematic.set_block(cell, &block);
+    }
+}
+
+/// Returns the connections the wire at the given cell gets from the game towards each
+/// horizontal direction, as pairs of direction and "side", "up" or "none"
+pub(crate) fn wire_sides(
+    schematic: &MCSchematic,
+    cell: Coords,
+) -> Vec<(&'static str, &'static str)> {
+    let mut sides: Vec<(&str, &str)> = DIRECTIONS
+        .iter()
+        .map(|&(name, offset)| (name, wire_connection(schematic, cell, name, offset)))
+        .collect();
+
+    // Wire going only one way still points both ways, and unconnected wire is a cross
+    let count = sides.iter().filter(|(_, side)| *side != "none").count();
+    if count <= 1 {
+        let opposite = sides
+            .iter()
+            .position(|(_, side)| *side != "none")
+            .map(|index| (index + 2) % 4);
+        for (index, (_, side)) in sides.iter_mut().enumerate() {
+            if count == 0 || Some(index) == opposite {
+                *side = "side";
+            }
+        }
+    }
+
+    sides
+}
+
+/// Returns how the wire at the given cell connects towards one side: "side", "up" or "none"
+fn wire_connection(
+    schematic: &MCSchematic,
+    (x, y, z): Coords,
+    direction: &str,
+    (dx, dz): (i16, i16),
+) -> &'static str {
+    let neighbour = schematic.get_block((x + dx, y, z + dz));
+    if connects_to_wire(neighbour, direction) {
+        return "side";
+    }
+
+    let above_neighbour = schematic.get_block((x + dx, y + 1, z + dz));
+    if supports_wire(neighbour)
+        && is_wire(above_neighbour)
+        && !is_solid(schematic.get_block((x, y + 1, z)))
+    {
+        return "up";
+    }
+
+    let below_neighbour = schematic.get_block((x + dx, y - 1, z + dz));
+    if !is_solid(neighbour) && is_wire(below_neighbour) {
+        return "side";
+    }
+
+    "none"
+}
+
+/// Whether wire next to the given block, on the given side of the wire, connects to it
+fn connects_to_wire(block: &str, direction: &str) -> bool {
+    let state = BlockState::parse(block);
+    let name = state.id.trim_start_matches("minecraft:");
+
+    match name {
+        "redstone_wire"
+        | "comparator"
+        | "redstone_torch"
+        | "redstone_wall_torch"
+        | "lever"
+        | "redstone_block"
+        | "target"
+        | "daylight_detector"
+        | "tripwire_hook" => true,
+        "repeater" => state
+            .property("facing")
+            .is_some_and(|facing| facing == direction || facing == opposite(direction)),
+        "observer" => state.property("facing") == Some(direction),
+        _ => name.ends_with("_button") || name.ends_with("_pressure_plate"),
+    }
+}
+
+/// Full blocks redstone powers, by their id without the namespace
+#[rustfmt::skip]
+const SOLID: &[&str] = &[
+    "stone", "granite", "diorite", "andesite", "deepslate", "calcite", "tuff", "dirt",
+    "coarse_dirt", "rooted_dirt", "podzol", "mycelium", "grass_block", "clay", "sand",
+    "red_sand", "gravel", "sandstone", "red_sandstone", "cobblestone", "bricks", "bedrock",
+    "obsidian", "crying_obsidian", "netherrack", "soul_sand", "soul_soil", "basalt",
+    "blackstone", "sculk", "pumpkin", "melon", "jack_o_lantern", "shroomlight", "sponge",
+    "wet_sponge", "bookshelf", "chiseled_bookshelf", "crafting_table", "cartography_table",
+    "fletching_table", "smithing_table", "loom", "furnace", "blast_furnace", "smoker",
+    "dispenser", "dropper", "crafter", "barrel", "jukebox", "target", "redstone_lamp",
+    "packed_ice", "blue_ice", "lodestone", "respawn_anchor", "budding_amethyst",
+    "ancient_debris", "purpur_pillar", "quartz_pillar", "smooth_quartz", "prismarine",
+    "dark_prismarine", "crimson_stem", "warped_stem", "stripped_crimson_stem",
+    "stripped_warped_stem", "mushroom_stem", "ochre_froglight", "verdant_froglight",
+    "pearlescent_froglight",
+];
+
+/// Families of full blocks redstone powers, by the end of their id
+#[rustfmt::skip]
+const SOLID_SUFFIXES: &[&str] = &[
+    "_planks", "_log", "_wood", "_hyphae", "_nylium", "_wool", "_concrete",
+    "_concrete_powder", "terracotta", "_ore", "_bricks", "_tiles", "_stone", "_granite",
+    "_diorite", "_andesite", "_deepslate", "_tuff", "_sandstone", "_cobblestone",
+    "_blackstone", "_basalt", "_copper", "_pumpkin", "_mud", "_block",
+];
+
+/// Blocks whose id ends like a family of SOLID_SUFFIXES but that redstone doesn't power
+const NOT_SOLID: &[&str] = &["redstone_block", "slime_block", "honey_block"];
+
+/// Blocks that aren't solid but have a full top, which wire can sit on
+#[rustfmt::skip]
+const WIRE_SUPPORTS: &[&str] = &[
+    "glass", "tinted_glass", "glowstone", "sea_lantern", "ice", "hopper", "redstone_block",
+    "observer", "piston", "sticky_piston", "slime_block",
+];
+
+/// Whether the block is a full block that redstone powers, which wire can sit on
+/// and which cuts wire going up past it. Double slabs count, other blocks that
+/// aren't full or let light through, such as glass, chests or pistons, don't
+pub(crate) fn is_solid(block: &str) -> bool {
+    let state = BlockState::parse(block);
+    let name = state.id.trim_start_matches("minecraft:");
+
+    if name.ends_with("_slab") {
+        return state.property("type") == Some("double");
+    }
+    !NOT_SOLID.contains(&name)
+        && (SOLID.contains(&name) || SOLID_SUFFIXES.iter().any(|suffix| name.ends_with(suffix)))
+}
+
+/// Whether wire can sit on the block: a solid block, glass, glowstone, a top slab,
+/// upside down stairs and a few other blocks with a full top
+pub(crate) fn supports_wire(block: &str) -> bool {
+    let state = BlockState::parse(block);
+    let name = state.id.trim_start_matches("minecraft:");
+
+    is_solid(block)
+        || WIRE_SUPPORTS.contains(&name)
+        || name.ends_with("_stained_glass")
+        || name.ends_with("_slab") && state.property("type") != Some("bottom")
+        || name.ends_with("_stairs") && state.property("half") == Some("top")
+}
+
+/// Whether the block is a redstone component wire could connect to or power
+pub(crate) fn is_redstone(block: &str) -> bool {
+    is_wire(block) || connects_to_wire(block, "north") || connects_to_wire(block, "east")
+}
+
+pub(crate) fn is_wire(block: &str) -> bool {
+    BlockState::parse(block).id == "minecraft:redstone_wire"
+}
+
+/// Places the support block below the cell if there's nothing there
+fn place_support(schematic: &mut MCSchematic, (x, y, z): Coords, support_block: &str) {
+    if schematic.get_block((x, y - 1, z)) == "minecraft:air" {
+        schematic.set_block((x, y - 1, z), support_block);
+    }
+}
+
+/// Whether the cell goes on in the direction it was entered from, on the same height
+fn is_straight(previous: Coords, cell: Coords, next: Coords) -> bool {
+    previous.1 == cell.1
+        && cell.1 == next.1
+        && (cell.0 - previous.0, cell.2 - previous.2) == (next.0 - cell.0, next.2 - cell.2)
+}
+
+/// Returns the horizontal direction going from one cell to its neighbour
+fn direction_name(from: Coords, to: Coords) -> &'static str {
+    let offset = ((to.0 - from.0).signum(), (to.2 - from.2).signum());
+    DIRECTIONS
+        .iter()
+        .find(|(_, direction)| *direction == offset)
+        .map_or("north", |(name, _)| name)
+}
+
+fn opposite(direction: &str) -> &'static str {
+    match direction {
+        "north" => "south",
+        "east" => "west",
+        "south" => "north",
+        "west" => "east",
+        "up" => "down",
+        _ => "up",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::Simulation;
+
+    const LEVER: &str = "minecraft:lever[face=floor,facing=east,powered=false]";
+
+    /// Routes wire from a lever next to `from` and returns the signal arriving at `to`
+    fn routed_signal(from: Coords, to: Coords, options: &RouteOptions) -> u8 {
+        let mut schematic = MCSchematic::new();
+        let lever = (from.0, from.1, from.2 - 1);
+        schematic.set_block((lever.0, lever.1 - 1, lever.2), "minecraft:stone");
+        schematic.set_block(lever, LEVER);
+        let cells = schematic.route_redstone(from, to, options).unwrap();
+        assert_eq!((cells[0], cells[cells.len() - 1]), (from, to));
+
+        let mut simulation = Simulation::new(&schematic);
+        simulation.set_lever(lever, true).unwrap();
+        simulation.run_until_stable(200).unwrap();
+        simulation.signal_strength(to)
+    }
+
+    #[test]
+    fn long_wires_get_repeaters() {
+        let signal = routed_signal((0, 1, 0), (0, 1, 40), &RouteOptions::default());
+        assert!(signal > 0);
+    }
+
+    #[test]
+    fn climbs_get_landings_for_repeaters() {
+        let signal = routed_signal((0, 0, 0), (0, 20, 20), &RouteOptions::default());
+        assert!(signal > 0);
+    }
+
+    #[test]
+    fn fails_when_the_signal_cant_make_it() {
+        let mut schematic = MCSchematic::new();
+        let options = RouteOptions {
+            margin: 0,
+            ..Default::default()
+        };
+        // Without room around the staircase there's nowhere to put a landing
+        assert!(schematic
+            .route_redstone((0, 0, 0), (0, 20, 20), &options)
+            .is_err());
+    }
+
+    #[test]
+    fn lays_repeaters_on_straight_wire_only() {
+        let mut schematic = MCSchematic::new();
+        let path: Vec<Coords> = (0..12).map(|x| (x, 1, 0)).collect();
+        lay_paths(&mut schematic, &[path], 6, "minecraft:stone");
+
+        assert_eq!(
+            schematic.get_block((1, 1, 0)),
+            "minecraft:repeater[delay=1,facing=west]"
+        );
+        assert!(is_wire(schematic.get_block((0, 1, 0))));
+        assert!(is_wire(schematic.get_block((2, 1, 0))));
+        assert_eq!(schematic.get_block((5, 0, 0)), "minecraft:stone");
+    }
+
+    #[test]
+    fn only_full_conductive_blocks_are_solid() {
+        for block in [
+            "minecraft:stone",
+            "minecraft:oak_planks",
+            "minecraft:white_concrete",
+            "minecraft:barrel[facing=up]",
+            "minecraft:redstone_lamp[lit=false]",
+            "minecraft:smooth_stone_slab[type=double]",
+        ] {
+            assert!(is_solid(block), "{}", block);
+        }
+        for block in [
+            "minecraft:air",
+            "minecraft:chest[facing=north]",
+            "minecraft:piston[facing=up]",
+            "minecraft:poppy",
+            "minecraft:shulker_box",
+            "minecraft:red_shulker_box",
+            "minecraft:glass",
+            "minecraft:redstone_block",
+            "minecraft:smooth_stone_slab[type=top]",
+            "minecraft:oak_stairs[half=top]",
+            "minecraft:pointed_dripstone",
+        ] {
+            assert!(!is_solid(block), "{}", block);
+        }
+    }
+
+    #[test]
+    fn wire_sits_on_full_tops_only() {
+        for block in [
+            "minecraft:stone",
+            "minecraft:glass",
+            "minecraft:hopper",
+            "minecraft:smooth_stone_slab[type=top]",
+            "minecraft:oak_stairs[half=top]",
+        ] {
+            assert!(supports_wire(block), "{}", block);
+        }
+        for block in [
+            "minecraft:air",
+            "minecraft:poppy",
+            "minecraft:smooth_stone_slab[type=bottom]",
+            "minecraft:oak_stairs[half=bottom]",
+        ] {
+            assert!(!supports_wire(block), "{}", block);
+        }
+    }
+
+    #[test]
+    fn repeaters_only_power_lamps_through_solid_blocks() {
+        for (block, solid) in [
+            ("minecraft:stone", true),
+            ("minecraft:smooth_stone_slab[type=double]", true),
+            ("minecraft:chest[facing=north]", false),
+            ("minecraft:piston[facing=up]", false),
+            ("minecraft:poppy", false),
+            ("minecraft:shulker_box", false),
+        ] {
+            let mut schematic = MCSchematic::new();
+            schematic.set_block((0, 0, 0), "minecraft:stone");
+            schematic.set_block((0, 1, 0), LEVER);
+            schematic.set_block((1, 1, 0), "minecraft:repeater[delay=1,facing=west]");
+            schematic.set_block((2, 1, 0), block);
+            schematic.set_block((3, 1, 0), "minecraft:redstone_lamp[lit=false]");
+
+            let mut simulation = Simulation::new(&schematic);
+            simulation.set_lever((0, 1, 0), true).unwrap();
+            simulation.run_until_stable(20).unwrap();
+            assert_eq!(simulation.is_powered((3, 1, 0)), solid, "{}", block);
+        }
+    }
+}